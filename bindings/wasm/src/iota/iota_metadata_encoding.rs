@@ -11,12 +11,16 @@ use wasm_bindgen::prelude::*;
 #[repr(u8)]
 pub enum WasmStateMetadataEncoding {
   Json = 0,
+  JsonBrotli = 1,
+  Cbor = 2,
 }
 
 impl From<StateMetadataEncoding> for WasmStateMetadataEncoding {
   fn from(encoding: StateMetadataEncoding) -> Self {
     match encoding {
       StateMetadataEncoding::Json => Self::Json,
+      StateMetadataEncoding::JsonBrotli => Self::JsonBrotli,
+      StateMetadataEncoding::Cbor => Self::Cbor,
     }
   }
 }
@@ -25,6 +29,8 @@ impl From<WasmStateMetadataEncoding> for StateMetadataEncoding {
   fn from(encoding: WasmStateMetadataEncoding) -> Self {
     match encoding {
       WasmStateMetadataEncoding::Json => Self::Json,
+      WasmStateMetadataEncoding::JsonBrotli => Self::JsonBrotli,
+      WasmStateMetadataEncoding::Cbor => Self::Cbor,
     }
   }
 }
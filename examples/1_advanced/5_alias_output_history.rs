@@ -1,9 +1,6 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
-
-use anyhow::Context;
 use examples::create_did;
 use examples::random_stronghold_path;
 use examples::API_ENDPOINT;
@@ -15,23 +12,14 @@ use identity_iota::did::DID;
 use identity_iota::document::Service;
 use identity_iota::iota::block::address::Address;
 use identity_iota::iota::block::output::RentStructure;
+use identity_iota::iota::DIDHistoryEntry;
 use identity_iota::iota::IotaClientExt;
 use identity_iota::iota::IotaDID;
 use identity_iota::iota::IotaDocument;
-use identity_iota::iota::IotaIdentityClient;
 use identity_iota::iota::IotaIdentityClientExt;
 use identity_iota::verification::MethodRelationship;
-use iota_client::block::input::Input;
-use iota_client::block::output::dto::OutputMetadataDto;
-use iota_client::block::output::AliasId;
 use iota_client::block::output::AliasOutput;
 use iota_client::block::output::AliasOutputBuilder;
-use iota_client::block::output::Output;
-use iota_client::block::output::OutputId;
-use iota_client::block::payload::transaction::TransactionEssence;
-use iota_client::block::payload::Payload;
-use iota_client::block::Block;
-use iota_client::block::BlockId;
 use iota_client::secret::stronghold::StrongholdSecretManager;
 use iota_client::secret::SecretManager;
 use iota_client::Client;
@@ -82,90 +70,16 @@ async fn main() -> anyhow::Result<()> {
   }
 
   // ====================================
-  // Retrieving the Alias Output History
+  // Retrieving the DID Document History
   // ====================================
-  let mut alias_history: Vec<AliasOutput> = Vec::new();
-
-  // Step 0 - Get the latest Alias Output
-  let alias_id: AliasId = AliasId::from(client.resolve_did(&did).await?.id());
-  let (mut output_id, mut alias_output): (OutputId, AliasOutput) = client.get_alias_output(alias_id).await?;
-
-  while alias_output.state_index() != 0 {
-    // Step 1 - Get the current block
-    let block: Block = current_block(&client, &output_id).await?;
-    // Step 2 - Get the OutputId of the previous block
-    output_id = previous_output_id(&block)?;
-    // Step 3 - Get the Alias Output from the block
-    alias_output = block_alias_output(&block, &alias_id)?;
-    alias_history.push(alias_output.clone());
+  // Every state of the Alias Output yields a version of the DID document, ordered from oldest to latest.
+  let history: Vec<DIDHistoryEntry> = client.resolve_did_history(&did).await?;
+  for entry in history.iter() {
+    println!(
+      "State index {} in block {} at {}: {:#}",
+      entry.state_index, entry.block_id, entry.milestone_timestamp, entry.document
+    );
   }
 
-  println!("Alias History: {alias_history:?}");
-
   Ok(())
 }
-
-async fn current_block(client: &Client, output_id: &OutputId) -> anyhow::Result<Block> {
-  let output_metadata: OutputMetadataDto = client.get_output_metadata(output_id).await?;
-  let block_id: BlockId = BlockId::from_str(&output_metadata.block_id)?;
-  let block: Block = client.get_block(&block_id).await?;
-  Ok(block)
-}
-
-fn previous_output_id(block: &Block) -> anyhow::Result<OutputId> {
-  match block
-    .payload()
-    .context("expected a transaction payload, but no payload was found")?
-  {
-    Payload::Transaction(transaction_payload) => match transaction_payload.essence() {
-      TransactionEssence::Regular(regular_transaction_essence) => {
-        match regular_transaction_essence
-          .inputs()
-          .first()
-          .context("expected an utxo for the block, but no input was found")?
-        {
-          Input::Utxo(utxo_input) => Ok(*utxo_input.output_id()),
-          Input::Treasury(_) => {
-            anyhow::bail!("expected an utxo input, found a treasury input");
-          }
-        }
-      }
-    },
-    Payload::Milestone(_) | Payload::TreasuryTransaction(_) | Payload::TaggedData(_) => {
-      anyhow::bail!("expected a transaction payload");
-    }
-  }
-}
-
-fn block_alias_output(block: &Block, alias_id: &AliasId) -> anyhow::Result<AliasOutput> {
-  match block
-    .payload()
-    .context("expected a transaction payload, but no payload was found")?
-  {
-    Payload::Transaction(transaction_payload) => match transaction_payload.essence() {
-      TransactionEssence::Regular(regular_transaction_essence) => {
-        for (index, output) in regular_transaction_essence.outputs().iter().enumerate() {
-          match output {
-            Output::Alias(alias_output) => {
-              if &alias_output.alias_id().or_from_output_id(
-                &OutputId::new(
-                  transaction_payload.id(),
-                  index.try_into().context("output index must fit into a u16")?,
-                )
-                .context("failed to create OutputId")?,
-              ) == alias_id
-              {
-                return Ok(alias_output.clone());
-              }
-            }
-            Output::Basic(_) | Output::Foundry(_) | Output::Nft(_) | Output::Treasury(_) => continue,
-          }
-        }
-      }
-    },
-    Payload::Milestone(_) | Payload::TreasuryTransaction(_) | Payload::TaggedData(_) => {
-      anyhow::bail!("expected a transaction payload");
-    }
-  }
-  anyhow::bail!("no alias output has been found");
-}
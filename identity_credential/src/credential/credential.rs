@@ -12,6 +12,7 @@ use identity_core::common::OneOrMany;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
 use identity_core::convert::FmtJson;
+use identity_core::convert::ToJson;
 use identity_core::crypto::GetSignature;
 use identity_core::crypto::GetSignatureMut;
 use identity_core::crypto::Proof;
@@ -19,6 +20,7 @@ use identity_core::crypto::SetSignature;
 use identity_verification::MethodUriType;
 use identity_verification::TryMethod;
 
+use crate::credential::jwt_serialization::CredentialJwtClaims;
 use crate::credential::CredentialBuilder;
 use crate::credential::Evidence;
 use crate::credential::Issuer;
@@ -166,6 +168,21 @@ impl<T> Credential<T> {
   }
 }
 
+impl<T> Credential<T>
+where
+  T: ToOwned<Owned = T> + Serialize,
+{
+  /// Serializes the [`Credential`] as a JWT claims set
+  /// in accordance with [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/#json-web-token).
+  ///
+  /// The resulting string can be used as the payload of a JWS when issuing the credential.
+  pub fn serialize_jwt(&self) -> Result<String> {
+    CredentialJwtClaims::new(self)?
+      .to_json()
+      .map_err(|err| Error::JwtClaimsSetSerializationError(err.into()))
+  }
+}
+
 impl<T> Display for Credential<T>
 where
   T: Serialize,
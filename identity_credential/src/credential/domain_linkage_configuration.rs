@@ -103,53 +103,23 @@ impl DomainLinkageConfiguration {
 mod __fetch_configuration {
   use crate::credential::DomainLinkageConfiguration;
   use crate::error::Result;
+  use crate::fetch::fetch_bounded;
+  use crate::fetch::MAX_FETCH_RESPONSE_SIZE;
   use crate::Error::DomainLinkageError;
-  use futures::StreamExt;
   use identity_core::common::Url;
   use identity_core::convert::FromJson;
-  use reqwest::redirect::Policy;
-  use reqwest::Client;
 
   impl DomainLinkageConfiguration {
     /// Fetches the the DID Configuration resource via a GET request at the
     /// well-known location: "`domain`/.well-known/did-configuration.json".
     ///
-    /// The maximum size of the domain linkage configuration that can be retrieved with this method is 1 MiB.
-    /// To download larger ones, use your own HTTP client.
+    /// The maximum size of the domain linkage configuration that can be retrieved with this method is
+    /// [`MAX_FETCH_RESPONSE_SIZE`] (1 MiB). To download larger ones, use your own HTTP client.
     pub async fn fetch_configuration(mut domain: Url) -> Result<DomainLinkageConfiguration> {
-      if domain.scheme() != "https" {
-        return Err(DomainLinkageError("domain` does not use `https` protocol".into()));
-      }
       domain.set_path(".well-known/did-configuration.json");
-
-      let client: Client = reqwest::ClientBuilder::new()
-        .https_only(true)
-        .redirect(Policy::none())
-        .build()
-        .map_err(|err| DomainLinkageError(Box::new(err)))?;
-
-      // We use a stream so we can limit the size of the response to 1 MiB.
-      let mut stream: _ = client
-        .get(domain.to_string())
-        .send()
+      let json: Vec<u8> = fetch_bounded(&domain, MAX_FETCH_RESPONSE_SIZE)
         .await
-        .map_err(|err| DomainLinkageError(Box::new(err)))?
-        .bytes_stream();
-
-      let mut json: Vec<u8> = Vec::new();
-      while let Some(item) = stream.next().await {
-        match item {
-          Ok(bytes) => {
-            json.extend(bytes);
-            if json.len() > 1_048_576 {
-              return Err(DomainLinkageError(
-                "domain linkage configuration can not exceed 1 MiB".into(),
-              ));
-            }
-          }
-          Err(err) => return Err(DomainLinkageError(Box::new(err))),
-        }
-      }
+        .map_err(DomainLinkageError)?;
       let domain_linkage_configuration: DomainLinkageConfiguration =
         DomainLinkageConfiguration::from_json_slice(&json).map_err(|err| DomainLinkageError(Box::new(err)))?;
       Ok(domain_linkage_configuration)
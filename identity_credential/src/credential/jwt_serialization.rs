@@ -0,0 +1,377 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+
+use crate::credential::issuer::IssuerData;
+use crate::credential::Credential;
+use crate::credential::Evidence;
+use crate::credential::Issuer;
+use crate::credential::Policy;
+use crate::credential::RefreshService;
+use crate::credential::Schema;
+use crate::credential::Status;
+use crate::credential::Subject;
+use crate::Error;
+use crate::Result;
+
+/// Implementation of the JWT claims set of a [`Credential`] as described in the
+/// [VC Data Model](https://www.w3.org/TR/vc-data-model/#json-web-token).
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: ToOwned<Owned = T> + Serialize + DeserializeOwned"))]
+pub(crate) struct CredentialJwtClaims<'credential, T = Object>
+where
+  T: ToOwned<Owned = T> + Serialize,
+{
+  /// Represents the `expirationDate` encoded as a UNIX timestamp.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  exp: Option<i64>,
+  /// Represents the `issuer` property.
+  iss: Cow<'credential, Url>,
+  /// Represents the `issuanceDate` encoded as a UNIX timestamp.
+  nbf: i64,
+  /// Represents the `id` property of the credential.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  jti: Option<Cow<'credential, Url>>,
+  /// Represents the `id` of the single `credentialSubject`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sub: Option<Cow<'credential, Url>>,
+  /// The remaining properties of the credential.
+  vc: InnerCredential<'credential, T>,
+}
+
+impl<'credential, T> CredentialJwtClaims<'credential, T>
+where
+  T: ToOwned<Owned = T> + Serialize,
+{
+  /// Maps the properties of `credential` to the registered JWT claims where applicable.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the credential contains an embedded proof, as the JWS replaces it.
+  pub(crate) fn new(credential: &'credential Credential<T>) -> Result<Self> {
+    let Credential {
+      context,
+      id,
+      types,
+      credential_subject,
+      issuer,
+      issuance_date,
+      expiration_date,
+      credential_status,
+      credential_schema,
+      refresh_service,
+      terms_of_use,
+      evidence,
+      non_transferable,
+      properties,
+      proof,
+    } = credential;
+
+    if proof.is_some() {
+      return Err(Error::JwtClaimsSetSerializationError(
+        "credentials with an embedded proof cannot be encoded as a JWT".into(),
+      ));
+    }
+
+    // The `sub` claim can only represent the subject if there is exactly one.
+    let (sub, credential_subject): (Option<Cow<'credential, Url>>, Cow<'credential, OneOrMany<Subject>>) =
+      match credential_subject {
+        OneOrMany::One(Subject {
+          id: Some(subject_id),
+          properties,
+        }) => (
+          Some(Cow::Borrowed(subject_id)),
+          Cow::Owned(OneOrMany::One(Subject::with_properties(properties.clone()))),
+        ),
+        _ => (None, Cow::Borrowed(credential_subject)),
+      };
+
+    // Additional properties of an issuer in object form are kept in the `vc` claim.
+    let (iss, issuer_properties): (&Url, Option<&Object>) = match issuer {
+      Issuer::Url(url) => (url, None),
+      Issuer::Obj(data) => (
+        &data.id,
+        Some(&data.properties).filter(|properties| !properties.is_empty()),
+      ),
+    };
+
+    Ok(Self {
+      exp: expiration_date.map(|expiration_date| expiration_date.to_unix()),
+      iss: Cow::Borrowed(iss),
+      nbf: issuance_date.to_unix(),
+      jti: id.as_ref().map(Cow::Borrowed),
+      sub,
+      vc: InnerCredential {
+        context: Cow::Borrowed(context),
+        types: Cow::Borrowed(types),
+        credential_subject,
+        issuer: issuer_properties.map(Cow::Borrowed),
+        credential_status: credential_status.as_ref().map(Cow::Borrowed),
+        credential_schema: Cow::Borrowed(credential_schema),
+        refresh_service: Cow::Borrowed(refresh_service),
+        terms_of_use: Cow::Borrowed(terms_of_use),
+        evidence: Cow::Borrowed(evidence),
+        non_transferable: *non_transferable,
+        properties: Cow::Borrowed(properties),
+        id: None,
+        issuance_date: None,
+        expiration_date: None,
+      },
+    })
+  }
+
+  /// Converts the claims set back into the [`Credential`] it represents.
+  ///
+  /// Properties that are duplicated in the `vc` claim, as permitted by the
+  /// [VC Data Model](https://www.w3.org/TR/vc-data-model/#jwt-decoding), must match the registered claims.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the registered claims are inconsistent with the properties of the `vc` claim.
+  pub(crate) fn try_into_credential(self) -> Result<Credential<T>> {
+    let Self {
+      exp,
+      iss,
+      nbf,
+      jti,
+      sub,
+      vc:
+        InnerCredential {
+          context,
+          types,
+          credential_subject,
+          issuer,
+          credential_status,
+          credential_schema,
+          refresh_service,
+          terms_of_use,
+          evidence,
+          non_transferable,
+          properties,
+          id: vc_id,
+          issuance_date: vc_issuance_date,
+          expiration_date: vc_expiration_date,
+        },
+    } = self;
+
+    let issuance_date: Timestamp =
+      Timestamp::from_unix(nbf).map_err(|err| Error::JwtClaimsSetDeserializationError(err.into()))?;
+    let expiration_date: Option<Timestamp> = exp
+      .map(Timestamp::from_unix)
+      .transpose()
+      .map_err(|err| Error::JwtClaimsSetDeserializationError(err.into()))?;
+    let jti: Option<Url> = jti.map(Cow::into_owned);
+
+    check_duplicate("issuanceDate", Some(&issuance_date), vc_issuance_date.as_ref())?;
+    check_duplicate("expirationDate", expiration_date.as_ref(), vc_expiration_date.as_ref())?;
+    check_duplicate("id", jti.as_ref(), vc_id.as_ref())?;
+
+    let mut credential_subject: OneOrMany<Subject> = credential_subject.into_owned();
+    if let Some(sub) = sub {
+      match credential_subject {
+        OneOrMany::One(ref mut subject) => {
+          check_duplicate("credentialSubject.id", Some(sub.as_ref()), subject.id.as_ref())?;
+          subject.id = Some(sub.into_owned());
+        }
+        OneOrMany::Many(_) => {
+          return Err(Error::JwtClaimsSetDeserializationError(
+            "the `sub` claim cannot represent multiple credential subjects".into(),
+          ))
+        }
+      }
+    }
+
+    let iss: Url = iss.into_owned();
+    let issuer: Issuer = match issuer.map(Cow::into_owned) {
+      Some(mut properties) => {
+        let issuer_id: Option<Url> = properties
+          .remove("id")
+          .map(Url::from_json_value)
+          .transpose()
+          .map_err(|err| Error::JwtClaimsSetDeserializationError(err.into()))?;
+        check_duplicate("issuer.id", Some(&iss), issuer_id.as_ref())?;
+        Issuer::Obj(IssuerData { id: iss, properties })
+      }
+      None => Issuer::Url(iss),
+    };
+
+    Ok(Credential {
+      context: context.into_owned(),
+      id: jti,
+      types: types.into_owned(),
+      credential_subject,
+      issuer,
+      issuance_date,
+      expiration_date,
+      credential_status: credential_status.map(Cow::into_owned),
+      credential_schema: credential_schema.into_owned(),
+      refresh_service: refresh_service.into_owned(),
+      terms_of_use: terms_of_use.into_owned(),
+      evidence: evidence.into_owned(),
+      non_transferable,
+      properties: properties.into_owned(),
+      proof: None,
+    })
+  }
+}
+
+/// Checks that a property duplicated in the `vc` claim matches the value of the corresponding registered claim.
+fn check_duplicate<V: PartialEq>(property: &str, claim: Option<&V>, duplicate: Option<&V>) -> Result<()> {
+  match duplicate {
+    Some(duplicate) if claim != Some(duplicate) => Err(Error::JwtClaimsSetDeserializationError(
+      format!("the `{property}` property does not match the corresponding registered claim").into(),
+    )),
+    _ => Ok(()),
+  }
+}
+
+/// The [`Credential`] properties that are not represented by registered JWT claims.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: ToOwned<Owned = T> + Serialize + DeserializeOwned"))]
+struct InnerCredential<'credential, T = Object>
+where
+  T: ToOwned<Owned = T> + Serialize,
+{
+  #[serde(rename = "@context")]
+  context: Cow<'credential, OneOrMany<Context>>,
+  #[serde(rename = "type")]
+  types: Cow<'credential, OneOrMany<String>>,
+  #[serde(rename = "credentialSubject")]
+  credential_subject: Cow<'credential, OneOrMany<Subject>>,
+  /// Additional properties of an issuer in object form, whose `id` is represented by the `iss` claim.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  issuer: Option<Cow<'credential, Object>>,
+  #[serde(rename = "credentialStatus", skip_serializing_if = "Option::is_none")]
+  credential_status: Option<Cow<'credential, Status>>,
+  #[serde(default, rename = "credentialSchema", skip_serializing_if = "OneOrMany::is_empty")]
+  credential_schema: Cow<'credential, OneOrMany<Schema>>,
+  #[serde(default, rename = "refreshService", skip_serializing_if = "OneOrMany::is_empty")]
+  refresh_service: Cow<'credential, OneOrMany<RefreshService>>,
+  #[serde(default, rename = "termsOfUse", skip_serializing_if = "OneOrMany::is_empty")]
+  terms_of_use: Cow<'credential, OneOrMany<Policy>>,
+  #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
+  evidence: Cow<'credential, OneOrMany<Evidence>>,
+  #[serde(rename = "nonTransferable", skip_serializing_if = "Option::is_none")]
+  non_transferable: Option<bool>,
+  #[serde(flatten)]
+  properties: Cow<'credential, T>,
+  /// Duplicate of the `jti` claim, only present when decoding.
+  #[serde(default, skip_serializing)]
+  id: Option<Url>,
+  /// Duplicate of the `nbf` claim, only present when decoding.
+  #[serde(default, rename = "issuanceDate", skip_serializing)]
+  issuance_date: Option<Timestamp>,
+  /// Duplicate of the `exp` claim, only present when decoding.
+  #[serde(default, rename = "expirationDate", skip_serializing)]
+  expiration_date: Option<Timestamp>,
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Timestamp;
+  use identity_core::convert::FromJson;
+  use serde_json::json;
+  use serde_json::Value;
+
+  use crate::credential::jwt_serialization::CredentialJwtClaims;
+  use crate::credential::Credential;
+
+  const CREDENTIAL_JSON: &str = r#"{
+    "@context": [
+      "https://www.w3.org/2018/credentials/v1",
+      "https://www.w3.org/2018/credentials/examples/v1"
+    ],
+    "id": "http://example.edu/credentials/3732",
+    "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+    "issuer": "https://example.edu/issuers/14",
+    "issuanceDate": "2010-01-01T19:23:24Z",
+    "expirationDate": "2020-01-01T19:23:24Z",
+    "credentialSubject": {
+      "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+      "degree": {
+        "type": "BachelorDegree",
+        "name": "Bachelor of Science in Mechanical Engineering"
+      }
+    }
+  }"#;
+
+  #[test]
+  fn credential_jwt_claims() {
+    let credential: Credential = Credential::from_json(CREDENTIAL_JSON).unwrap();
+    let claims: Value = serde_json::from_str(&credential.serialize_jwt().unwrap()).unwrap();
+
+    let expected: Value = json!({
+      "iss": "https://example.edu/issuers/14",
+      "nbf": Timestamp::parse("2010-01-01T19:23:24Z").unwrap().to_unix(),
+      "exp": Timestamp::parse("2020-01-01T19:23:24Z").unwrap().to_unix(),
+      "jti": "http://example.edu/credentials/3732",
+      "sub": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+      "vc": {
+        "@context": [
+          "https://www.w3.org/2018/credentials/v1",
+          "https://www.w3.org/2018/credentials/examples/v1"
+        ],
+        "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+        "credentialSubject": {
+          "degree": {
+            "type": "BachelorDegree",
+            "name": "Bachelor of Science in Mechanical Engineering"
+          }
+        }
+      }
+    });
+    assert_eq!(claims, expected);
+  }
+
+  #[test]
+  fn credential_jwt_claims_issuer_object() {
+    let mut credential: Value = serde_json::from_str(CREDENTIAL_JSON).unwrap();
+    credential["issuer"] = json!({ "id": "https://example.edu/issuers/14", "name": "Example University" });
+    credential["credentialSubject"] = json!([
+      { "id": "did:example:1", "name": "Alice" },
+      { "id": "did:example:2", "name": "Bob" },
+    ]);
+    let credential: Credential = serde_json::from_value(credential).unwrap();
+    let claims: Value = serde_json::from_str(&credential.serialize_jwt().unwrap()).unwrap();
+
+    assert_eq!(claims["iss"], "https://example.edu/issuers/14");
+    assert_eq!(claims["vc"]["issuer"], json!({ "name": "Example University" }));
+    // Multiple subjects cannot be represented by the `sub` claim.
+    assert!(claims.get("sub").is_none());
+    assert_eq!(claims["vc"]["credentialSubject"][1]["id"], "did:example:2");
+  }
+
+  #[test]
+  fn claims_to_credential_roundtrip() {
+    let credential: Credential = Credential::from_json(CREDENTIAL_JSON).unwrap();
+    let claims: CredentialJwtClaims<'_> = CredentialJwtClaims::from_json(&credential.serialize_jwt().unwrap()).unwrap();
+    assert_eq!(claims.try_into_credential().unwrap(), credential);
+  }
+
+  #[test]
+  fn claims_with_inconsistent_duplicates() {
+    let credential: Credential = Credential::from_json(CREDENTIAL_JSON).unwrap();
+    let mut claims: Value = serde_json::from_str(&credential.serialize_jwt().unwrap()).unwrap();
+
+    // Duplicates that match the registered claims are accepted.
+    claims["vc"]["issuanceDate"] = json!("2010-01-01T19:23:24Z");
+    let decoded: CredentialJwtClaims<'_> = serde_json::from_value(claims.clone()).unwrap();
+    assert_eq!(decoded.try_into_credential().unwrap(), credential);
+
+    claims["vc"]["issuanceDate"] = json!("2011-01-01T19:23:24Z");
+    let decoded: CredentialJwtClaims<'_> = serde_json::from_value(claims).unwrap();
+    assert!(decoded.try_into_credential().is_err());
+  }
+}
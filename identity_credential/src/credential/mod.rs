@@ -11,6 +11,8 @@ mod domain_linkage_configuration;
 mod domain_linkage_credential_builder;
 mod evidence;
 mod issuer;
+mod jwt;
+pub(crate) mod jwt_serialization;
 mod linked_domain_service;
 mod policy;
 mod refresh;
@@ -26,6 +28,7 @@ pub use self::domain_linkage_configuration::DomainLinkageConfiguration;
 pub use self::domain_linkage_credential_builder::DomainLinkageCredentialBuilder;
 pub use self::evidence::Evidence;
 pub use self::issuer::Issuer;
+pub use self::jwt::Jwt;
 pub use self::linked_domain_service::LinkedDomainService;
 pub use self::policy::Policy;
 pub use self::refresh::RefreshService;
@@ -11,6 +11,7 @@ use identity_did::DIDUrl;
 use crate::credential::Status;
 use crate::error::Error;
 use crate::error::Result;
+use crate::revocation::StatusPurpose;
 
 /// Information used to determine the current status of a [`Credential`][crate::credential::Credential]
 /// using the `RevocationBitmap2022` specification.
@@ -19,6 +20,7 @@ pub struct RevocationBitmapStatus(Status);
 
 impl RevocationBitmapStatus {
   const INDEX_PROPERTY: &'static str = "revocationBitmapIndex";
+  const PURPOSE_PROPERTY: &'static str = "statusPurpose";
   /// Type name of the revocation bitmap.
   pub const TYPE: &'static str = "RevocationBitmap2022";
 
@@ -53,6 +55,30 @@ impl RevocationBitmapStatus {
     ))
   }
 
+  /// Creates a new `RevocationBitmapStatus` with the given `purpose`.
+  ///
+  /// The `id` should resolve to a `RevocationBitmap2022` service with the same purpose,
+  /// e.g. one created with [`RevocationBitmap::to_service`](crate::revocation::RevocationBitmap::to_service).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use identity_credential::credential::RevocationBitmapStatus;
+  /// # use identity_credential::revocation::StatusPurpose;
+  /// # use identity_did::DIDUrl;
+  /// let did_url: DIDUrl = DIDUrl::parse("did:method:0xffff#suspension-1").unwrap();
+  /// let status: RevocationBitmapStatus = RevocationBitmapStatus::new_with_purpose(did_url, 5, StatusPurpose::Suspension);
+  /// assert_eq!(status.purpose().unwrap(), StatusPurpose::Suspension);
+  /// ```
+  pub fn new_with_purpose(id: DIDUrl, index: u32, purpose: StatusPurpose) -> Self {
+    let mut status: Self = Self::new(id, index);
+    status
+      .0
+      .properties
+      .insert(Self::PURPOSE_PROPERTY.to_owned(), Value::String(purpose.to_string()));
+    status
+  }
+
   /// Returns the [`DIDUrl`] of the `RevocationBitmapStatus`, which should resolve
   /// to a `RevocationBitmap2022` service in a DID Document.
   pub fn id(&self) -> Result<DIDUrl> {
@@ -60,6 +86,21 @@ impl RevocationBitmapStatus {
       .map_err(|err| Error::InvalidStatus(format!("invalid DID Url '{}': {:?}", self.0.id, err)))
   }
 
+  /// Returns the purpose of the status.
+  ///
+  /// Defaults to [`StatusPurpose::Revocation`] if the `statusPurpose` property is absent.
+  pub fn purpose(&self) -> Result<StatusPurpose> {
+    match self.0.properties.get(Self::PURPOSE_PROPERTY) {
+      None => Ok(StatusPurpose::Revocation),
+      Some(Value::String(purpose)) => StatusPurpose::from_str(purpose)
+        .map_err(|_| Error::InvalidStatus(format!("unsupported {} '{purpose}'", Self::PURPOSE_PROPERTY))),
+      Some(_) => Err(Error::InvalidStatus(format!(
+        "property '{}' is not a string",
+        Self::PURPOSE_PROPERTY
+      ))),
+    }
+  }
+
   /// Returns the index of the credential in the issuer's revocation bitmap if it can be decoded.
   pub fn index(&self) -> Result<u32> {
     if let Some(Value::String(index)) = self.0.properties.get(Self::INDEX_PROPERTY) {
@@ -118,7 +159,10 @@ impl TryFrom<Status> for RevocationBitmapStatus {
       }
     }
 
-    Ok(Self(status))
+    let status: Self = Self(status);
+    status.purpose()?;
+
+    Ok(status)
   }
 }
 
@@ -145,6 +189,7 @@ mod tests {
   use identity_core::convert::FromJson;
   use identity_did::DIDUrl;
 
+  use crate::revocation::StatusPurpose;
   use crate::Error;
 
   use super::RevocationBitmapStatus;
@@ -220,4 +265,28 @@ mod tests {
     .unwrap();
     assert!(RevocationBitmapStatus::try_from(status).is_ok());
   }
+
+  #[test]
+  fn test_revocation_bitmap_status_purpose() {
+    let did_url: DIDUrl = DIDUrl::parse("did:method:0xffff#rev-0").unwrap();
+    let status: RevocationBitmapStatus = RevocationBitmapStatus::new(did_url.clone(), 5);
+    assert_eq!(status.purpose().unwrap(), StatusPurpose::Revocation);
+
+    let status: RevocationBitmapStatus =
+      RevocationBitmapStatus::new_with_purpose(did_url, 5, StatusPurpose::Suspension);
+    let status: RevocationBitmapStatus = RevocationBitmapStatus::try_from(Status::from(status)).unwrap();
+    assert_eq!(status.purpose().unwrap(), StatusPurpose::Suspension);
+
+    let status: Status = Status::from_json_value(serde_json::json!({
+      "id": "did:method:0xffff?index=5#rev-0",
+      "type": RevocationBitmapStatus::TYPE,
+      RevocationBitmapStatus::INDEX_PROPERTY: "5",
+      RevocationBitmapStatus::PURPOSE_PROPERTY: "expiration",
+    }))
+    .unwrap();
+    assert!(matches!(
+      RevocationBitmapStatus::try_from(status).unwrap_err(),
+      Error::InvalidStatus(_)
+    ));
+  }
 }
@@ -36,4 +36,10 @@ pub enum Error {
   /// Caused when constructing an invalid `LinkedDomainService` or `DomainLinkageConfiguration`.
   #[error("domain linkage error: {0}")]
   DomainLinkageError(Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused when attempting to encode a `Credential` or `Presentation` as a JWT claims set.
+  #[error("could not serialize JWT claims set")]
+  JwtClaimsSetSerializationError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused when a JWT claims set cannot be converted into a `Credential`.
+  #[error("could not deserialize JWT claims set")]
+  JwtClaimsSetDeserializationError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
@@ -0,0 +1,57 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Size-limited retrieval of resources published on the web, such as domain linkage configurations.
+
+/// The maximum size of a resource fetched from the web, 1 MiB.
+pub const MAX_FETCH_RESPONSE_SIZE: usize = 1_048_576;
+
+#[cfg(feature = "domain-linkage-fetch")]
+pub use self::__fetch::fetch_bounded;
+
+#[cfg(feature = "domain-linkage-fetch")]
+mod __fetch {
+  use futures::StreamExt;
+  use identity_core::common::Url;
+  use reqwest::redirect::Policy;
+  use reqwest::Client;
+
+  /// Fetches the resource at `url` via a GET request and returns the response body.
+  ///
+  /// Only `https` requests are permitted and redirects are not followed.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `url` does not use the `https` protocol, the request fails or the response exceeds
+  /// `max_size` bytes.
+  pub async fn fetch_bounded(
+    url: &Url,
+    max_size: usize,
+  ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    if url.scheme() != "https" {
+      return Err(format!("`{url}` does not use the `https` protocol").into());
+    }
+
+    let client: Client = reqwest::ClientBuilder::new()
+      .https_only(true)
+      .redirect(Policy::none())
+      .build()?;
+
+    // We use a stream so we can limit the size of the response.
+    let mut stream: _ = client
+      .get(url.to_string())
+      .send()
+      .await?
+      .error_for_status()?
+      .bytes_stream();
+
+    let mut body: Vec<u8> = Vec::new();
+    while let Some(bytes) = stream.next().await {
+      body.extend(bytes?);
+      if body.len() > max_size {
+        return Err(format!("response can not exceed {max_size} bytes").into());
+      }
+    }
+    Ok(body)
+  }
+}
@@ -26,10 +26,13 @@ pub use self::error::Result;
 #[cfg(feature = "credential")]
 pub mod credential;
 pub mod error;
+pub mod fetch;
 #[cfg(feature = "presentation")]
 pub mod presentation;
-#[cfg(feature = "revocation-bitmap")]
+#[cfg(any(feature = "revocation-bitmap", feature = "status-list-2021"))]
 pub mod revocation;
+#[cfg(feature = "sd-jwt")]
+pub mod sd_jwt;
 
 #[cfg(feature = "validator")]
 pub mod validator;
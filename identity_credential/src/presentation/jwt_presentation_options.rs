@@ -0,0 +1,59 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+
+/// Options to be set in the JWT claims of a verifiable presentation.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtPresentationOptions {
+  /// Set the presentation's expiration date.
+  /// Default: `None`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub expiration_date: Option<Timestamp>,
+  /// Set the presentation's issuance date.
+  /// Default: current datetime.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub issuance_date: Option<Timestamp>,
+  /// Sets the audience for presentation (`aud` property in JWT claims).
+  /// Default: `None`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub audience: Option<Url>,
+}
+
+impl JwtPresentationOptions {
+  /// Constructor that sets all options to their defaults.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the expiration date of the presentation.
+  pub fn expiration_date(mut self, expiration_date: Timestamp) -> Self {
+    self.expiration_date = Some(expiration_date);
+    self
+  }
+
+  /// Sets the issuance date of the presentation.
+  pub fn issuance_date(mut self, issuance_date: Timestamp) -> Self {
+    self.issuance_date = Some(issuance_date);
+    self
+  }
+
+  /// Sets the intended audience of the presentation.
+  pub fn audience(mut self, audience: Url) -> Self {
+    self.audience = Some(audience);
+    self
+  }
+}
+
+impl Default for JwtPresentationOptions {
+  fn default() -> Self {
+    Self {
+      expiration_date: None,
+      issuance_date: Some(Timestamp::now_utc()),
+      audience: None,
+    }
+  }
+}
@@ -0,0 +1,158 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use serde::Serialize;
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::common::Url;
+
+use crate::credential::Credential;
+use crate::credential::Policy;
+use crate::credential::RefreshService;
+use crate::presentation::JwtPresentationOptions;
+use crate::presentation::Presentation;
+use crate::Error;
+use crate::Result;
+
+/// Implementation of the JWT claims set of a [`Presentation`] as described in the
+/// [VC Data Model](https://www.w3.org/TR/vc-data-model/#json-web-token).
+#[derive(Serialize)]
+pub(crate) struct PresentationJwtClaims<'presentation, T = Object, U = Object>
+where
+  T: ToOwned<Owned = T> + Serialize,
+  U: Clone + Serialize,
+{
+  /// Represents the expiration date encoded as a UNIX timestamp.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  exp: Option<i64>,
+  /// Represents the `holder` property.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  iss: Option<Cow<'presentation, Url>>,
+  /// Represents the issuance date encoded as a UNIX timestamp.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  nbf: Option<i64>,
+  /// Represents the `id` property of the presentation.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  jti: Option<Cow<'presentation, Url>>,
+  /// The intended audience of the presentation.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  aud: Option<Url>,
+  /// The remaining properties of the presentation.
+  vp: InnerPresentation<'presentation, T, U>,
+}
+
+impl<'presentation, T, U> PresentationJwtClaims<'presentation, T, U>
+where
+  T: ToOwned<Owned = T> + Serialize,
+  U: Clone + Serialize,
+{
+  /// Maps the properties of `presentation` and `options` to the registered JWT claims where applicable.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the presentation contains an embedded proof, as the JWS replaces it.
+  pub(crate) fn new(presentation: &'presentation Presentation<T, U>, options: &JwtPresentationOptions) -> Result<Self> {
+    let Presentation {
+      context,
+      id,
+      types,
+      verifiable_credential,
+      holder,
+      refresh_service,
+      terms_of_use,
+      properties,
+      proof,
+    } = presentation;
+
+    if proof.is_some() {
+      return Err(Error::JwtClaimsSetSerializationError(
+        "presentations with an embedded proof cannot be encoded as a JWT".into(),
+      ));
+    }
+
+    Ok(Self {
+      exp: options.expiration_date.map(|expiration_date| expiration_date.to_unix()),
+      iss: holder.as_ref().map(Cow::Borrowed),
+      nbf: options.issuance_date.map(|issuance_date| issuance_date.to_unix()),
+      jti: id.as_ref().map(Cow::Borrowed),
+      aud: options.audience.clone(),
+      vp: InnerPresentation {
+        context: Cow::Borrowed(context),
+        types: Cow::Borrowed(types),
+        verifiable_credential: Cow::Borrowed(verifiable_credential),
+        refresh_service: Cow::Borrowed(refresh_service),
+        terms_of_use: Cow::Borrowed(terms_of_use),
+        properties: Cow::Borrowed(properties),
+      },
+    })
+  }
+}
+
+/// The [`Presentation`] properties that are not represented by registered JWT claims.
+#[derive(Serialize)]
+struct InnerPresentation<'presentation, T = Object, U = Object>
+where
+  T: ToOwned<Owned = T> + Serialize,
+  U: Clone + Serialize,
+{
+  #[serde(rename = "@context")]
+  context: Cow<'presentation, OneOrMany<Context>>,
+  #[serde(rename = "type")]
+  types: Cow<'presentation, OneOrMany<String>>,
+  #[serde(rename = "verifiableCredential")]
+  verifiable_credential: Cow<'presentation, OneOrMany<Credential<U>>>,
+  #[serde(rename = "refreshService", skip_serializing_if = "OneOrMany::is_empty")]
+  refresh_service: Cow<'presentation, OneOrMany<RefreshService>>,
+  #[serde(rename = "termsOfUse", skip_serializing_if = "OneOrMany::is_empty")]
+  terms_of_use: Cow<'presentation, OneOrMany<Policy>>,
+  #[serde(flatten)]
+  properties: Cow<'presentation, T>,
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+  use serde_json::Value;
+
+  use crate::presentation::JwtPresentationOptions;
+  use crate::presentation::Presentation;
+
+  const JSON: &str = include_str!("../../tests/fixtures/presentation-1.json");
+
+  #[test]
+  fn presentation_jwt_claims() {
+    let mut presentation: Presentation = Presentation::from_json(JSON).unwrap();
+    presentation.proof = None;
+    presentation.holder = Some(Url::parse("did:example:holder").unwrap());
+
+    let issuance_date: Timestamp = Timestamp::parse("2023-01-01T00:00:00Z").unwrap();
+    let options: JwtPresentationOptions = JwtPresentationOptions::new()
+      .issuance_date(issuance_date)
+      .audience(Url::parse("https://verifier.example").unwrap());
+    let claims: Value = serde_json::from_str(&presentation.serialize_jwt(&options).unwrap()).unwrap();
+
+    assert_eq!(claims["iss"], "did:example:holder");
+    assert_eq!(claims["jti"], "urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5");
+    assert_eq!(claims["nbf"], issuance_date.to_unix());
+    assert_eq!(claims["aud"], "https://verifier.example/");
+    assert!(claims.get("exp").is_none());
+    assert!(claims["vp"].get("holder").is_none());
+    assert!(claims["vp"].get("proof").is_none());
+    assert_eq!(
+      claims["vp"]["verifiableCredential"],
+      serde_json::to_value(&presentation.verifiable_credential).unwrap()
+    );
+  }
+
+  #[test]
+  fn presentation_with_proof_is_rejected() {
+    let presentation: Presentation = Presentation::from_json(JSON).unwrap();
+    assert!(presentation.serialize_jwt(&JwtPresentationOptions::new()).is_err());
+  }
+}
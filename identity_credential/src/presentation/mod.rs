@@ -6,7 +6,10 @@
 #![allow(clippy::module_inception)]
 
 mod builder;
+mod jwt_presentation_options;
+mod jwt_serialization;
 mod presentation;
 
 pub use self::builder::PresentationBuilder;
+pub use self::jwt_presentation_options::JwtPresentationOptions;
 pub use self::presentation::Presentation;
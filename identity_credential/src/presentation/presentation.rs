@@ -11,6 +11,7 @@ use identity_core::common::Object;
 use identity_core::common::OneOrMany;
 use identity_core::common::Url;
 use identity_core::convert::FmtJson;
+use identity_core::convert::ToJson;
 use identity_core::crypto::GetSignature;
 use identity_core::crypto::GetSignatureMut;
 use identity_core::crypto::Proof;
@@ -23,6 +24,8 @@ use crate::credential::Policy;
 use crate::credential::RefreshService;
 use crate::error::Error;
 use crate::error::Result;
+use crate::presentation::jwt_serialization::PresentationJwtClaims;
+use crate::presentation::JwtPresentationOptions;
 use crate::presentation::PresentationBuilder;
 
 /// Represents a bundle of one or more [Credential]s.
@@ -126,6 +129,22 @@ impl<T, U> Presentation<T, U> {
   }
 }
 
+impl<T, U> Presentation<T, U>
+where
+  T: ToOwned<Owned = T> + Serialize,
+  U: Clone + Serialize,
+{
+  /// Serializes the [`Presentation`] as a JWT claims set
+  /// in accordance with [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/#json-web-token).
+  ///
+  /// The resulting string can be used as the payload of a JWS when issuing the presentation.
+  pub fn serialize_jwt(&self, options: &JwtPresentationOptions) -> Result<String> {
+    PresentationJwtClaims::new(self, options)?
+      .to_json()
+      .map_err(|err| Error::JwtClaimsSetSerializationError(err.into()))
+  }
+}
+
 impl<T, U> Display for Presentation<T, U>
 where
   T: Serialize,
@@ -2,17 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::io::Write;
+use std::str::FromStr;
 
 use dataurl::DataUrl;
 use flate2::write::ZlibDecoder;
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
+use identity_core::common::Object;
 use identity_core::common::Url;
+use identity_core::common::Value;
 use identity_core::utils::Base;
 use identity_core::utils::BaseEncoding;
+use identity_did::DIDUrl;
 use roaring::RoaringBitmap;
 
 use super::error::RevocationError;
+use super::StatusPurpose;
 use identity_document::service::Service;
 use identity_document::service::ServiceEndpoint;
 
@@ -25,6 +30,10 @@ pub struct RevocationBitmap(RoaringBitmap);
 impl RevocationBitmap {
   /// The name of the service type.
   pub const TYPE: &'static str = "RevocationBitmap2022";
+  /// The name of the service property holding the [`StatusPurpose`] of the bitmap.
+  ///
+  /// Services without this property are revocation bitmaps.
+  pub const PURPOSE_PROPERTY: &'static str = "statusPurpose";
 
   /// Constructs a new empty [`RevocationBitmap`].
   pub fn new() -> Self {
@@ -73,6 +82,34 @@ impl RevocationBitmap {
       .map_err(|e| RevocationError::UrlConstructionError(e.into()))
   }
 
+  /// Return the bitmap as a `RevocationBitmap2022` service with the given `service_id` and `purpose`.
+  ///
+  /// The `statusPurpose` property is only set for [`StatusPurpose::Suspension`], so revocation services
+  /// remain compatible with verifiers unaware of suspension.
+  pub fn to_service(&self, service_id: DIDUrl, purpose: StatusPurpose) -> Result<Service, RevocationError> {
+    let mut properties: Object = Object::new();
+    if purpose == StatusPurpose::Suspension {
+      properties.insert(Self::PURPOSE_PROPERTY.to_owned(), purpose.to_string().into());
+    }
+    Service::builder(properties)
+      .id(service_id)
+      .type_(Self::TYPE)
+      .service_endpoint(self.to_endpoint()?)
+      .build()
+      .map_err(|_| RevocationError::InvalidService("invalid service - the id must have a fragment"))
+  }
+
+  /// Returns the [`StatusPurpose`] of a `RevocationBitmap2022` `service`.
+  pub fn service_purpose(service: &Service) -> Result<StatusPurpose, RevocationError> {
+    match service.properties().get(Self::PURPOSE_PROPERTY) {
+      None => Ok(StatusPurpose::Revocation),
+      Some(Value::String(purpose)) => StatusPurpose::from_str(purpose),
+      Some(_) => Err(RevocationError::InvalidService(
+        "invalid property - expected `statusPurpose` to be a string",
+      )),
+    }
+  }
+
   /// Construct a `RevocationBitmap` from a data url embedded in `service_endpoint`.
   pub fn from_endpoint(service_endpoint: &ServiceEndpoint) -> Result<Self, RevocationError> {
     if let ServiceEndpoint::One(url) = service_endpoint {
@@ -9,21 +9,38 @@ use identity_document::utils::Queryable;
 
 use super::RevocationError;
 use super::RevocationResult;
+use super::StatusPurpose;
 /// Extension trait providing convenience methods to update a `RevocationBitmap2022` service
 /// in a [`CoreDocument`](::identity_document::document::CoreDocument).   
 pub trait RevocationDocumentExt: private::Sealed {
   /// If the document has a [`RevocationBitmap`] service identified by `service_query`,
   /// revoke all specified `indices`.
+  ///
+  /// Fails if the service has the [`StatusPurpose::Suspension`] purpose.
   fn revoke_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> RevocationResult<()>
   where
     Q: Into<DIDUrlQuery<'query>>;
 
   /// If the document has a [`RevocationBitmap`] service identified by `service_query`,
   /// unrevoke all specified `indices`.
+  ///
+  /// Fails if the service has the [`StatusPurpose::Suspension`] purpose.
   fn unrevoke_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> RevocationResult<()>
   where
     Q: Into<DIDUrlQuery<'query>>;
 
+  /// If the document has a [`RevocationBitmap`] service with the [`StatusPurpose::Suspension`] purpose
+  /// identified by `service_query`, suspend all specified `indices`.
+  fn suspend_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> RevocationResult<()>
+  where
+    Q: Into<DIDUrlQuery<'query>>;
+
+  /// If the document has a [`RevocationBitmap`] service with the [`StatusPurpose::Suspension`] purpose
+  /// identified by `service_query`, unsuspend all specified `indices`.
+  fn unsuspend_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> RevocationResult<()>
+  where
+    Q: Into<DIDUrlQuery<'query>>;
+
   /// Extracts the `RevocationBitmap` from the referenced service in the DID Document.
   ///
   /// # Errors
@@ -46,7 +63,7 @@ impl RevocationDocumentExt for CoreDocument {
   where
     Q: Into<DIDUrlQuery<'query>>,
   {
-    update_revocation_bitmap(self, service_query, |revocation_bitmap| {
+    update_revocation_bitmap(self, service_query, StatusPurpose::Revocation, |revocation_bitmap| {
       for credential in indices {
         revocation_bitmap.revoke(*credential);
       }
@@ -57,7 +74,29 @@ impl RevocationDocumentExt for CoreDocument {
   where
     Q: Into<DIDUrlQuery<'query>>,
   {
-    update_revocation_bitmap(self, service_query, |revocation_bitmap| {
+    update_revocation_bitmap(self, service_query, StatusPurpose::Revocation, |revocation_bitmap| {
+      for credential in indices {
+        revocation_bitmap.unrevoke(*credential);
+      }
+    })
+  }
+
+  fn suspend_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> RevocationResult<()>
+  where
+    Q: Into<DIDUrlQuery<'query>>,
+  {
+    update_revocation_bitmap(self, service_query, StatusPurpose::Suspension, |revocation_bitmap| {
+      for credential in indices {
+        revocation_bitmap.revoke(*credential);
+      }
+    })
+  }
+
+  fn unsuspend_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> RevocationResult<()>
+  where
+    Q: Into<DIDUrlQuery<'query>>,
+  {
+    update_revocation_bitmap(self, service_query, StatusPurpose::Suspension, |revocation_bitmap| {
       for credential in indices {
         revocation_bitmap.unrevoke(*credential);
       }
@@ -75,6 +114,7 @@ impl RevocationDocumentExt for CoreDocument {
 fn update_revocation_bitmap<'query, 'me, F, Q>(
   document: &'me mut CoreDocument,
   service_query: Q,
+  purpose: StatusPurpose,
   f: F,
 ) -> RevocationResult<()>
 where
@@ -86,6 +126,13 @@ where
     .query_mut(service_query)
     .ok_or(RevocationError::InvalidService("invalid id - service not found"))?;
 
+  if RevocationBitmap::service_purpose(service)? != purpose {
+    return Err(RevocationError::InvalidService(match purpose {
+      StatusPurpose::Revocation => "invalid service - expected a revocation bitmap",
+      StatusPurpose::Suspension => "invalid service - expected a suspension bitmap",
+    }));
+  }
+
   let mut revocation_bitmap: RevocationBitmap = RevocationBitmap::try_from(&*service)?;
   f(&mut revocation_bitmap);
 
@@ -191,4 +238,51 @@ mod tests {
       assert!(!decoded_bitmap.is_revoked(index));
     }
   }
+
+  #[test]
+  fn test_suspension() {
+    let mut document: CoreDocument = CoreDocument::from_json(&START_DOCUMENT_JSON).unwrap();
+    let revocation_id = document.id().to_url().join("#revocation-service").unwrap();
+    let suspension_id = document.id().to_url().join("#suspension-service").unwrap();
+
+    let bitmap: RevocationBitmap = RevocationBitmap::new();
+    document
+      .insert_service(
+        bitmap
+          .to_service(revocation_id.clone(), StatusPurpose::Revocation)
+          .unwrap(),
+      )
+      .unwrap();
+    document
+      .insert_service(
+        bitmap
+          .to_service(suspension_id.clone(), StatusPurpose::Suspension)
+          .unwrap(),
+      )
+      .unwrap();
+
+    // Revocation and suspension only apply to services with the matching purpose.
+    assert!(document.suspend_credentials(&revocation_id, &[1]).is_err());
+    assert!(document.revoke_credentials(&suspension_id, &[1]).is_err());
+    assert!(document.unrevoke_credentials(&suspension_id, &[1]).is_err());
+
+    document.suspend_credentials(&suspension_id, &[1, 2]).unwrap();
+    document.unsuspend_credentials(&suspension_id, &[1]).unwrap();
+
+    let service: &Service = document.resolve_service(&suspension_id).unwrap();
+    assert_eq!(
+      RevocationBitmap::service_purpose(service).unwrap(),
+      StatusPurpose::Suspension
+    );
+    let decoded_bitmap: RevocationBitmap = service.try_into().unwrap();
+    assert!(!decoded_bitmap.is_revoked(1));
+    assert!(decoded_bitmap.is_revoked(2));
+
+    let service: &Service = document.resolve_service(&revocation_id).unwrap();
+    assert_eq!(
+      RevocationBitmap::service_purpose(service).unwrap(),
+      StatusPurpose::Revocation
+    );
+    assert!(!service.properties().contains_key(RevocationBitmap::PURPOSE_PROPERTY));
+  }
 }
@@ -1,10 +1,11 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-/// A result type designed for `RevocationBitmap2022` handling.
+/// A result type designed for `RevocationBitmap2022` and `StatusList2021` handling.
 pub type RevocationResult<T> = std::result::Result<T, RevocationError>;
 
 /// Errors occurring when creating or extracting a Service of type `RevocationBitmap2022`
+/// or when handling a `StatusList2021`.
 #[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
 pub enum RevocationError {
   #[error("revocation bitmap could not be deserialized or decompressed")]
@@ -24,4 +25,27 @@ pub enum RevocationError {
   #[non_exhaustive]
   /// Indicates a failure to construct a URL when attempting to construct a `ServiceEndpoint`.
   UrlConstructionError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  #[error("status list could not be compressed")]
+  /// Indicates that a `StatusList2021` could not be encoded.
+  StatusListEncodingError(#[source] std::io::Error),
+  #[error("status list could not be decompressed")]
+  /// Indicates that a `StatusList2021` could not be reconstructed from its encoded form.
+  StatusListDecodingError(#[source] std::io::Error),
+  #[error("decompressed status list exceeds the maximum size of {0} bytes")]
+  /// Indicates that a `StatusList2021` is larger than `MAX_STATUS_LIST_BYTES` when decompressed.
+  StatusListTooLarge(usize),
+  #[error("index {index} is out of bounds for a status list of length {len}")]
+  /// Indicates an access to a `StatusList2021` entry that does not exist.
+  StatusListIndexOutOfBounds {
+    /// The accessed index.
+    index: usize,
+    /// The number of entries in the list.
+    len: usize,
+  },
+  #[error("unknown status purpose `{0}`")]
+  /// Indicates an unknown `statusPurpose`.
+  InvalidStatusPurpose(String),
+  #[error("invalid status list credential: {0}")]
+  /// Indicates that a credential is not a valid `StatusList2021Credential`.
+  InvalidStatusListCredential(&'static str),
 }
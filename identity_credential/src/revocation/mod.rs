@@ -1,11 +1,21 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-//! Contains a bitmap for managing credential revocation.
+//! Contains a bitmap for managing credential revocation and suspension and an implementation of `StatusList2021`.
+#[cfg(feature = "revocation-bitmap")]
 mod bitmap;
+#[cfg(feature = "revocation-bitmap")]
 mod document_ext;
 mod error;
+mod purpose;
+#[cfg(feature = "status-list-2021")]
+mod status_list_2021;
+#[cfg(feature = "revocation-bitmap")]
 pub use self::bitmap::RevocationBitmap;
+#[cfg(feature = "revocation-bitmap")]
 pub use self::document_ext::RevocationDocumentExt;
 pub use self::error::RevocationError;
 pub use self::error::RevocationResult;
+pub use self::purpose::StatusPurpose;
+#[cfg(feature = "status-list-2021")]
+pub use self::status_list_2021::*;
@@ -0,0 +1,50 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use crate::revocation::RevocationError;
+
+/// The purpose of a credential status, as expressed by the `statusPurpose` property.
+///
+/// Used by both `StatusList2021` and `RevocationBitmap2022`.
+///
+/// See: <https://w3c-ccg.github.io/vc-status-list-2021/#statuslist2021entry>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusPurpose {
+  /// A set entry indicates that the credential has been permanently revoked.
+  Revocation,
+  /// A set entry indicates that the credential is temporarily suspended.
+  Suspension,
+}
+
+impl StatusPurpose {
+  /// Returns the string representation of the purpose.
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::Revocation => "revocation",
+      Self::Suspension => "suspension",
+    }
+  }
+}
+
+impl Display for StatusPurpose {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for StatusPurpose {
+  type Err = RevocationError;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    match string {
+      "revocation" => Ok(Self::Revocation),
+      "suspension" => Ok(Self::Suspension),
+      _ => Err(RevocationError::InvalidStatusPurpose(string.to_owned())),
+    }
+  }
+}
@@ -0,0 +1,344 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::common::Value;
+
+use crate::credential::Credential;
+use crate::credential::Issuer;
+use crate::credential::Subject;
+use crate::revocation::RevocationError;
+use crate::revocation::RevocationResult;
+
+use super::StatusList2021;
+use crate::revocation::StatusPurpose;
+
+lazy_static! {
+  static ref STATUS_LIST_2021_CONTEXT: Context =
+    Context::Url(Url::parse("https://w3id.org/vc/status-list/2021/v1").unwrap());
+}
+
+const SUBJECT_TYPE: &str = "StatusList2021";
+const TYPE_PROPERTY: &str = "type";
+const PURPOSE_PROPERTY: &str = "statusPurpose";
+const ENCODED_LIST_PROPERTY: &str = "encodedList";
+
+/// A [`Credential`] publishing a [`StatusList2021`].
+///
+/// See: <https://w3c-ccg.github.io/vc-status-list-2021/#statuslist2021credential>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusList2021Credential(Credential);
+
+impl StatusList2021Credential {
+  /// Type name of the status list credential.
+  pub const TYPE: &'static str = "StatusList2021Credential";
+
+  /// Returns the JSON-LD context of status list credentials.
+  pub fn context() -> &'static Context {
+    &STATUS_LIST_2021_CONTEXT
+  }
+
+  /// Returns a reference to the underlying [`Credential`].
+  pub fn credential(&self) -> &Credential {
+    &self.0
+  }
+
+  /// Returns the [`StatusPurpose`] of the list.
+  pub fn purpose(&self) -> StatusPurpose {
+    // The purpose was validated on construction.
+    self
+      .subject_property(PURPOSE_PROPERTY)
+      .and_then(|purpose| StatusPurpose::from_str(purpose).ok())
+      .expect("the status purpose should have been validated")
+  }
+
+  /// Decodes the [`StatusList2021`] contained in the credential subject.
+  pub fn status_list(&self) -> RevocationResult<StatusList2021> {
+    let encoded: &str = self
+      .subject_property(ENCODED_LIST_PROPERTY)
+      .ok_or(RevocationError::InvalidStatusListCredential("missing encodedList"))?;
+    StatusList2021::from_encoded_str(encoded)
+  }
+
+  /// Sets the entry at `index` of the contained [`StatusList2021`] to `value`.
+  ///
+  /// Returns the previous value of the entry.
+  ///
+  /// # Warning
+  /// Any existing proof is invalidated by this operation and is therefore removed.
+  pub fn set_entry(&mut self, index: usize, value: bool) -> RevocationResult<bool> {
+    let mut status_list: StatusList2021 = self.status_list()?;
+    let previous: bool = status_list.set(index, value)?;
+    self.set_status_list(&status_list)?;
+    Ok(previous)
+  }
+
+  /// Replaces the [`StatusList2021`] contained in the credential subject.
+  ///
+  /// # Warning
+  /// Any existing proof is invalidated by this operation and is therefore removed.
+  pub fn set_status_list(&mut self, status_list: &StatusList2021) -> RevocationResult<()> {
+    let encoded: String = status_list.to_encoded_str()?;
+    let subject: &mut Subject = match &mut self.0.credential_subject {
+      OneOrMany::One(subject) => subject,
+      OneOrMany::Many(subjects) => &mut subjects[0],
+    };
+    subject
+      .properties
+      .insert(ENCODED_LIST_PROPERTY.to_owned(), Value::String(encoded));
+    self.0.proof = None;
+    Ok(())
+  }
+
+  /// Consumes the wrapper and returns the underlying [`Credential`].
+  pub fn into_inner(self) -> Credential {
+    self.0
+  }
+
+  fn subject(&self) -> Option<&Subject> {
+    match &self.0.credential_subject {
+      OneOrMany::One(subject) => Some(subject),
+      OneOrMany::Many(subjects) if subjects.len() == 1 => subjects.first(),
+      OneOrMany::Many(_) => None,
+    }
+  }
+
+  fn subject_property(&self, name: &str) -> Option<&str> {
+    match self.subject()?.properties.get(name) {
+      Some(Value::String(value)) => Some(value),
+      _ => None,
+    }
+  }
+}
+
+impl TryFrom<Credential> for StatusList2021Credential {
+  type Error = RevocationError;
+
+  fn try_from(credential: Credential) -> RevocationResult<Self> {
+    if !credential.types.contains(&Self::TYPE.to_owned()) {
+      return Err(RevocationError::InvalidStatusListCredential(
+        "missing type `StatusList2021Credential`",
+      ));
+    }
+
+    let credential: Self = Self(credential);
+    let subject: &Subject = credential
+      .subject()
+      .ok_or(RevocationError::InvalidStatusListCredential(
+        "expected exactly one credential subject",
+      ))?;
+    if subject.properties.get(TYPE_PROPERTY) != Some(&Value::String(SUBJECT_TYPE.to_owned())) {
+      return Err(RevocationError::InvalidStatusListCredential(
+        "credential subject is not of type `StatusList2021`",
+      ));
+    }
+    credential
+      .subject_property(PURPOSE_PROPERTY)
+      .ok_or(RevocationError::InvalidStatusListCredential("missing statusPurpose"))
+      .and_then(StatusPurpose::from_str)?;
+    credential
+      .subject_property(ENCODED_LIST_PROPERTY)
+      .ok_or(RevocationError::InvalidStatusListCredential("missing encodedList"))?;
+
+    Ok(credential)
+  }
+}
+
+impl From<StatusList2021Credential> for Credential {
+  fn from(credential: StatusList2021Credential) -> Self {
+    credential.0
+  }
+}
+
+/// Convenient builder to create a spec compliant [`StatusList2021Credential`].
+///
+/// The builder expects `issuer` to be set. If no status list is set, an empty list of
+/// [`MINIMUM_STATUS_LIST_LENGTH`](super::MINIMUM_STATUS_LIST_LENGTH) entries is used.
+/// The purpose defaults to [`StatusPurpose::Revocation`] and the `issuanceDate` to the current time.
+#[derive(Debug, Default)]
+pub struct StatusList2021CredentialBuilder {
+  pub(crate) id: Option<Url>,
+  pub(crate) subject_id: Option<Url>,
+  pub(crate) issuer: Option<Issuer>,
+  pub(crate) issuance_date: Option<Timestamp>,
+  pub(crate) expiration_date: Option<Timestamp>,
+  pub(crate) purpose: Option<StatusPurpose>,
+  pub(crate) status_list: Option<StatusList2021>,
+}
+
+impl StatusList2021CredentialBuilder {
+  /// Creates a new `StatusList2021CredentialBuilder`.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the value of the `Credential` `id`, which is the URL the status list credential is published at.
+  #[must_use]
+  pub fn id(mut self, value: Url) -> Self {
+    self.id = Some(value);
+    self
+  }
+
+  /// Sets the `id` of the `credentialSubject`.
+  #[must_use]
+  pub fn subject_id(mut self, value: Url) -> Self {
+    self.subject_id = Some(value);
+    self
+  }
+
+  /// Sets the value of the `Credential` `issuer`.
+  #[must_use]
+  pub fn issuer(mut self, value: Issuer) -> Self {
+    self.issuer = Some(value);
+    self
+  }
+
+  /// Sets the value of the `Credential` `issuanceDate`.
+  #[must_use]
+  pub fn issuance_date(mut self, value: Timestamp) -> Self {
+    self.issuance_date = Some(value);
+    self
+  }
+
+  /// Sets the value of the `Credential` `expirationDate`.
+  #[must_use]
+  pub fn expiration_date(mut self, value: Timestamp) -> Self {
+    self.expiration_date = Some(value);
+    self
+  }
+
+  /// Sets the `statusPurpose` of the list.
+  #[must_use]
+  pub fn purpose(mut self, value: StatusPurpose) -> Self {
+    self.purpose = Some(value);
+    self
+  }
+
+  /// Sets the status list published by the credential.
+  #[must_use]
+  pub fn status_list(mut self, value: StatusList2021) -> Self {
+    self.status_list = Some(value);
+    self
+  }
+
+  /// Returns a new [`StatusList2021Credential`] based on the builder configuration.
+  pub fn build(self) -> crate::error::Result<StatusList2021Credential> {
+    let issuer: Issuer = self.issuer.ok_or(crate::Error::MissingIssuer)?;
+    let purpose: StatusPurpose = self.purpose.unwrap_or(StatusPurpose::Revocation);
+    let encoded: String = self
+      .status_list
+      .unwrap_or_default()
+      .to_encoded_str()
+      .map_err(|err| crate::Error::InvalidStatus(err.to_string()))?;
+
+    let mut properties: Object = Object::new();
+    properties.insert(TYPE_PROPERTY.to_owned(), Value::String(SUBJECT_TYPE.to_owned()));
+    properties.insert(PURPOSE_PROPERTY.to_owned(), Value::String(purpose.to_string()));
+    properties.insert(ENCODED_LIST_PROPERTY.to_owned(), Value::String(encoded));
+    let subject: Subject = match self.subject_id {
+      Some(id) => Subject::with_id_and_properties(id, properties),
+      None => Subject::with_properties(properties),
+    };
+
+    Ok(StatusList2021Credential(Credential {
+      context: OneOrMany::Many(vec![
+        Credential::<Object>::base_context().clone(),
+        StatusList2021Credential::context().clone(),
+      ]),
+      id: self.id,
+      types: OneOrMany::Many(vec![
+        Credential::<Object>::base_type().to_owned(),
+        StatusList2021Credential::TYPE.to_owned(),
+      ]),
+      credential_subject: OneOrMany::One(subject),
+      issuer,
+      issuance_date: self.issuance_date.unwrap_or_else(Timestamp::now_utc),
+      expiration_date: self.expiration_date,
+      credential_status: None,
+      credential_schema: Vec::new().into(),
+      refresh_service: Vec::new().into(),
+      terms_of_use: Vec::new().into(),
+      evidence: Vec::new().into(),
+      non_transferable: None,
+      properties: Object::new(),
+      proof: None,
+    }))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+
+  use super::*;
+
+  #[test]
+  fn test_builder_and_set_entry() {
+    let mut credential: StatusList2021Credential = StatusList2021CredentialBuilder::new()
+      .id(Url::parse("https://example.com/credentials/status/3").unwrap())
+      .issuer(Issuer::Url(Url::parse("did:example:12345").unwrap()))
+      .purpose(StatusPurpose::Suspension)
+      .build()
+      .unwrap();
+    assert!(credential.credential().check_structure().is_ok());
+    assert_eq!(credential.purpose(), StatusPurpose::Suspension);
+    assert!(!credential.status_list().unwrap().get(42).unwrap());
+
+    assert!(!credential.set_entry(42, true).unwrap());
+    assert!(credential.status_list().unwrap().get(42).unwrap());
+
+    // Round-trip through the plain credential.
+    let parsed: StatusList2021Credential = StatusList2021Credential::try_from(credential.clone().into_inner()).unwrap();
+    assert_eq!(parsed, credential);
+  }
+
+  #[test]
+  fn test_builder_no_issuer() {
+    assert!(matches!(
+      StatusList2021CredentialBuilder::new().build(),
+      Err(crate::Error::MissingIssuer)
+    ));
+  }
+
+  #[test]
+  fn test_parse_spec_example() {
+    let credential: Credential = Credential::from_json_value(serde_json::json!({
+      "@context": [
+        "https://www.w3.org/2018/credentials/v1",
+        "https://w3id.org/vc/status-list/2021/v1"
+      ],
+      "id": "https://example.com/credentials/status/3",
+      "type": ["VerifiableCredential", "StatusList2021Credential"],
+      "issuer": "did:example:12345",
+      "issuanceDate": "2021-04-05T14:27:40Z",
+      "credentialSubject": {
+        "id": "https://example.com/status/3#list",
+        "type": "StatusList2021",
+        "statusPurpose": "revocation",
+        "encodedList": "H4sIAAAAAAAAA-3BMQEAAADCoPVPbQwfoAAAAAAAAAAAAAAAAAAAAIC3AYbSVKsAQAAA"
+      }
+    }))
+    .unwrap();
+    let credential: StatusList2021Credential = StatusList2021Credential::try_from(credential).unwrap();
+    assert_eq!(credential.purpose(), StatusPurpose::Revocation);
+    assert_eq!(
+      credential.status_list().unwrap().len(),
+      super::super::MINIMUM_STATUS_LIST_LENGTH
+    );
+
+    let mut invalid: Credential = credential.into_inner();
+    if let OneOrMany::One(subject) = &mut invalid.credential_subject {
+      subject.properties.remove(PURPOSE_PROPERTY);
+    }
+    assert!(matches!(
+      StatusList2021Credential::try_from(invalid).unwrap_err(),
+      RevocationError::InvalidStatusListCredential(_)
+    ));
+  }
+}
@@ -0,0 +1,185 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use identity_core::common::Object;
+use identity_core::common::Url;
+use identity_core::common::Value;
+
+use crate::credential::Status;
+use crate::error::Error;
+use crate::error::Result;
+
+use crate::revocation::StatusPurpose;
+
+/// Information used to determine the current status of a [`Credential`][crate::credential::Credential]
+/// using the `StatusList2021` specification.
+///
+/// See: <https://w3c-ccg.github.io/vc-status-list-2021/#statuslist2021entry>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusList2021Entry(Status);
+
+impl StatusList2021Entry {
+  const PURPOSE_PROPERTY: &'static str = "statusPurpose";
+  const INDEX_PROPERTY: &'static str = "statusListIndex";
+  const CREDENTIAL_PROPERTY: &'static str = "statusListCredential";
+  /// Type name of the status list entry.
+  pub const TYPE: &'static str = "StatusList2021Entry";
+
+  /// Creates a new `StatusList2021Entry` pointing at `index` in the status list credential
+  /// identified by `status_list_credential`.
+  ///
+  /// The `id` of the entry is set to the `status_list_credential` URL with `index` as its fragment.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use identity_core::common::Url;
+  /// # use identity_credential::revocation::StatusList2021Entry;
+  /// # use identity_credential::revocation::StatusPurpose;
+  /// let list_url: Url = Url::parse("https://example.com/credentials/status/3").unwrap();
+  /// let entry: StatusList2021Entry = StatusList2021Entry::new(list_url, StatusPurpose::Revocation, 94567);
+  /// assert_eq!(entry.id().as_str(), "https://example.com/credentials/status/3#94567");
+  /// assert_eq!(entry.index().unwrap(), 94567);
+  /// ```
+  pub fn new(status_list_credential: Url, purpose: StatusPurpose, index: usize) -> Self {
+    let mut id: Url = status_list_credential.clone();
+    id.set_fragment(Some(&index.to_string()));
+
+    let mut object = Object::new();
+    object.insert(Self::PURPOSE_PROPERTY.to_owned(), Value::String(purpose.to_string()));
+    object.insert(Self::INDEX_PROPERTY.to_owned(), Value::String(index.to_string()));
+    object.insert(
+      Self::CREDENTIAL_PROPERTY.to_owned(),
+      Value::String(status_list_credential.into_string()),
+    );
+    StatusList2021Entry(Status::new_with_properties(id, Self::TYPE.to_owned(), object))
+  }
+
+  /// Returns the `id` of the entry.
+  pub fn id(&self) -> &Url {
+    &self.0.id
+  }
+
+  /// Returns the purpose of the status list this entry points to.
+  pub fn purpose(&self) -> Result<StatusPurpose> {
+    let purpose: &str = self.string_property(Self::PURPOSE_PROPERTY)?;
+    StatusPurpose::from_str(purpose)
+      .map_err(|_| Error::InvalidStatus(format!("unsupported {} '{purpose}'", Self::PURPOSE_PROPERTY)))
+  }
+
+  /// Returns the index of the credential in the status list.
+  pub fn index(&self) -> Result<usize> {
+    let index: &str = self.string_property(Self::INDEX_PROPERTY)?;
+    usize::from_str(index).map_err(|err| {
+      Error::InvalidStatus(format!(
+        "{} cannot be converted to an unsigned integer: {err}",
+        Self::INDEX_PROPERTY
+      ))
+    })
+  }
+
+  /// Returns the URL of the status list credential.
+  pub fn status_list_credential(&self) -> Result<Url> {
+    let url: &str = self.string_property(Self::CREDENTIAL_PROPERTY)?;
+    Url::parse(url).map_err(|err| Error::InvalidStatus(format!("invalid {} '{url}': {err}", Self::CREDENTIAL_PROPERTY)))
+  }
+
+  fn string_property(&self, name: &str) -> Result<&str> {
+    match self.0.properties.get(name) {
+      Some(Value::String(value)) => Ok(value),
+      Some(_) => Err(Error::InvalidStatus(format!("property '{name}' is not a string"))),
+      None => Err(Error::InvalidStatus(format!("missing required property '{name}'"))),
+    }
+  }
+}
+
+impl TryFrom<Status> for StatusList2021Entry {
+  type Error = Error;
+
+  fn try_from(status: Status) -> Result<Self> {
+    if status.type_ != Self::TYPE {
+      return Err(Error::InvalidStatus(format!(
+        "expected type '{}', got '{}'",
+        Self::TYPE,
+        status.type_
+      )));
+    }
+
+    let entry: Self = Self(status);
+    entry.purpose()?;
+    entry.index()?;
+    entry.status_list_credential()?;
+
+    Ok(entry)
+  }
+}
+
+impl From<StatusList2021Entry> for Status {
+  fn from(entry: StatusList2021Entry) -> Self {
+    entry.0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+
+  use super::*;
+
+  #[test]
+  fn test_entry_roundtrip() {
+    let list_url: Url = Url::parse("https://example.com/credentials/status/3").unwrap();
+    let entry: StatusList2021Entry = StatusList2021Entry::new(list_url.clone(), StatusPurpose::Suspension, 5);
+    assert_eq!(entry.purpose().unwrap(), StatusPurpose::Suspension);
+    assert_eq!(entry.index().unwrap(), 5);
+    assert_eq!(entry.status_list_credential().unwrap(), list_url);
+
+    let status: Status = entry.clone().into();
+    assert_eq!(StatusList2021Entry::try_from(status).unwrap(), entry);
+  }
+
+  #[test]
+  fn test_entry_from_json() {
+    // Example from the StatusList2021 specification.
+    let status: Status = Status::from_json_value(serde_json::json!({
+      "id": "https://example.com/credentials/status/3#94567",
+      "type": "StatusList2021Entry",
+      "statusPurpose": "revocation",
+      "statusListIndex": "94567",
+      "statusListCredential": "https://example.com/credentials/status/3"
+    }))
+    .unwrap();
+    let entry: StatusList2021Entry = StatusList2021Entry::try_from(status).unwrap();
+    assert_eq!(entry.purpose().unwrap(), StatusPurpose::Revocation);
+    assert_eq!(entry.index().unwrap(), 94567);
+  }
+
+  #[test]
+  fn test_invalid_entries() {
+    let valid = serde_json::json!({
+      "id": "https://example.com/credentials/status/3#94567",
+      "type": "StatusList2021Entry",
+      "statusPurpose": "revocation",
+      "statusListIndex": "94567",
+      "statusListCredential": "https://example.com/credentials/status/3"
+    });
+
+    for (key, value) in [
+      ("type", serde_json::json!("RevocationBitmap2022")),
+      ("statusPurpose", serde_json::json!("expiration")),
+      ("statusListIndex", serde_json::json!(94567)),
+      ("statusListIndex", serde_json::json!("-1")),
+      ("statusListCredential", serde_json::json!("not a url")),
+    ] {
+      let mut json = valid.clone();
+      json[key] = value;
+      let status: Status = Status::from_json_value(json).unwrap();
+      assert!(matches!(
+        StatusList2021Entry::try_from(status).unwrap_err(),
+        Error::InvalidStatus(_)
+      ));
+    }
+  }
+}
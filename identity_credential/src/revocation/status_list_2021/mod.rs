@@ -0,0 +1,15 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of the [StatusList2021](https://w3c-ccg.github.io/vc-status-list-2021/) specification.
+
+mod credential;
+mod entry;
+mod status_list;
+
+pub use self::credential::StatusList2021Credential;
+pub use self::credential::StatusList2021CredentialBuilder;
+pub use self::entry::StatusList2021Entry;
+pub use self::status_list::StatusList2021;
+pub use self::status_list::MAX_STATUS_LIST_BYTES;
+pub use self::status_list::MINIMUM_STATUS_LIST_LENGTH;
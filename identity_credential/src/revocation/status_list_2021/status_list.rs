@@ -0,0 +1,191 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+use std::io::Write;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use identity_core::utils::Base;
+use identity_core::utils::BaseEncoding;
+
+use crate::revocation::RevocationError;
+use crate::revocation::RevocationResult;
+
+/// The minimum number of entries of a status list, as recommended for group privacy.
+///
+/// See: <https://w3c-ccg.github.io/vc-status-list-2021/#revocation-bitstring-length>
+pub const MINIMUM_STATUS_LIST_LENGTH: usize = 131_072;
+
+/// The maximum size in bytes of a decompressed status list accepted by [`StatusList2021::from_encoded_str`].
+///
+/// This bounds the memory used to decode untrusted `encodedList` properties, which might otherwise decompress to an
+/// arbitrary size.
+pub const MAX_STATUS_LIST_BYTES: usize = 16 * 1024 * 1024;
+
+/// A bitstring where each bit holds the status of a single credential.
+///
+/// The first index corresponds to the most significant bit of the first byte.
+///
+/// See: <https://w3c-ccg.github.io/vc-status-list-2021/#bitstring-encoding>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusList2021(Box<[u8]>);
+
+impl StatusList2021 {
+  /// Constructs a new [`StatusList2021`] with at least `num_entries` entries, all of which are unset.
+  ///
+  /// The length is rounded up to the nearest multiple of 8. Lists shorter than
+  /// [`MINIMUM_STATUS_LIST_LENGTH`] are allowed, but provide less group privacy.
+  pub fn new(num_entries: usize) -> Self {
+    Self(vec![0; (num_entries + 7) / 8].into_boxed_slice())
+  }
+
+  /// Returns the number of entries in the list.
+  pub fn len(&self) -> usize {
+    self.0.len() * 8
+  }
+
+  /// Returns `true` if the list has no entries.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Returns whether the entry at the given `index` is set.
+  pub fn get(&self, index: usize) -> RevocationResult<bool> {
+    let (byte, mask): (usize, u8) = self.position(index)?;
+    Ok(self.0[byte] & mask != 0)
+  }
+
+  /// Sets the entry at the given `index` to `value`.
+  ///
+  /// Returns the previous value of the entry.
+  pub fn set(&mut self, index: usize, value: bool) -> RevocationResult<bool> {
+    let (byte, mask): (usize, u8) = self.position(index)?;
+    let previous: bool = self.0[byte] & mask != 0;
+    if value {
+      self.0[byte] |= mask;
+    } else {
+      self.0[byte] &= !mask;
+    }
+    Ok(previous)
+  }
+
+  /// Returns the GZIP-compressed, base64url-encoded representation of the list,
+  /// as used by the `encodedList` property.
+  pub fn to_encoded_str(&self) -> RevocationResult<String> {
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+      .write_all(&self.0)
+      .map_err(RevocationError::StatusListEncodingError)?;
+    let compressed: Vec<u8> = encoder.finish().map_err(RevocationError::StatusListEncodingError)?;
+    Ok(BaseEncoding::encode(&compressed, Base::Base64Url))
+  }
+
+  /// Decodes a [`StatusList2021`] from the GZIP-compressed, base64-encoded `encodedList` property.
+  ///
+  /// Both the base64url and the standard base64 alphabet are accepted, with or without padding.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`RevocationError::StatusListTooLarge`] if the list decompresses to more than
+  /// [`MAX_STATUS_LIST_BYTES`].
+  pub fn from_encoded_str(encoded: &str) -> RevocationResult<Self> {
+    let unpadded: &str = encoded.trim_end_matches('=');
+    let compressed: Vec<u8> = BaseEncoding::decode(unpadded, Base::Base64Url)
+      .or_else(|_| BaseEncoding::decode(unpadded, Base::Base64))
+      .map_err(|err| RevocationError::Base64DecodingError(encoded.to_owned(), err))?;
+
+    // Read at most one byte more than allowed, to detect lists that exceed the limit.
+    let mut bitstring: Vec<u8> = Vec::new();
+    GzDecoder::new(compressed.as_slice())
+      .take(MAX_STATUS_LIST_BYTES as u64 + 1)
+      .read_to_end(&mut bitstring)
+      .map_err(RevocationError::StatusListDecodingError)?;
+    if bitstring.len() > MAX_STATUS_LIST_BYTES {
+      return Err(RevocationError::StatusListTooLarge(MAX_STATUS_LIST_BYTES));
+    }
+
+    Ok(Self(bitstring.into_boxed_slice()))
+  }
+
+  fn position(&self, index: usize) -> RevocationResult<(usize, u8)> {
+    if index >= self.len() {
+      return Err(RevocationError::StatusListIndexOutOfBounds { index, len: self.len() });
+    }
+    Ok((index / 8, 0b1000_0000 >> (index % 8)))
+  }
+}
+
+impl Default for StatusList2021 {
+  fn default() -> Self {
+    Self::new(MINIMUM_STATUS_LIST_LENGTH)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_set_and_get() {
+    let mut status_list: StatusList2021 = StatusList2021::new(20);
+    assert_eq!(status_list.len(), 24);
+
+    assert!(!status_list.set(0, true).unwrap());
+    assert!(!status_list.set(13, true).unwrap());
+    assert!(status_list.set(13, true).unwrap());
+    assert!(status_list.get(0).unwrap());
+    assert!(status_list.get(13).unwrap());
+    assert!(!status_list.get(12).unwrap());
+    // Index 0 is the most significant bit of the first byte.
+    assert_eq!(status_list.0[0], 0b1000_0000);
+    assert_eq!(status_list.0[1], 0b0000_0100);
+
+    assert!(status_list.set(13, false).unwrap());
+    assert!(!status_list.get(13).unwrap());
+
+    assert!(matches!(
+      status_list.get(24).unwrap_err(),
+      RevocationError::StatusListIndexOutOfBounds { index: 24, len: 24 }
+    ));
+  }
+
+  #[test]
+  fn test_encoding_roundtrip() {
+    let mut status_list: StatusList2021 = StatusList2021::default();
+    assert_eq!(status_list.len(), MINIMUM_STATUS_LIST_LENGTH);
+    status_list.set(94567, true).unwrap();
+
+    let encoded: String = status_list.to_encoded_str().unwrap();
+    let decoded: StatusList2021 = StatusList2021::from_encoded_str(&encoded).unwrap();
+    assert_eq!(decoded, status_list);
+    assert!(decoded.get(94567).unwrap());
+  }
+
+  #[test]
+  fn test_decode_spec_example() {
+    // The empty 16KB list from the StatusList2021 specification example.
+    let encoded: &str = "H4sIAAAAAAAAA-3BMQEAAADCoPVPbQwfoAAAAAAAAAAAAAAAAAAAAIC3AYbSVKsAQAAA";
+    let status_list: StatusList2021 = StatusList2021::from_encoded_str(encoded).unwrap();
+    assert_eq!(status_list.len(), MINIMUM_STATUS_LIST_LENGTH);
+    assert!((0..status_list.len()).all(|index| !status_list.get(index).unwrap()));
+  }
+
+  #[test]
+  fn test_decode_too_large() {
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&vec![0; MAX_STATUS_LIST_BYTES + 1]).unwrap();
+    let encoded: String = BaseEncoding::encode(&encoder.finish().unwrap(), Base::Base64Url);
+
+    assert!(matches!(
+      StatusList2021::from_encoded_str(&encoded).unwrap_err(),
+      RevocationError::StatusListTooLarge(MAX_STATUS_LIST_BYTES)
+    ));
+
+    // A list of exactly the maximum size is accepted.
+    let status_list: StatusList2021 = StatusList2021::new(MAX_STATUS_LIST_BYTES * 8);
+    let decoded: StatusList2021 = StatusList2021::from_encoded_str(&status_list.to_encoded_str().unwrap()).unwrap();
+    assert_eq!(decoded.len(), MAX_STATUS_LIST_BYTES * 8);
+  }
+}
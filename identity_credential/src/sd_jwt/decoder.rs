@@ -0,0 +1,348 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+
+use super::Disclosure;
+use super::Hasher;
+use super::SdJwtError;
+use super::SdJwtResult;
+use super::Sha256Hasher;
+use super::ARRAY_DIGEST_KEY;
+use super::DIGESTS_KEY;
+use super::SD_ALG;
+
+/// Reconstructs the disclosed claims of an [`Object`] whose values were concealed by an
+/// [`SdObjectEncoder`](super::SdObjectEncoder).
+///
+/// Each digest referenced by an `_sd` property or an array element of the form `{"...": <digest>}` is replaced by
+/// the value of the matching disclosure. Digests without a matching disclosure are removed together with the
+/// `_sd_alg` claim.
+pub struct SdObjectDecoder {
+  hashers: BTreeMap<String, Box<dyn Hasher>>,
+}
+
+impl SdObjectDecoder {
+  /// Creates a new [`SdObjectDecoder`] supporting SHA-256.
+  pub fn new() -> Self {
+    let mut decoder: Self = Self::empty();
+    decoder.add_hasher(Box::new(Sha256Hasher::new()));
+    decoder
+  }
+
+  /// Creates a new [`SdObjectDecoder`] without any hashers.
+  pub fn empty() -> Self {
+    Self {
+      hashers: BTreeMap::new(),
+    }
+  }
+
+  /// Adds a [`Hasher`] for its [`alg_name`](Hasher::alg_name), returning the hasher it replaces.
+  pub fn add_hasher(&mut self, hasher: Box<dyn Hasher>) -> Option<Box<dyn Hasher>> {
+    self.hashers.insert(hasher.alg_name().to_owned(), hasher)
+  }
+
+  /// Removes the [`Hasher`] for `alg_name`.
+  pub fn remove_hasher(&mut self, alg_name: &str) -> Option<Box<dyn Hasher>> {
+    self.hashers.remove(alg_name)
+  }
+
+  /// Returns the [`Hasher`] used for `object`, which is determined by its `_sd_alg` claim and defaults to SHA-256.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `_sd_alg` is not a string or no hasher is available for it.
+  pub fn hasher_for(&self, object: &Object) -> SdJwtResult<&dyn Hasher> {
+    let alg: &str = match object.get(SD_ALG) {
+      Some(Value::String(alg)) => alg,
+      Some(_) => return Err(SdJwtError::DataTypeMismatch(format!("`{SD_ALG}` is not a string"))),
+      None => Sha256Hasher::ALG_NAME,
+    };
+    self
+      .hashers
+      .get(alg)
+      .map(AsRef::as_ref)
+      .ok_or_else(|| SdJwtError::UnsupportedHashAlgorithm(alg.to_owned()))
+  }
+
+  /// Replaces the digests in `object` by the values of the matching `disclosures` and removes all undisclosed
+  /// digests and the `_sd_alg` claim.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if
+  /// - a disclosure is malformed, or not referenced by any digest,
+  /// - a digest is referenced more than once,
+  /// - a disclosed property already exists in the object containing its digest,
+  /// - an object property is disclosed by an array element disclosure or vice versa,
+  /// - the `_sd_alg` claim names an unsupported algorithm.
+  pub fn decode(&self, object: &Object, disclosures: &[String]) -> SdJwtResult<Object> {
+    let hasher: &dyn Hasher = self.hasher_for(object)?;
+
+    let mut disclosures_by_digest: HashMap<String, Disclosure> = HashMap::new();
+    for disclosure in disclosures {
+      let digest: String = hasher.encoded_digest(disclosure);
+      if disclosures_by_digest
+        .insert(digest.clone(), Disclosure::parse(disclosure)?)
+        .is_some()
+      {
+        return Err(SdJwtError::DuplicateDigest(digest));
+      }
+    }
+
+    let mut context: DecodingContext<'_> = DecodingContext {
+      disclosures: &disclosures_by_digest,
+      processed: HashSet::new(),
+    };
+    let mut decoded: Object = context.decode_object(object.iter())?.into_iter().collect();
+    decoded.remove(SD_ALG);
+
+    if let Some((_, unused)) = disclosures_by_digest
+      .iter()
+      .find(|(digest, _)| !context.processed.contains(digest.as_str()))
+    {
+      return Err(SdJwtError::UnusedDisclosure(unused.to_string()));
+    }
+
+    Ok(decoded)
+  }
+}
+
+impl Default for SdObjectDecoder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl core::fmt::Debug for SdObjectDecoder {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("SdObjectDecoder")
+      .field("hashers", &self.hashers.keys().collect::<Vec<_>>())
+      .finish()
+  }
+}
+
+struct DecodingContext<'a> {
+  disclosures: &'a HashMap<String, Disclosure>,
+  processed: HashSet<String>,
+}
+
+impl<'a> DecodingContext<'a> {
+  fn decode_value(&mut self, value: &Value) -> SdJwtResult<Value> {
+    match value {
+      Value::Object(object) => self
+        .decode_object(object.iter())
+        .map(|decoded| Value::Object(decoded.into_iter().collect())),
+      Value::Array(array) => self.decode_array(array).map(Value::Array),
+      value => Ok(value.clone()),
+    }
+  }
+
+  fn decode_object<'o>(
+    &mut self,
+    object: impl Iterator<Item = (&'o String, &'o Value)>,
+  ) -> SdJwtResult<BTreeMap<String, Value>> {
+    let mut decoded: BTreeMap<String, Value> = BTreeMap::new();
+    let mut digests: Option<&Value> = None;
+
+    for (key, value) in object {
+      if key == DIGESTS_KEY {
+        digests = Some(value);
+      } else {
+        decoded.insert(key.clone(), self.decode_value(value)?);
+      }
+    }
+
+    let digests: &[Value] = match digests {
+      Some(Value::Array(digests)) => digests,
+      Some(_) => return Err(SdJwtError::DataTypeMismatch(format!("`{DIGESTS_KEY}` is not an array"))),
+      None => &[],
+    };
+    for digest in digests {
+      let digest: &str = digest
+        .as_str()
+        .ok_or_else(|| SdJwtError::DataTypeMismatch(format!("`{DIGESTS_KEY}` contains a non-string digest")))?;
+      let disclosure: &Disclosure = match self.disclose(digest)? {
+        Some(disclosure) => disclosure,
+        None => continue,
+      };
+      let name: &str = disclosure.claim_name().ok_or_else(|| {
+        SdJwtError::DataTypeMismatch(format!(
+          "array element disclosure `{disclosure}` is referenced by `{DIGESTS_KEY}`"
+        ))
+      })?;
+      if name == DIGESTS_KEY || name == ARRAY_DIGEST_KEY || decoded.contains_key(name) {
+        return Err(SdJwtError::ClaimCollision(name.to_owned()));
+      }
+      let value: Value = self.decode_value(disclosure.claim_value())?;
+      decoded.insert(name.to_owned(), value);
+    }
+
+    Ok(decoded)
+  }
+
+  fn decode_array(&mut self, array: &[Value]) -> SdJwtResult<Vec<Value>> {
+    let mut decoded: Vec<Value> = Vec::with_capacity(array.len());
+
+    for element in array {
+      let digest: Option<&Value> = element
+        .as_object()
+        .filter(|object| object.len() == 1)
+        .and_then(|object| object.get(ARRAY_DIGEST_KEY));
+      let digest: &Value = match digest {
+        Some(digest) => digest,
+        None => {
+          decoded.push(self.decode_value(element)?);
+          continue;
+        }
+      };
+
+      let digest: &str = digest
+        .as_str()
+        .ok_or_else(|| SdJwtError::DataTypeMismatch(format!("`{ARRAY_DIGEST_KEY}` is not a string")))?;
+      // Undisclosed elements and decoys are removed.
+      let disclosure: &Disclosure = match self.disclose(digest)? {
+        Some(disclosure) => disclosure,
+        None => continue,
+      };
+      if disclosure.claim_name().is_some() {
+        return Err(SdJwtError::DataTypeMismatch(format!(
+          "object property disclosure `{disclosure}` is referenced by an array element"
+        )));
+      }
+      decoded.push(self.decode_value(disclosure.claim_value())?);
+    }
+
+    Ok(decoded)
+  }
+
+  /// Marks `digest` as processed and returns the matching disclosure, if any.
+  fn disclose(&mut self, digest: &str) -> SdJwtResult<Option<&'a Disclosure>> {
+    if !self.processed.insert(digest.to_owned()) {
+      return Err(SdJwtError::DuplicateDigest(digest.to_owned()));
+    }
+    Ok(self.disclosures.get(digest))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+
+  use super::super::SdObjectEncoder;
+  use super::*;
+
+  fn encoder() -> SdObjectEncoder {
+    SdObjectEncoder::new(
+      Object::from_json(
+        r#"{
+          "sub": "did:example:holder",
+          "vc": {
+            "credentialSubject": {
+              "name": "Alice",
+              "degree": { "type": "BachelorDegree", "name": "Bachelor of Science" },
+              "nationalities": ["DE", "US"]
+            }
+          }
+        }"#,
+      )
+      .unwrap(),
+    )
+  }
+
+  #[test]
+  fn test_decode_all_disclosed() {
+    let mut encoder: SdObjectEncoder = encoder();
+    let original: Object = encoder.object().clone();
+    let disclosures: Vec<String> = [
+      "/vc/credentialSubject/degree/type",
+      "/vc/credentialSubject/degree",
+      "/vc/credentialSubject/nationalities/0",
+      "/vc/credentialSubject/name",
+    ]
+    .into_iter()
+    .map(|path| encoder.conceal(path, None).unwrap().into_string())
+    .collect();
+    encoder.add_decoys("/vc/credentialSubject", 2).unwrap();
+    encoder.add_decoys("/vc/credentialSubject/nationalities", 1).unwrap();
+    encoder.add_sd_alg_property();
+
+    let decoded: Object = SdObjectDecoder::new().decode(encoder.object(), &disclosures).unwrap();
+    assert_eq!(decoded, original);
+  }
+
+  #[test]
+  fn test_decode_partially_disclosed() {
+    let mut encoder: SdObjectEncoder = encoder();
+    let degree_type: Disclosure = encoder.conceal("/vc/credentialSubject/degree/type", None).unwrap();
+    let degree: Disclosure = encoder.conceal("/vc/credentialSubject/degree", None).unwrap();
+    encoder.conceal("/vc/credentialSubject/nationalities/1", None).unwrap();
+    encoder.conceal("/vc/credentialSubject/name", None).unwrap();
+    encoder.add_sd_alg_property();
+
+    let decoded: Object = SdObjectDecoder::new()
+      .decode(encoder.object(), &[degree.to_string()])
+      .unwrap();
+    assert!(!decoded.contains_key(SD_ALG));
+    let subject: &Value = &decoded["vc"]["credentialSubject"];
+    assert!(subject.get("name").is_none());
+    assert_eq!(subject["nationalities"], Value::Array(vec![Value::from("DE")]));
+    assert_eq!(
+      subject["degree"],
+      Value::Object(std::iter::once(("name".to_owned(), Value::from("Bachelor of Science"))).collect())
+    );
+
+    // A nested disclosure is not referenced unless its parent is disclosed.
+    assert!(matches!(
+      SdObjectDecoder::new().decode(encoder.object(), &[degree_type.to_string()]),
+      Err(SdJwtError::UnusedDisclosure(_))
+    ));
+  }
+
+  #[test]
+  fn test_decode_invalid() {
+    let mut encoder: SdObjectEncoder = encoder();
+    let name: Disclosure = encoder.conceal("/vc/credentialSubject/name", None).unwrap();
+    let object: Object = encoder.object().clone();
+
+    // Duplicate disclosures.
+    assert!(matches!(
+      SdObjectDecoder::new().decode(&object, &[name.to_string(), name.to_string()]),
+      Err(SdJwtError::DuplicateDigest(_))
+    ));
+
+    // Disclosed claims must not overwrite existing ones.
+    let mut colliding: Object = object.clone();
+    colliding
+      .get_mut("vc")
+      .and_then(|vc| vc["credentialSubject"].as_object_mut())
+      .unwrap()
+      .insert("name".to_owned(), Value::from("Mallory"));
+    assert!(matches!(
+      SdObjectDecoder::new().decode(&colliding, &[name.to_string()]),
+      Err(SdJwtError::ClaimCollision(_))
+    ));
+
+    // Digests must be referenced only once.
+    let mut duplicated: Object = object.clone();
+    let digests: Value = duplicated["vc"]["credentialSubject"][DIGESTS_KEY].clone();
+    duplicated.insert(DIGESTS_KEY.to_owned(), digests);
+    assert!(matches!(
+      SdObjectDecoder::new().decode(&duplicated, &[]),
+      Err(SdJwtError::DuplicateDigest(_))
+    ));
+
+    // Unsupported hash algorithm.
+    let mut unsupported: Object = object;
+    unsupported.insert(SD_ALG.to_owned(), Value::from("sha-1"));
+    assert!(matches!(
+      SdObjectDecoder::new().decode(&unsupported, &[]),
+      Err(SdJwtError::UnsupportedHashAlgorithm(_))
+    ));
+  }
+}
@@ -0,0 +1,160 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_jose::jwu;
+
+use super::SdJwtError;
+use super::SdJwtResult;
+
+/// A disclosure of a concealed claim.
+///
+/// A disclosure is the base64url-encoded JSON array `[salt, claim_name, claim_value]` of an object property or
+/// `[salt, claim_value]` of an array element. Its digest replaces the claim in the payload of the SD-JWT.
+///
+/// The encoded form is retained, since digests must be computed over the exact string issued.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disclosure {
+  salt: String,
+  claim_name: Option<String>,
+  claim_value: Value,
+  encoded: String,
+}
+
+impl Disclosure {
+  /// Creates a new [`Disclosure`] of the property `claim_name` or, if `None`, of an array element.
+  pub fn new(salt: String, claim_name: Option<String>, claim_value: Value) -> Self {
+    let array: Vec<Value> = match claim_name {
+      Some(ref name) => vec![
+        Value::String(salt.clone()),
+        Value::String(name.clone()),
+        claim_value.clone(),
+      ],
+      None => vec![Value::String(salt.clone()), claim_value.clone()],
+    };
+    // Serializing a `Value` cannot fail, since all of its map keys are strings.
+    let json: Vec<u8> = array.to_json_vec().expect("serializing a JSON value does not fail");
+
+    Self {
+      salt,
+      claim_name,
+      claim_value,
+      encoded: jwu::encode_b64(json),
+    }
+  }
+
+  /// Parses an encoded disclosure.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `disclosure` is not a base64url-encoded JSON array of a string salt, an optional string
+  /// claim name and a claim value.
+  pub fn parse(disclosure: &str) -> SdJwtResult<Self> {
+    let decoded: Vec<u8> = jwu::decode_b64(disclosure)
+      .map_err(|_| SdJwtError::InvalidDisclosure(format!("`{disclosure}` is not base64url-encoded")))?;
+    let array: Vec<Value> = Vec::from_json_slice(&decoded)
+      .map_err(|_| SdJwtError::InvalidDisclosure(format!("`{disclosure}` is not a JSON array")))?;
+
+    let string = |value: &Value| -> SdJwtResult<String> {
+      value.as_str().map(ToOwned::to_owned).ok_or_else(|| {
+        SdJwtError::InvalidDisclosure(format!("`{disclosure}` contains a salt or name that is not a string"))
+      })
+    };
+
+    let (salt, claim_name, claim_value): (String, Option<String>, Value) = match array.as_slice() {
+      [salt, value] => (string(salt)?, None, value.clone()),
+      [salt, name, value] => (string(salt)?, Some(string(name)?), value.clone()),
+      _ => {
+        return Err(SdJwtError::InvalidDisclosure(format!(
+          "`{disclosure}` must contain two or three elements"
+        )))
+      }
+    };
+
+    Ok(Self {
+      salt,
+      claim_name,
+      claim_value,
+      encoded: disclosure.to_owned(),
+    })
+  }
+
+  /// Returns the salt.
+  pub fn salt(&self) -> &str {
+    &self.salt
+  }
+
+  /// Returns the name of the disclosed property, or `None` for an array element.
+  pub fn claim_name(&self) -> Option<&str> {
+    self.claim_name.as_deref()
+  }
+
+  /// Returns the disclosed value.
+  pub fn claim_value(&self) -> &Value {
+    &self.claim_value
+  }
+
+  /// Returns the encoded disclosure.
+  pub fn as_str(&self) -> &str {
+    &self.encoded
+  }
+
+  /// Consumes the disclosure and returns its encoded form.
+  pub fn into_string(self) -> String {
+    self.encoded
+  }
+}
+
+impl Display for Disclosure {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str(&self.encoded)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_property_disclosure() {
+    // Example from the SD-JWT specification.
+    let disclosure: Disclosure = Disclosure::parse("WyI2cU1RdlJMNWhhaiIsICJmYW1pbHlfbmFtZSIsICJNw7ZiaXVzIl0").unwrap();
+    assert_eq!(disclosure.salt(), "6qMQvRL5haj");
+    assert_eq!(disclosure.claim_name(), Some("family_name"));
+    assert_eq!(disclosure.claim_value(), &Value::String("Möbius".to_owned()));
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let property: Disclosure = Disclosure::new("salt".to_owned(), Some("name".to_owned()), Value::from(42));
+    assert_eq!(Disclosure::parse(property.as_str()).unwrap(), property);
+
+    let element: Disclosure = Disclosure::new("salt".to_owned(), None, Value::from("DE"));
+    let parsed: Disclosure = Disclosure::parse(&element.to_string()).unwrap();
+    assert_eq!(parsed.claim_name(), None);
+    assert_eq!(parsed, element);
+  }
+
+  #[test]
+  fn test_parse_invalid() {
+    assert!(matches!(
+      Disclosure::parse("not base64!"),
+      Err(SdJwtError::InvalidDisclosure(_))
+    ));
+    let single: String = jwu::encode_b64(r#"["salt"]"#);
+    assert!(matches!(
+      Disclosure::parse(&single),
+      Err(SdJwtError::InvalidDisclosure(_))
+    ));
+    let numeric_name: String = jwu::encode_b64(r#"["salt", 1, "value"]"#);
+    assert!(matches!(
+      Disclosure::parse(&numeric_name),
+      Err(SdJwtError::InvalidDisclosure(_))
+    ));
+  }
+}
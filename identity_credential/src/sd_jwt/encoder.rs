@@ -0,0 +1,330 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_jose::jwu;
+
+use super::Disclosure;
+use super::Hasher;
+use super::SdJwtError;
+use super::SdJwtResult;
+use super::Sha256Hasher;
+
+/// The property of an object holding the digests of its concealed properties.
+pub const DIGESTS_KEY: &str = "_sd";
+/// The property of the object replacing a concealed array element.
+pub const ARRAY_DIGEST_KEY: &str = "...";
+/// The claim naming the hash algorithm used for the digests.
+pub const SD_ALG: &str = "_sd_alg";
+/// The default number of random bytes of a salt.
+pub const DEFAULT_SALT_SIZE: usize = 16;
+
+/// Conceals properties and array elements of an [`Object`], e.g. the JWT claims set of a credential, by
+/// replacing them with the digests of their [`Disclosure`]s.
+///
+/// Values are referenced by [JSON pointers](https://www.rfc-editor.org/rfc/rfc6901), e.g.
+/// `/vc/credentialSubject/degree/name` or `/vc/credentialSubject/nationalities/0`. Concealing a nested value
+/// before its parent makes the nested value selectively disclosable only once the parent is disclosed.
+///
+/// Concealing an array element replaces it with `{"...": <digest>}`, so the indices of subsequent elements are
+/// unaffected.
+#[derive(Debug, Clone)]
+pub struct SdObjectEncoder<H: Hasher = Sha256Hasher> {
+  object: Object,
+  hasher: H,
+  salt_size: usize,
+}
+
+impl SdObjectEncoder {
+  /// Creates a new [`SdObjectEncoder`] using SHA-256 for the digests.
+  pub fn new(object: Object) -> Self {
+    Self::with_hasher(object, Sha256Hasher::new())
+  }
+
+  /// Creates a new [`SdObjectEncoder`] using SHA-256 for the digests from a serialized JSON object, such as the
+  /// output of [`Credential::serialize_jwt`](crate::credential::Credential::serialize_jwt).
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `json` is not a JSON object.
+  pub fn try_from_json(json: &str) -> SdJwtResult<Self> {
+    Object::from_json(json).map(Self::new).map_err(SdJwtError::JsonError)
+  }
+}
+
+impl<H: Hasher> SdObjectEncoder<H> {
+  /// Creates a new [`SdObjectEncoder`] using the given `hasher` for the digests.
+  pub fn with_hasher(object: Object, hasher: H) -> Self {
+    Self {
+      object,
+      hasher,
+      salt_size: DEFAULT_SALT_SIZE,
+    }
+  }
+
+  /// Sets the number of random bytes of generated salts.
+  #[must_use]
+  pub fn salt_size(mut self, salt_size: usize) -> Self {
+    self.salt_size = salt_size;
+    self
+  }
+
+  /// Conceals the value referenced by the JSON pointer `path` and returns its [`Disclosure`].
+  ///
+  /// A random salt is generated unless `salt` is provided.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `path` does not reference an existing property or array element, references an `_sd`
+  /// property, or if the `_sd` property of the parent object is not an array.
+  pub fn conceal(&mut self, path: &str, salt: Option<String>) -> SdJwtResult<Disclosure> {
+    let (parent_path, key) = split_path(path)?;
+    let salt: String = match salt {
+      Some(salt) => salt,
+      None => self.generate_salt()?,
+    };
+    let hasher: &H = &self.hasher;
+
+    with_value(&mut self.object, |root| {
+      let parent: &mut Value = root
+        .pointer_mut(parent_path)
+        .ok_or_else(|| SdJwtError::InvalidPath(path.to_owned()))?;
+
+      if let Some(array) = parent.as_array_mut() {
+        let element: &mut Value = key
+          .parse::<usize>()
+          .ok()
+          .and_then(|index| array.get_mut(index))
+          .ok_or_else(|| SdJwtError::InvalidPath(path.to_owned()))?;
+
+        let disclosure: Disclosure = Disclosure::new(salt, None, element.take());
+        *element = array_digest(hasher.encoded_digest(disclosure.as_str()));
+        return Ok(disclosure);
+      }
+
+      if key == DIGESTS_KEY || key == SD_ALG {
+        return Err(SdJwtError::InvalidPath(path.to_owned()));
+      }
+      check_digests(parent)?;
+      let value: Value = parent
+        .as_object_mut()
+        .and_then(|object| object.remove(&key))
+        .ok_or_else(|| SdJwtError::InvalidPath(path.to_owned()))?;
+
+      let disclosure: Disclosure = Disclosure::new(salt, Some(key), value);
+      push_digests(parent, std::iter::once(hasher.encoded_digest(disclosure.as_str())));
+      Ok(disclosure)
+    })
+  }
+
+  /// Adds `number` decoy digests to the object or array referenced by the JSON pointer `path`, hiding the number of
+  /// concealed values from verifiers. The root object is referenced by the empty string.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `path` does not reference an object or array.
+  pub fn add_decoys(&mut self, path: &str, number: usize) -> SdJwtResult<()> {
+    let decoys: Vec<String> = (0..number)
+      .map(|_| self.generate_salt().map(|salt| self.hasher.encoded_digest(&salt)))
+      .collect::<SdJwtResult<_>>()?;
+
+    with_value(&mut self.object, |root| match root.pointer_mut(path) {
+      Some(Value::Array(array)) => {
+        array.extend(decoys.into_iter().map(array_digest));
+        Ok(())
+      }
+      Some(parent) if parent.is_object() => {
+        check_digests(parent)?;
+        push_digests(parent, decoys.into_iter());
+        Ok(())
+      }
+      _ => Err(SdJwtError::InvalidPath(path.to_owned())),
+    })
+  }
+
+  /// Sets the `_sd_alg` claim to the name of the hash algorithm, returning the previous value.
+  ///
+  /// This should be called once all values are concealed and before the object is signed.
+  pub fn add_sd_alg_property(&mut self) -> Option<Value> {
+    self
+      .object
+      .insert(SD_ALG.to_owned(), Value::String(self.hasher.alg_name().to_owned()))
+  }
+
+  /// Returns a reference to the object.
+  pub fn object(&self) -> &Object {
+    &self.object
+  }
+
+  /// Consumes the encoder and returns the object.
+  pub fn into_object(self) -> Object {
+    self.object
+  }
+
+  /// Serializes the object as JSON, e.g. to be used as the payload of a JWS.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the object cannot be serialized.
+  pub fn try_to_string(&self) -> SdJwtResult<String> {
+    self.object.to_json().map_err(SdJwtError::JsonError)
+  }
+
+  /// Returns a reference to the hasher.
+  pub fn hasher(&self) -> &H {
+    &self.hasher
+  }
+
+  fn generate_salt(&self) -> SdJwtResult<String> {
+    let mut salt: Vec<u8> = vec![0; self.salt_size];
+    crypto::utils::rand::fill(&mut salt).map_err(|_| SdJwtError::SaltGenerationError)?;
+    Ok(jwu::encode_b64(salt))
+  }
+}
+
+/// Applies `f` to `object` as a [`Value`], which supports JSON pointers.
+fn with_value<T>(object: &mut Object, f: impl FnOnce(&mut Value) -> SdJwtResult<T>) -> SdJwtResult<T> {
+  let mut root: Value = Value::Object(std::mem::take(object).into_iter().collect());
+  let result: SdJwtResult<T> = f(&mut root);
+  if let Value::Object(map) = root {
+    *object = map.into_iter().collect();
+  }
+  result
+}
+
+/// Splits a JSON pointer into the pointer to the parent and the unescaped last reference token.
+fn split_path(path: &str) -> SdJwtResult<(&str, String)> {
+  let (parent, key) = path
+    .rsplit_once('/')
+    .filter(|(parent, _)| parent.is_empty() || parent.starts_with('/'))
+    .ok_or_else(|| SdJwtError::InvalidPath(path.to_owned()))?;
+  Ok((parent, key.replace("~1", "/").replace("~0", "~")))
+}
+
+/// Ensures that the `_sd` property of `object`, if present, is an array.
+fn check_digests(object: &Value) -> SdJwtResult<()> {
+  match object.get(DIGESTS_KEY) {
+    Some(digests) if !digests.is_array() => {
+      Err(SdJwtError::DataTypeMismatch(format!("`{DIGESTS_KEY}` is not an array")))
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Appends `digests` to the `_sd` property of `object`, which is kept sorted to hide the original order of the
+/// properties.
+fn push_digests(object: &mut Value, digests: impl Iterator<Item = String>) {
+  if let Some(Value::Array(array)) = object
+    .as_object_mut()
+    .map(|object| object.entry(DIGESTS_KEY).or_insert_with(|| Value::Array(Vec::new())))
+  {
+    array.extend(digests.map(Value::String));
+    array.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+  }
+}
+
+/// Returns the object replacing a concealed array element.
+fn array_digest(digest: String) -> Value {
+  Value::Object(std::iter::once((ARRAY_DIGEST_KEY.to_owned(), Value::String(digest))).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn object() -> Object {
+    Object::from_json(
+      r#"{
+        "sub": "did:example:holder",
+        "vc": {
+          "credentialSubject": {
+            "name": "Alice",
+            "degree": { "type": "BachelorDegree", "name": "Bachelor of Science" },
+            "nationalities": ["DE", "US"]
+          }
+        }
+      }"#,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn test_conceal_property() {
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(object());
+    let disclosure: Disclosure = encoder
+      .conceal("/vc/credentialSubject/name", Some("salt".to_owned()))
+      .unwrap();
+    assert_eq!(disclosure.claim_name(), Some("name"));
+    assert_eq!(disclosure.claim_value(), &Value::from("Alice"));
+
+    let subject: &Value = &encoder.object()["vc"]["credentialSubject"];
+    assert!(subject.get("name").is_none());
+    assert_eq!(
+      subject[DIGESTS_KEY],
+      Value::Array(vec![Value::String(Sha256Hasher.encoded_digest(disclosure.as_str()))])
+    );
+  }
+
+  #[test]
+  fn test_conceal_array_element() {
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(object());
+    let disclosure: Disclosure = encoder.conceal("/vc/credentialSubject/nationalities/1", None).unwrap();
+    assert_eq!(disclosure.claim_name(), None);
+    assert_eq!(disclosure.claim_value(), &Value::from("US"));
+
+    let nationalities: &Value = &encoder.object()["vc"]["credentialSubject"]["nationalities"];
+    assert_eq!(nationalities[0], Value::from("DE"));
+    assert_eq!(
+      nationalities[1][ARRAY_DIGEST_KEY],
+      Value::String(Sha256Hasher.encoded_digest(disclosure.as_str()))
+    );
+  }
+
+  #[test]
+  fn test_conceal_nested() {
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(object());
+    encoder.conceal("/vc/credentialSubject/degree/type", None).unwrap();
+    let degree: Disclosure = encoder.conceal("/vc/credentialSubject/degree", None).unwrap();
+    assert!(degree.claim_value()[DIGESTS_KEY].is_array());
+    assert_eq!(degree.claim_value()["name"], Value::from("Bachelor of Science"));
+  }
+
+  #[test]
+  fn test_decoys_and_sd_alg() {
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(object());
+    encoder.add_decoys("", 3).unwrap();
+    encoder.add_decoys("/vc/credentialSubject/nationalities", 2).unwrap();
+    assert_eq!(encoder.object()[DIGESTS_KEY].as_array().unwrap().len(), 3);
+    assert_eq!(
+      encoder.object()["vc"]["credentialSubject"]["nationalities"]
+        .as_array()
+        .unwrap()
+        .len(),
+      4
+    );
+
+    assert!(encoder.add_sd_alg_property().is_none());
+    assert_eq!(encoder.object()[SD_ALG], Value::from(Sha256Hasher::ALG_NAME));
+  }
+
+  #[test]
+  fn test_conceal_invalid_path() {
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(object());
+    for path in [
+      "",
+      "sub",
+      "/missing",
+      "/vc/credentialSubject/nationalities/2",
+      "/sub/name",
+    ] {
+      assert!(matches!(encoder.conceal(path, None), Err(SdJwtError::InvalidPath(_))));
+    }
+    encoder.conceal("/sub", None).unwrap();
+    assert!(matches!(encoder.conceal("/_sd", None), Err(SdJwtError::InvalidPath(_))));
+    // Failed operations leave the object intact.
+    assert!(encoder.object().contains_key("vc"));
+  }
+}
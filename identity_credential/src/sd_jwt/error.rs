@@ -0,0 +1,42 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// A result type designed for SD-JWT handling.
+pub type SdJwtResult<T> = std::result::Result<T, SdJwtError>;
+
+/// Errors occurring when concealing, presenting or reconstructing selectively disclosable claims.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum SdJwtError {
+  #[error("invalid disclosure: {0}")]
+  /// Indicates that a disclosure is not a base64url-encoded JSON array of the expected form.
+  InvalidDisclosure(String),
+  #[error("invalid path `{0}`")]
+  /// Indicates that a JSON pointer does not reference a concealable value.
+  InvalidPath(String),
+  #[error("invalid SD-JWT: {0}")]
+  /// Indicates that an SD-JWT or the payload of its JWT is malformed.
+  InvalidSdJwt(&'static str),
+  #[error("unsupported hash algorithm `{0}`")]
+  /// Indicates that no [`Hasher`](super::Hasher) is available for the `_sd_alg` of an SD-JWT.
+  UnsupportedHashAlgorithm(String),
+  #[error("digest `{0}` appears more than once")]
+  /// Indicates that a digest is referenced more than once.
+  DuplicateDigest(String),
+  #[error("claim `{0}` is already present")]
+  /// Indicates that a disclosed claim would overwrite an existing claim.
+  ClaimCollision(String),
+  #[error("disclosure `{0}` is not referenced by any digest")]
+  /// Indicates that a disclosure does not correspond to any digest of the payload.
+  UnusedDisclosure(String),
+  #[error("unexpected data type: {0}")]
+  /// Indicates that a value does not have the type required by its position, e.g. an `_sd` property that is not
+  /// an array of strings.
+  DataTypeMismatch(String),
+  #[error("salt could not be generated")]
+  /// Indicates a failure of the random number generator used for salts.
+  SaltGenerationError,
+  #[error("JSON serialization or deserialization failed")]
+  /// Indicates that a value could not be converted to or from JSON.
+  JsonError(#[source] identity_core::Error),
+}
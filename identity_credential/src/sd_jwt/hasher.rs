@@ -0,0 +1,64 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+use identity_jose::jwu;
+
+/// Computes the digests that replace concealed claims.
+///
+/// The name returned by [`Hasher::alg_name`] is the value of the `_sd_alg` claim and must be registered in the
+/// [IANA Named Information Hash Algorithm Registry](https://www.iana.org/assignments/named-information/named-information.xhtml).
+pub trait Hasher: Send + Sync {
+  /// Hashes `input`.
+  fn digest(&self, input: &[u8]) -> Vec<u8>;
+
+  /// Returns the name of the hash algorithm.
+  fn alg_name(&self) -> &str;
+
+  /// Returns the base64url-encoded digest of an encoded `disclosure`.
+  fn encoded_digest(&self, disclosure: &str) -> String {
+    jwu::encode_b64(self.digest(disclosure.as_bytes()))
+  }
+}
+
+/// A [`Hasher`] using SHA-256, the default hash algorithm of SD-JWT.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sha256Hasher;
+
+impl Sha256Hasher {
+  /// The name of the algorithm used in the `_sd_alg` claim.
+  pub const ALG_NAME: &'static str = "sha-256";
+
+  /// Creates a new [`Sha256Hasher`].
+  pub fn new() -> Self {
+    Self
+  }
+}
+
+impl Hasher for Sha256Hasher {
+  fn digest(&self, input: &[u8]) -> Vec<u8> {
+    let mut digest: [u8; SHA256_LEN] = [0; SHA256_LEN];
+    SHA256(input, &mut digest);
+    digest.to_vec()
+  }
+
+  fn alg_name(&self) -> &str {
+    Self::ALG_NAME
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_sha256_encoded_digest() {
+    // Example from the SD-JWT specification.
+    let disclosure: &str = "WyI2cU1RdlJMNWhhaiIsICJmYW1pbHlfbmFtZSIsICJNw7ZiaXVzIl0";
+    assert_eq!(
+      Sha256Hasher::new().encoded_digest(disclosure),
+      "uutlBuYeMDyjLLTpf6Jxi7yNkEF35jdyWMn9U7b_RYY"
+    );
+  }
+}
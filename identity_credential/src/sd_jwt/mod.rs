@@ -0,0 +1,30 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Selective disclosure of claims with [SD-JWT](https://datatracker.ietf.org/doc/draft-ietf-oauth-selective-disclosure-jwt/).
+//!
+//! The issuer replaces selected claims of a JWT claims set with salted digests using an [`SdObjectEncoder`] and
+//! hands the resulting [`Disclosure`]s to the holder together with the signed JWT. The holder chooses which
+//! disclosures to present with [`SdJwt::present`], and the verifier reconstructs the disclosed claims with an
+//! [`SdObjectDecoder`].
+
+mod decoder;
+mod disclosure;
+mod encoder;
+mod error;
+mod hasher;
+#[allow(clippy::module_inception)]
+mod sd_jwt;
+
+pub use self::decoder::SdObjectDecoder;
+pub use self::disclosure::Disclosure;
+pub use self::encoder::SdObjectEncoder;
+pub use self::encoder::ARRAY_DIGEST_KEY;
+pub use self::encoder::DEFAULT_SALT_SIZE;
+pub use self::encoder::DIGESTS_KEY;
+pub use self::encoder::SD_ALG;
+pub use self::error::SdJwtError;
+pub use self::error::SdJwtResult;
+pub use self::hasher::Hasher;
+pub use self::hasher::Sha256Hasher;
+pub use self::sd_jwt::SdJwt;
@@ -0,0 +1,298 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_jose::jwu;
+
+use super::Disclosure;
+use super::Hasher;
+use super::SdJwtError;
+use super::SdJwtResult;
+use super::SdObjectDecoder;
+use super::ARRAY_DIGEST_KEY;
+use super::DIGESTS_KEY;
+
+/// An SD-JWT consisting of a signed JWT, the disclosures of concealed claims and an optional key binding JWT.
+///
+/// It is serialized as `<JWT>~<Disclosure 1>~...~<Disclosure N>~<optional KB-JWT>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdJwt {
+  jwt: String,
+  disclosures: Vec<String>,
+  key_binding_jwt: Option<String>,
+}
+
+impl SdJwt {
+  /// Creates a new [`SdJwt`] from its components.
+  pub fn new(jwt: String, disclosures: Vec<String>, key_binding_jwt: Option<String>) -> Self {
+    Self {
+      jwt,
+      disclosures,
+      key_binding_jwt,
+    }
+  }
+
+  /// Parses a serialized SD-JWT.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `sd_jwt` does not contain a `~` separator or any of its components is empty.
+  pub fn parse(sd_jwt: &str) -> SdJwtResult<Self> {
+    let mut components: Vec<&str> = sd_jwt.split('~').collect();
+    if components.len() < 2 {
+      return Err(SdJwtError::InvalidSdJwt("missing `~` separator"));
+    }
+
+    let key_binding_jwt: Option<String> = components
+      .pop()
+      .filter(|kb_jwt| !kb_jwt.is_empty())
+      .map(ToOwned::to_owned);
+    let jwt: String = components.remove(0).to_owned();
+    if jwt.is_empty() || components.iter().any(|disclosure| disclosure.is_empty()) {
+      return Err(SdJwtError::InvalidSdJwt("empty component"));
+    }
+
+    Ok(Self {
+      jwt,
+      disclosures: components.into_iter().map(ToOwned::to_owned).collect(),
+      key_binding_jwt,
+    })
+  }
+
+  /// Returns the signed JWT.
+  pub fn jwt(&self) -> &str {
+    &self.jwt
+  }
+
+  /// Returns the encoded disclosures.
+  pub fn disclosures(&self) -> &[String] {
+    &self.disclosures
+  }
+
+  /// Returns the key binding JWT.
+  pub fn key_binding_jwt(&self) -> Option<&str> {
+    self.key_binding_jwt.as_deref()
+  }
+
+  /// Sets the key binding JWT, returning the previous one.
+  pub fn set_key_binding_jwt(&mut self, key_binding_jwt: Option<String>) -> Option<String> {
+    std::mem::replace(&mut self.key_binding_jwt, key_binding_jwt)
+  }
+
+  /// Serializes the SD-JWT.
+  pub fn presentation(&self) -> String {
+    let mut presentation: String = self.jwt.clone();
+    for disclosure in &self.disclosures {
+      presentation.push('~');
+      presentation.push_str(disclosure);
+    }
+    presentation.push('~');
+    if let Some(ref kb_jwt) = self.key_binding_jwt {
+      presentation.push_str(kb_jwt);
+    }
+    presentation
+  }
+
+  /// Decodes the payload of the JWT **without** verifying its signature.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the JWT is not in compact serialization or its payload is not a base64url-encoded JSON
+  /// object.
+  pub fn unverified_payload(&self) -> SdJwtResult<Object> {
+    let payload: &str = self
+      .jwt
+      .split('.')
+      .nth(1)
+      .ok_or(SdJwtError::InvalidSdJwt("the JWT is not in compact serialization"))?;
+    let payload: Vec<u8> =
+      jwu::decode_b64(payload).map_err(|_| SdJwtError::InvalidSdJwt("the JWT payload is not base64url-encoded"))?;
+    Object::from_json_slice(&payload).map_err(SdJwtError::JsonError)
+  }
+
+  /// Creates an SD-JWT presenting only the disclosures for which `select` returns `true`.
+  ///
+  /// A disclosure of a nested claim is only presented if all of its enclosing concealed claims are presented, since
+  /// verifiers reject disclosures that are not referenced. The key binding JWT is not retained, as it must be
+  /// created for the new presentation.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the payload of the JWT cannot be decoded, its `_sd_alg` is not SHA-256, or a disclosure is
+  /// malformed. See [`SdJwt::present_with_decoder`] to support other hash algorithms.
+  pub fn present<F>(&self, select: F) -> SdJwtResult<Self>
+  where
+    F: FnMut(&Disclosure) -> bool,
+  {
+    self.present_with_decoder(&SdObjectDecoder::new(), select)
+  }
+
+  /// Creates an SD-JWT presenting only the disclosures for which `select` returns `true`, using the hashers of
+  /// `decoder`.
+  ///
+  /// See [`SdJwt::present`].
+  pub fn present_with_decoder<F>(&self, decoder: &SdObjectDecoder, mut select: F) -> SdJwtResult<Self>
+  where
+    F: FnMut(&Disclosure) -> bool,
+  {
+    let payload: Object = self.unverified_payload()?;
+    let hasher: &dyn Hasher = decoder.hasher_for(&payload)?;
+
+    let mut selected: HashMap<String, Disclosure> = HashMap::new();
+    for encoded in &self.disclosures {
+      let disclosure: Disclosure = Disclosure::parse(encoded)?;
+      if select(&disclosure) {
+        selected.insert(hasher.encoded_digest(encoded), disclosure);
+      }
+    }
+
+    // Keep the selected disclosures reachable from the payload through other selected disclosures.
+    let mut pending: Vec<String> = Vec::new();
+    collect_digests(&Value::Object(payload.into_iter().collect()), &mut pending);
+    let mut reachable: HashSet<String> = HashSet::new();
+    while let Some(digest) = pending.pop() {
+      if let Some(disclosure) = selected.get(&digest) {
+        if reachable.insert(digest) {
+          collect_digests(disclosure.claim_value(), &mut pending);
+        }
+      }
+    }
+
+    let disclosures: Vec<String> = self
+      .disclosures
+      .iter()
+      .filter(|disclosure| reachable.contains(&hasher.encoded_digest(disclosure)))
+      .cloned()
+      .collect();
+
+    Ok(Self::new(self.jwt.clone(), disclosures, None))
+  }
+}
+
+/// Appends the digests referenced by `value` and its nested values, excluding those inside disclosures, to `digests`.
+fn collect_digests(value: &Value, digests: &mut Vec<String>) {
+  match value {
+    Value::Object(object) => {
+      if let Some(Value::Array(sd)) = object.get(DIGESTS_KEY) {
+        digests.extend(sd.iter().filter_map(Value::as_str).map(ToOwned::to_owned));
+      }
+      match object.get(ARRAY_DIGEST_KEY) {
+        Some(Value::String(digest)) if object.len() == 1 => digests.push(digest.clone()),
+        _ => object.values().for_each(|value| collect_digests(value, digests)),
+      }
+    }
+    Value::Array(array) => array.iter().for_each(|value| collect_digests(value, digests)),
+    _ => {}
+  }
+}
+
+impl Display for SdJwt {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str(&self.presentation())
+  }
+}
+
+impl FromStr for SdJwt {
+  type Err = SdJwtError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::parse(s)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::ToJson;
+
+  use super::super::SdObjectEncoder;
+  use super::*;
+
+  fn sd_jwt() -> SdJwt {
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(
+      Object::from_json(
+        r#"{
+          "vc": {
+            "credentialSubject": {
+              "name": "Alice",
+              "degree": { "type": "BachelorDegree", "name": "Bachelor of Science" },
+              "nationalities": ["DE", "US"]
+            }
+          }
+        }"#,
+      )
+      .unwrap(),
+    );
+    let disclosures: Vec<String> = [
+      "/vc/credentialSubject/degree/type",
+      "/vc/credentialSubject/degree",
+      "/vc/credentialSubject/nationalities/1",
+      "/vc/credentialSubject/name",
+    ]
+    .into_iter()
+    .map(|path| encoder.conceal(path, None).unwrap().into_string())
+    .collect();
+    encoder.add_sd_alg_property();
+
+    // The signature is irrelevant for presenting disclosures.
+    let jwt: String = format!(
+      "eyJhbGciOiJFZERTQSJ9.{}.c2lnbmF0dXJl",
+      jwu::encode_b64(encoder.object().to_json_vec().unwrap())
+    );
+    SdJwt::new(jwt, disclosures, None)
+  }
+
+  #[test]
+  fn test_parse_round_trip() {
+    let sd_jwt: SdJwt = sd_jwt();
+    let presentation: String = sd_jwt.presentation();
+    assert!(presentation.ends_with('~'));
+    assert_eq!(SdJwt::parse(&presentation).unwrap(), sd_jwt);
+
+    let mut with_kb_jwt: SdJwt = sd_jwt;
+    with_kb_jwt.set_key_binding_jwt(Some("kb.jwt.signature".to_owned()));
+    let parsed: SdJwt = with_kb_jwt.to_string().parse().unwrap();
+    assert_eq!(parsed.key_binding_jwt(), Some("kb.jwt.signature"));
+    assert_eq!(parsed.disclosures().len(), 4);
+
+    let without_disclosures: SdJwt = SdJwt::parse("header.payload.signature~").unwrap();
+    assert!(without_disclosures.disclosures().is_empty());
+    assert!(without_disclosures.key_binding_jwt().is_none());
+
+    assert!(SdJwt::parse("header.payload.signature").is_err());
+    assert!(SdJwt::parse("header.payload.signature~~").is_err());
+  }
+
+  #[test]
+  fn test_present() {
+    let sd_jwt: SdJwt = sd_jwt();
+
+    let presented: SdJwt = sd_jwt
+      .present(|disclosure| disclosure.claim_name() == Some("name"))
+      .unwrap();
+    assert_eq!(presented.disclosures().len(), 1);
+    let decoded: Object = SdObjectDecoder::new()
+      .decode(&presented.unverified_payload().unwrap(), presented.disclosures())
+      .unwrap();
+    let subject: &Value = &decoded["vc"]["credentialSubject"];
+    assert_eq!(subject["name"], Value::from("Alice"));
+    assert!(subject.get("degree").is_none());
+    assert_eq!(subject["nationalities"], Value::Array(vec![Value::from("DE")]));
+
+    // The degree type is dropped without the enclosing degree.
+    let presented: SdJwt = sd_jwt
+      .present(|disclosure| disclosure.claim_name() == Some("type") || disclosure.claim_name().is_none())
+      .unwrap();
+    assert_eq!(presented.disclosures().len(), 1);
+
+    let presented: SdJwt = sd_jwt.present(|_| true).unwrap();
+    assert_eq!(presented.disclosures(), sd_jwt.disclosures());
+  }
+}
@@ -6,8 +6,6 @@ use std::str::FromStr;
 use identity_document::document::CoreDocument;
 use serde::Serialize;
 
-#[cfg(feature = "revocation-bitmap")]
-use crate::revocation::RevocationBitmap;
 use identity_core::common::OneOrMany;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
@@ -16,16 +14,24 @@ use identity_did::DID;
 use identity_document::verifiable::VerifierOptions;
 
 use crate::credential::Credential;
-#[cfg(feature = "revocation-bitmap")]
-use crate::credential::RevocationBitmapStatus;
+#[cfg(feature = "status-list-2021")]
+use crate::revocation::StatusList2021Credential;
+#[cfg(feature = "status-list-2021")]
+use crate::revocation::StatusList2021Entry;
 
 use super::errors::CompoundCredentialValidationError;
 use super::errors::SignerContext;
 use super::errors::ValidationError;
-#[cfg(feature = "revocation-bitmap")]
 use super::validation_options::StatusCheck;
+#[cfg(feature = "status-list-2021")]
+use super::CredentialStatusChecker;
+use super::CredentialStatusCheckers;
 use super::CredentialValidationOptions;
 use super::FailFast;
+#[cfg(feature = "json-schema")]
+use super::SchemaLoader;
+#[cfg(feature = "status-list-2021")]
+use super::StatusList2021StatusChecker;
 use super::SubjectHolderRelationship;
 
 /// A struct for validating [`Credential`]s.
@@ -34,6 +40,11 @@ use super::SubjectHolderRelationship;
 pub struct CredentialValidator;
 
 type ValidationUnitResult = std::result::Result<(), ValidationError>;
+
+#[cfg(feature = "json-schema")]
+const JSON_SCHEMA_VALIDATOR_2018: &str = "JsonSchemaValidator2018";
+#[cfg(feature = "json-schema")]
+const JSON_SCHEMA_2023: &str = "JsonSchema2023";
 type CredentialValidationResult = std::result::Result<(), CompoundCredentialValidationError>;
 
 impl CredentialValidator {
@@ -162,14 +173,108 @@ impl CredentialValidator {
       .ok_or(ValidationError::SubjectHolderRelationship)
   }
 
-  /// Checks whether the credential status has been revoked.
+  /// Validates each credential subject against the JSON Schemas referenced in the `credentialSchema` of the
+  /// [`Credential`], loaded with `loader`.
   ///
-  /// Only supports `BitmapRevocation2022`.
-  #[cfg(feature = "revocation-bitmap")]
+  /// Only schemas of type `JsonSchemaValidator2018` or `JsonSchema2023` are considered, all others are skipped.
+  /// `JsonSchemaValidator2018` schemas are compiled as draft-07 and `JsonSchema2023` schemas as draft 2020-12,
+  /// regardless of the dialect declared with `$schema`.
+  ///
+  /// # Errors
+  /// Returns one [`ValidationError::CredentialSchemaViolation`] per violation, or a
+  /// [`ValidationError::CredentialSchemaLoadError`] if a schema cannot be loaded or compiled.
+  #[cfg(feature = "json-schema")]
+  pub fn check_credential_schema<T>(
+    credential: &Credential<T>,
+    loader: &dyn SchemaLoader,
+  ) -> std::result::Result<(), Vec<ValidationError>> {
+    use identity_core::common::Value;
+    use identity_core::convert::ToJson;
+    use jsonschema::Draft;
+    use jsonschema::JSONSchema;
+
+    let mut errors: Vec<ValidationError> = Vec::new();
+    for schema in credential.credential_schema.iter().filter(|schema| {
+      schema
+        .types
+        .iter()
+        .any(|type_| type_ == JSON_SCHEMA_VALIDATOR_2018 || type_ == JSON_SCHEMA_2023)
+    }) {
+      let load_error =
+        |source: Box<dyn std::error::Error + Send + Sync + 'static>| ValidationError::CredentialSchemaLoadError {
+          schema: schema.id.to_string(),
+          source,
+        };
+
+      let draft: Draft = if schema.types.iter().any(|type_| type_ == JSON_SCHEMA_2023) {
+        Draft::Draft202012
+      } else {
+        Draft::Draft7
+      };
+      let compiled: JSONSchema = match loader.load(schema).and_then(|value| {
+        JSONSchema::options()
+          .with_draft(draft)
+          .compile(&value)
+          .map_err(|err| err.to_string().into())
+      }) {
+        Ok(compiled) => compiled,
+        Err(err) => {
+          errors.push(load_error(err));
+          continue;
+        }
+      };
+
+      for subject in credential.credential_subject.iter() {
+        let instance: Value = match subject.to_json_value() {
+          Ok(instance) => instance,
+          Err(err) => {
+            errors.push(load_error(err.into()));
+            continue;
+          }
+        };
+        if let Err(violations) = compiled.validate(&instance) {
+          errors.extend(violations.map(|violation| ValidationError::CredentialSchemaViolation {
+            schema: schema.id.to_string(),
+            instance_path: violation.instance_path.to_string(),
+            message: violation.to_string(),
+          }));
+        };
+      }
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Checks whether the credential status has been revoked, using the default [`CredentialStatusCheckers`].
+  ///
+  /// See [`CredentialValidator::check_status_with_checkers`].
   pub fn check_status<DOC: AsRef<CoreDocument>, T>(
     credential: &Credential<T>,
     trusted_issuers: &[DOC],
     status_check: StatusCheck,
+  ) -> ValidationUnitResult {
+    Self::check_status_with_checkers(
+      credential,
+      trusted_issuers,
+      status_check,
+      &CredentialStatusCheckers::default(),
+    )
+  }
+
+  /// Checks whether the credential status has been revoked.
+  ///
+  /// Dispatches on the type of the `credentialStatus` to the matching checker in `checkers`.
+  /// No `StatusList2021Entry` checker is registered by default, as the referenced status list credential must be
+  /// fetched first. Use [`CredentialValidator::check_status_with_status_list_2021`] to check such entries.
+  pub fn check_status_with_checkers<DOC: AsRef<CoreDocument>, T>(
+    credential: &Credential<T>,
+    trusted_issuers: &[DOC],
+    status_check: StatusCheck,
+    checkers: &CredentialStatusCheckers,
   ) -> ValidationUnitResult {
     if status_check == StatusCheck::SkipAll {
       return Ok(());
@@ -177,53 +282,64 @@ impl CredentialValidator {
 
     match &credential.credential_status {
       None => Ok(()),
-      Some(status) => {
-        // Check status is supported.
-        if status.type_ != RevocationBitmap::TYPE {
-          if status_check == StatusCheck::SkipUnsupported {
-            return Ok(());
-          }
-          return Err(ValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
-            "unsupported type '{}'",
-            status.type_
-          ))));
+      Some(status) => match checkers.get(&status.type_) {
+        Some(checker) => {
+          let trusted_issuers: Vec<&CoreDocument> = trusted_issuers.iter().map(AsRef::as_ref).collect();
+          checker.check_status(status, &credential.issuer, &trusted_issuers)
         }
-        let status: RevocationBitmapStatus =
-          RevocationBitmapStatus::try_from(status.clone()).map_err(ValidationError::InvalidStatus)?;
-
-        // Check the credential index against the issuer's DID Document.
-        let issuer_did: CoreDID = Self::extract_issuer(credential)?;
-        trusted_issuers
-          .iter()
-          .find(|issuer| <CoreDocument>::id(issuer.as_ref()) == &issuer_did)
-          .ok_or(ValidationError::DocumentMismatch(SignerContext::Issuer))
-          .and_then(|issuer| CredentialValidator::check_revocation_bitmap_status(issuer, status))
-      }
+        None => Self::unsupported_status(status_check, &format!("unsupported type '{}'", status.type_)),
+      },
     }
   }
 
-  /// Check the given `status` against the matching [`RevocationBitmap`] service in the
-  /// issuer's DID Document.
-  #[cfg(feature = "revocation-bitmap")]
-  fn check_revocation_bitmap_status<DOC: AsRef<CoreDocument> + ?Sized>(
-    issuer: &DOC,
-    status: RevocationBitmapStatus,
-  ) -> ValidationUnitResult {
-    use crate::revocation::RevocationDocumentExt;
-
-    let issuer_service_url: identity_did::DIDUrl = status.id().map_err(ValidationError::InvalidStatus)?;
+  /// Checks whether the credential status has been revoked or suspended, fetching `StatusList2021`
+  /// credentials with `fetch`.
+  ///
+  /// `fetch` is called with the `statusListCredential` URL of a `StatusList2021Entry` and must return
+  /// the status list credential. It is responsible for verifying the proof of the returned credential.
+  /// The fetched credential is checked with a [`StatusList2021StatusChecker`].
+  ///
+  /// All other status types are checked as in [`CredentialValidator::check_status`].
+  #[cfg(feature = "status-list-2021")]
+  pub async fn check_status_with_status_list_2021<DOC, T, F, FUT, E>(
+    credential: &Credential<T>,
+    trusted_issuers: &[DOC],
+    status_check: StatusCheck,
+    fetch: &F,
+  ) -> ValidationUnitResult
+  where
+    DOC: AsRef<CoreDocument>,
+    F: Fn(Url) -> FUT,
+    FUT: std::future::Future<Output = std::result::Result<Credential, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    match &credential.credential_status {
+      Some(status) if status_check != StatusCheck::SkipAll && status.type_ == StatusList2021Entry::TYPE => {
+        let entry: StatusList2021Entry =
+          StatusList2021Entry::try_from(status.clone()).map_err(ValidationError::InvalidStatus)?;
+        let list_url: Url = entry.status_list_credential().map_err(ValidationError::InvalidStatus)?;
+        let list_credential: Credential = fetch(list_url.clone())
+          .await
+          .map_err(|err| ValidationError::StatusListFetchError(err.into()))?;
+        let list_credential: StatusList2021Credential = StatusList2021Credential::try_from(list_credential)
+          .map_err(|err| ValidationError::InvalidStatus(crate::Error::InvalidStatus(err.to_string())))?;
+
+        let trusted_issuers: Vec<&CoreDocument> = trusted_issuers.iter().map(AsRef::as_ref).collect();
+        StatusList2021StatusChecker::new()
+          .with(list_url, list_credential)
+          .check_status(status, &credential.issuer, &trusted_issuers)
+      }
+      _ => Self::check_status(credential, trusted_issuers, status_check),
+    }
+  }
 
-    // Check whether index is revoked.
-    let revocation_bitmap: RevocationBitmap = issuer
-      .as_ref()
-      .resolve_revocation_bitmap(issuer_service_url.into())
-      .map_err(|_| ValidationError::ServiceLookupError)?;
-    let index: u32 = status.index().map_err(ValidationError::InvalidStatus)?;
-    if revocation_bitmap.is_revoked(index) {
-      Err(ValidationError::Revoked)
-    } else {
-      Ok(())
+  fn unsupported_status(status_check: StatusCheck, reason: &str) -> ValidationUnitResult {
+    if status_check == StatusCheck::SkipUnsupported {
+      return Ok(());
     }
+    Err(ValidationError::InvalidStatus(crate::Error::InvalidStatus(
+      reason.to_owned(),
+    )))
   }
 
   // This method takes a slice of issuer's instead of a single issuer in order to better accommodate presentation
@@ -236,9 +352,28 @@ impl CredentialValidator {
     relationship_criterion: Option<(&Url, SubjectHolderRelationship)>,
     fail_fast: FailFast,
   ) -> CredentialValidationResult {
+    Self::validate_with_signature_check(credential, issuers, options, relationship_criterion, fail_fast, || {
+      Self::verify_signature(credential, issuers, &options.verifier_options)
+    })
+  }
+
+  // Runs the same validations as `validate_extended`, but checks the signature with `signature_check`. This allows
+  // credentials whose signature is not an embedded proof to be validated in the same way.
+  // `issuers` is only needed to check the credential status.
+  pub(crate) fn validate_with_signature_check<DOC, T, F>(
+    credential: &Credential<T>,
+    issuers: &[DOC],
+    options: &CredentialValidationOptions,
+    relationship_criterion: Option<(&Url, SubjectHolderRelationship)>,
+    fail_fast: FailFast,
+    signature_check: F,
+  ) -> CredentialValidationResult
+  where
+    DOC: AsRef<CoreDocument>,
+    F: FnOnce() -> ValidationUnitResult,
+  {
     // Run all single concern validations in turn and fail immediately if `fail_fast` is true.
-    let signature_validation =
-      std::iter::once_with(|| Self::verify_signature(credential, issuers, &options.verifier_options));
+    let signature_validation = std::iter::once_with(signature_check);
 
     let expiry_date_validation = std::iter::once_with(|| {
       Self::check_expires_on_or_after(credential, options.earliest_expiry_date.unwrap_or_default())
@@ -250,6 +385,10 @@ impl CredentialValidator {
 
     let structure_validation = std::iter::once_with(|| Self::check_structure(credential));
 
+    let revocation_validation = std::iter::once_with(|| {
+      Self::check_status_with_checkers(credential, issuers, options.status, &options.status_checkers)
+    });
+
     let subject_holder_validation = std::iter::once_with(|| {
       relationship_criterion
         .map(|(holder, relationship)| Self::check_subject_holder_relationship(credential, holder, relationship))
@@ -260,12 +399,21 @@ impl CredentialValidator {
       .chain(expiry_date_validation)
       .chain(structure_validation)
       .chain(subject_holder_validation)
-      .chain(signature_validation);
+      .chain(signature_validation)
+      .chain(revocation_validation);
 
-    #[cfg(feature = "revocation-bitmap")]
+    // Schema validation may report multiple violations, each of which is a separate error.
+    #[cfg(feature = "json-schema")]
     let validation_units_iter = {
-      let revocation_validation = std::iter::once_with(|| Self::check_status(credential, issuers, options.status));
-      validation_units_iter.chain(revocation_validation)
+      let schema_validation = std::iter::once_with(|| {
+        options
+          .schema_loader
+          .as_deref()
+          .map(|loader| Self::check_credential_schema(credential, loader))
+          .unwrap_or(Ok(()))
+      })
+      .flat_map(|result| result.err().unwrap_or_default().into_iter().map(Err));
+      validation_units_iter.chain(schema_validation)
     };
 
     let validation_units_error_iter = validation_units_iter.filter_map(|result| result.err());
@@ -312,8 +460,12 @@ mod tests {
   use identity_document::document::CoreDocument;
   use identity_document::service::Service;
 
+  #[cfg(feature = "revocation-bitmap")]
+  use crate::credential::RevocationBitmapStatus;
   use crate::credential::Status;
   use crate::credential::Subject;
+  #[cfg(feature = "revocation-bitmap")]
+  use crate::revocation::RevocationBitmap;
   use crate::validator::test_utils;
   use crate::validator::CredentialValidationOptions;
 
@@ -539,6 +691,45 @@ mod tests {
     assert!(CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError).is_ok());
   }
 
+  #[test]
+  fn test_full_validation_without_status_checkers() {
+    let Setup {
+      issuer_doc,
+      issuer_key,
+      unsigned_credential: mut credential,
+      issuance_date,
+      expiration_date,
+    } = Setup::new();
+    credential.credential_status = Some(Status::new(
+      Url::parse("https://example.com/status/1").unwrap(),
+      "CustomStatus2023".to_owned(),
+    ));
+    issuer_doc
+      .signer(issuer_key.private())
+      .options(ProofOptions::default())
+      .method(issuer_doc.methods(None).get(0).unwrap().id())
+      .sign(&mut credential)
+      .unwrap();
+
+    let options = CredentialValidationOptions::default()
+      .latest_issuance_date(issuance_date.checked_add(Duration::days(14)).unwrap())
+      .earliest_expiry_date(expiration_date.checked_sub(Duration::hours(1)).unwrap());
+
+    // Without any registered checker, every status type is unsupported.
+    let options = options.status_checkers(CredentialStatusCheckers::empty());
+    for (status_check, expected) in [
+      (StatusCheck::Strict, false),
+      (StatusCheck::SkipUnsupported, true),
+      (StatusCheck::SkipAll, true),
+    ] {
+      let options = options.clone().status_check(status_check);
+      assert_eq!(
+        CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError).is_ok(),
+        expected
+      );
+    }
+  }
+
   #[test]
   fn test_matches_issuer_did_unrelated_issuer() {
     let Setup {
@@ -816,6 +1007,365 @@ mod tests {
     }
   }
 
+  #[cfg(feature = "revocation-bitmap")]
+  #[test]
+  fn test_check_status_suspension() {
+    use crate::revocation::RevocationDocumentExt;
+    use crate::revocation::StatusPurpose;
+
+    let Setup {
+      mut issuer_doc,
+      unsigned_credential: mut credential,
+      ..
+    } = Setup::new();
+    let revocation_url: identity_did::DIDUrl = issuer_doc.id().to_url().join("#revocation-service").unwrap();
+    let suspension_url: identity_did::DIDUrl = issuer_doc.id().to_url().join("#suspension-service").unwrap();
+    for (url, purpose) in [
+      (&revocation_url, StatusPurpose::Revocation),
+      (&suspension_url, StatusPurpose::Suspension),
+    ] {
+      issuer_doc
+        .insert_service(RevocationBitmap::new().to_service(url.clone(), purpose).unwrap())
+        .unwrap();
+    }
+    let index: u32 = 7;
+
+    // A suspended credential is reported as such and can be unsuspended.
+    credential.credential_status =
+      Some(RevocationBitmapStatus::new_with_purpose(suspension_url.clone(), index, StatusPurpose::Suspension).into());
+    assert!(CredentialValidator::check_status(&credential, &[&issuer_doc], StatusCheck::Strict).is_ok());
+    issuer_doc.suspend_credentials(&suspension_url, &[index]).unwrap();
+    assert!(matches!(
+      CredentialValidator::check_status(&credential, &[&issuer_doc], StatusCheck::Strict).unwrap_err(),
+      ValidationError::Suspended
+    ));
+    issuer_doc.unsuspend_credentials(&suspension_url, &[index]).unwrap();
+    assert!(CredentialValidator::check_status(&credential, &[&issuer_doc], StatusCheck::Strict).is_ok());
+
+    // The purpose of the status must match the purpose of the service.
+    credential.credential_status = Some(RevocationBitmapStatus::new(suspension_url, index).into());
+    assert!(matches!(
+      CredentialValidator::check_status(&credential, &[&issuer_doc], StatusCheck::Strict).unwrap_err(),
+      ValidationError::InvalidStatus(_)
+    ));
+
+    // Revocation is reported separately.
+    issuer_doc.revoke_credentials(&revocation_url, &[index]).unwrap();
+    credential.credential_status = Some(RevocationBitmapStatus::new(revocation_url, index).into());
+    assert!(matches!(
+      CredentialValidator::check_status(&credential, &[&issuer_doc], StatusCheck::Strict).unwrap_err(),
+      ValidationError::Revoked
+    ));
+  }
+
+  #[cfg(feature = "revocation-bitmap")]
+  #[test]
+  fn test_check_status_with_checkers() {
+    use crate::credential::Issuer;
+    use crate::validator::CredentialStatusCheckers;
+
+    let Setup {
+      issuer_doc,
+      unsigned_credential: mut credential,
+      ..
+    } = Setup::new();
+    credential.credential_status = Some(Status::new(
+      Url::parse("https://example.com/status/1").unwrap(),
+      "CustomStatus2023".to_owned(),
+    ));
+
+    let checkers: CredentialStatusCheckers = CredentialStatusCheckers::default().with(
+      "CustomStatus2023",
+      |status: &Status, _: &Issuer, _: &[&CoreDocument]| match status.id.path() {
+        "/status/1" => Err(ValidationError::Suspended),
+        _ => Ok(()),
+      },
+    );
+    assert!(checkers.contains(RevocationBitmap::TYPE));
+    for status_check in [StatusCheck::Strict, StatusCheck::SkipUnsupported] {
+      assert!(matches!(
+        CredentialValidator::check_status_with_checkers(&credential, &[&issuer_doc], status_check, &checkers)
+          .unwrap_err(),
+        ValidationError::Suspended
+      ));
+    }
+    assert!(CredentialValidator::check_status_with_checkers(
+      &credential,
+      &[&issuer_doc],
+      StatusCheck::SkipAll,
+      &checkers
+    )
+    .is_ok());
+
+    // The built-in RevocationBitmap2022 checker can be removed.
+    let service_url: identity_did::DIDUrl = issuer_doc.id().to_url().join("#revocation-service").unwrap();
+    credential.credential_status = Some(RevocationBitmapStatus::new(service_url, 0).into());
+    let checkers: CredentialStatusCheckers = CredentialStatusCheckers::empty();
+    assert!(matches!(
+      CredentialValidator::check_status_with_checkers(&credential, &[&issuer_doc], StatusCheck::Strict, &checkers)
+        .unwrap_err(),
+      ValidationError::InvalidStatus(_)
+    ));
+    assert!(CredentialValidator::check_status_with_checkers(
+      &credential,
+      &[&issuer_doc],
+      StatusCheck::SkipUnsupported,
+      &checkers
+    )
+    .is_ok());
+  }
+
+  #[cfg(feature = "status-list-2021")]
+  #[tokio::test]
+  async fn test_check_status_with_status_list_2021() {
+    use crate::revocation::StatusList2021CredentialBuilder;
+    use crate::revocation::StatusPurpose;
+
+    let Setup {
+      issuer_doc,
+      unsigned_credential: mut credential,
+      ..
+    } = Setup::new();
+    let list_url: Url = Url::parse("https://example.com/credentials/status/3").unwrap();
+    let index: usize = 42;
+    let mut list_credential: StatusList2021Credential = StatusList2021CredentialBuilder::new()
+      .id(list_url.clone())
+      .issuer(credential.issuer.clone())
+      .purpose(StatusPurpose::Suspension)
+      .build()
+      .unwrap();
+    credential.credential_status =
+      Some(StatusList2021Entry::new(list_url.clone(), StatusPurpose::Suspension, index).into());
+
+    // 0: the synchronous check cannot fetch the status list, so the status type is unsupported.
+    for (status_check, expected) in [
+      (StatusCheck::Strict, false),
+      (StatusCheck::SkipUnsupported, true),
+      (StatusCheck::SkipAll, true),
+    ] {
+      assert_eq!(
+        CredentialValidator::check_status(&credential, &[&issuer_doc], status_check).is_ok(),
+        expected
+      );
+    }
+
+    // 0: a registered checker with the already fetched status list credential succeeds.
+    let checkers: CredentialStatusCheckers = CredentialStatusCheckers::default().with(
+      StatusList2021Entry::TYPE,
+      StatusList2021StatusChecker::new().with(list_url.clone(), list_credential.clone()),
+    );
+    assert!(CredentialValidator::check_status_with_checkers(
+      &credential,
+      &[&issuer_doc],
+      StatusCheck::Strict,
+      &checkers
+    )
+    .is_ok());
+
+    let check = |list_credential: StatusList2021Credential| {
+      let credential: &Credential = &credential;
+      let issuer_doc: &CoreDocument = &issuer_doc;
+      let list_url: &Url = &list_url;
+      async move {
+        let fetch = |url: Url| {
+          let list_credential: Credential = list_credential.clone().into_inner();
+          async move {
+            assert_eq!(&url, list_url);
+            Ok::<_, std::io::Error>(list_credential)
+          }
+        };
+        CredentialValidator::check_status_with_status_list_2021(credential, &[issuer_doc], StatusCheck::Strict, &fetch)
+          .await
+      }
+    };
+
+    // 1: unset entry succeeds.
+    assert!(check(list_credential.clone()).await.is_ok());
+
+    // 2: set entry in a suspension list.
+    list_credential.set_entry(index, true).unwrap();
+    assert!(matches!(
+      check(list_credential.clone()).await.unwrap_err(),
+      ValidationError::Suspended
+    ));
+
+    // 3: list with a different purpose.
+    let revocation_list: StatusList2021Credential = StatusList2021CredentialBuilder::new()
+      .issuer(credential.issuer.clone())
+      .purpose(StatusPurpose::Revocation)
+      .build()
+      .unwrap();
+    assert!(matches!(
+      check(revocation_list).await.unwrap_err(),
+      ValidationError::InvalidStatus(_)
+    ));
+
+    // 4: list from a different issuer.
+    let foreign_list: StatusList2021Credential = StatusList2021CredentialBuilder::new()
+      .issuer(crate::credential::Issuer::Url(Url::parse("did:example:other").unwrap()))
+      .purpose(StatusPurpose::Suspension)
+      .build()
+      .unwrap();
+    assert!(matches!(
+      check(foreign_list).await.unwrap_err(),
+      ValidationError::InvalidStatus(_)
+    ));
+
+    // 5: list from an untrusted issuer.
+    let fetch = |_: Url| {
+      let list_credential: Credential = list_credential.clone().into_inner();
+      async move { Ok::<_, std::io::Error>(list_credential) }
+    };
+    let no_issuers: &[&CoreDocument] = &[];
+    assert!(matches!(
+      CredentialValidator::check_status_with_status_list_2021(&credential, no_issuers, StatusCheck::Strict, &fetch)
+        .await
+        .unwrap_err(),
+      ValidationError::DocumentMismatch(SignerContext::Issuer)
+    ));
+
+    // 6: fetch failure.
+    let fetch = |_: Url| async { Err::<Credential, _>(std::io::Error::from(std::io::ErrorKind::NotFound)) };
+    assert!(matches!(
+      CredentialValidator::check_status_with_status_list_2021(&credential, &[&issuer_doc], StatusCheck::Strict, &fetch)
+        .await
+        .unwrap_err(),
+      ValidationError::StatusListFetchError(_)
+    ));
+  }
+
+  #[cfg(feature = "json-schema")]
+  #[test]
+  fn test_check_credential_schema() {
+    use crate::credential::Schema;
+    use crate::validator::InMemorySchemaLoader;
+
+    let Setup {
+      issuer_doc,
+      issuer_key,
+      unsigned_credential: mut credential,
+      issuance_date,
+      expiration_date,
+    } = Setup::new();
+    let schema_url: Url = Url::parse("https://example.org/examples/degree.json").unwrap();
+    let loader: InMemorySchemaLoader = InMemorySchemaLoader::new().with_schema(
+      schema_url.clone(),
+      serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": {
+          "name": { "type": "string" },
+          "GPA": { "type": "number" },
+          "degree": {
+            "type": "object",
+            "properties": { "type": { "enum": ["MasterDegree"] } },
+            "required": ["type"]
+          }
+        },
+        "required": ["name", "degree"]
+      }),
+    );
+
+    // Schemas of other types are skipped.
+    credential.credential_schema = Schema::new(schema_url.clone(), "ZkpExampleSchema2018".to_owned()).into();
+    assert!(CredentialValidator::check_credential_schema(&credential, &loader).is_ok());
+
+    // Each violation is reported separately.
+    credential.credential_schema = Schema::new(schema_url.clone(), JSON_SCHEMA_VALIDATOR_2018.to_owned()).into();
+    let errors: Vec<ValidationError> = CredentialValidator::check_credential_schema(&credential, &loader).unwrap_err();
+    let mut instance_paths: Vec<&str> = errors
+      .iter()
+      .map(|error| match error {
+        ValidationError::CredentialSchemaViolation {
+          schema, instance_path, ..
+        } => {
+          assert_eq!(schema, schema_url.as_str());
+          instance_path.as_str()
+        }
+        other => panic!("unexpected error: {other}"),
+      })
+      .collect();
+    instance_paths.sort_unstable();
+    assert_eq!(instance_paths, ["/GPA", "/degree/type"]);
+
+    // Unknown schemas cannot be loaded.
+    credential.credential_schema = Schema::new(
+      Url::parse("https://example.org/unknown.json").unwrap(),
+      JSON_SCHEMA_2023.to_owned(),
+    )
+    .into();
+    assert!(matches!(
+      CredentialValidator::check_credential_schema(&credential, &loader)
+        .unwrap_err()
+        .as_slice(),
+      [ValidationError::CredentialSchemaLoadError { .. }]
+    ));
+
+    // The unit is only part of the full validation if a loader is set.
+    credential.credential_schema = Schema::new(schema_url, JSON_SCHEMA_VALIDATOR_2018.to_owned()).into();
+    issuer_doc
+      .signer(issuer_key.private())
+      .options(ProofOptions::default())
+      .method(issuer_doc.methods(None).first().unwrap().id())
+      .sign(&mut credential)
+      .unwrap();
+    let options: CredentialValidationOptions = CredentialValidationOptions::default()
+      .latest_issuance_date(issuance_date)
+      .earliest_expiry_date(expiration_date);
+    assert!(CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::AllErrors).is_ok());
+
+    let options: CredentialValidationOptions = options.schema_loader(loader);
+    let error: CompoundCredentialValidationError =
+      CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::AllErrors).unwrap_err();
+    assert_eq!(error.validation_errors.len(), 2);
+    let error: CompoundCredentialValidationError =
+      CredentialValidator::validate(&credential, &issuer_doc, &options, FailFast::FirstError).unwrap_err();
+    assert_eq!(error.validation_errors.len(), 1);
+  }
+
+  #[cfg(feature = "json-schema")]
+  #[test]
+  fn test_check_credential_schema_draft() {
+    use crate::credential::Schema;
+    use crate::validator::InMemorySchemaLoader;
+
+    let Setup {
+      unsigned_credential: mut credential,
+      ..
+    } = Setup::new();
+    credential.credential_subject = OneOrMany::One(
+      Subject::from_json_value(serde_json::json!({
+        "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+        "email": "not-an-email"
+      }))
+      .unwrap(),
+    );
+    // Draft-07 asserts `format` by default, whereas draft 2020-12 only treats it as an annotation.
+    let schema_url: Url = Url::parse("https://example.org/examples/email.json").unwrap();
+    let schema: serde_json::Value = serde_json::json!({
+      "type": "object",
+      "properties": {
+        "email": {
+          "type": "string",
+          "format": "email"
+        }
+      }
+    });
+    let loader: InMemorySchemaLoader = InMemorySchemaLoader::new().with_schema(schema_url.clone(), schema);
+
+    // `JsonSchemaValidator2018` schemas are compiled as draft-07.
+    credential.credential_schema = Schema::new(schema_url.clone(), JSON_SCHEMA_VALIDATOR_2018.to_owned()).into();
+    let errors: Vec<ValidationError> = CredentialValidator::check_credential_schema(&credential, &loader).unwrap_err();
+    assert!(matches!(
+      errors.as_slice(),
+      [ValidationError::CredentialSchemaViolation { instance_path, .. }] if instance_path == "/email"
+    ));
+
+    // `JsonSchema2023` schemas are compiled as draft 2020-12.
+    credential.credential_schema = Schema::new(schema_url, JSON_SCHEMA_2023.to_owned()).into();
+    assert!(CredentialValidator::check_credential_schema(&credential, &loader).is_ok());
+  }
+
   #[test]
   fn test_full_validation_invalid_structure() {
     let Setup {
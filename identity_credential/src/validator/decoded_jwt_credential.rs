@@ -0,0 +1,20 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_jose::jws::JwsHeader;
+
+use crate::credential::Credential;
+
+/// A [`Credential`] decoded from a JWS whose signature was verified.
+///
+/// Note that having an instance of this type only means that the signature of the JWS was verified. The remaining
+/// properties of the credential, such as its expiration date, have not necessarily been validated.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct DecodedJwtCredential<T = Object> {
+  /// The decoded credential parsed to the [Verifiable Credentials Data model](https://www.w3.org/TR/vc-data-model/).
+  pub credential: Credential<T>,
+  /// The protected header parsed from the JWS.
+  pub header: Box<JwsHeader>,
+}
@@ -29,6 +29,24 @@ pub enum ValidationError {
     signer_ctx: SignerContext,
   },
 
+  /// Indicates that the JWS of a credential (resp. presentation) could not be decoded.
+  #[error("could not decode jws")]
+  JwsDecodingError(#[source] identity_jose::error::Error),
+
+  /// Indicates that the verification method referenced by the `kid` header of a JWS could not be
+  /// obtained from the issuer's (resp. holder's) DID Document.
+  #[error("could not obtain the {signer_ctx}'s verification method: {message}")]
+  #[non_exhaustive]
+  MethodDataLookupError {
+    /// The underlying error, if any.
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    /// Description of the failed lookup.
+    message: &'static str,
+    /// Specifies whether the error was from the DID Document of a credential issuer
+    /// or the presentation holder.
+    signer_ctx: SignerContext,
+  },
+
   /// Indicates that the credential's (resp. presentation's) issuer's (resp. holder's) URL could
   /// not be parsed as a valid DID.
   #[error("{signer_ctx} URL is not a valid DID")]
@@ -72,6 +90,38 @@ pub enum ValidationError {
   /// Indicates that the credential has been revoked.
   #[error("credential has been revoked")]
   Revoked,
+  /// Indicates that the credential has been suspended, which unlike revocation may be reversed by the issuer.
+  #[error("credential has been suspended")]
+  Suspended,
+  /// Indicates that a credential subject violates the JSON Schema referenced by the credential.
+  #[cfg(feature = "json-schema")]
+  #[error("credential subject violates schema `{schema}` at `{instance_path}`: {message}")]
+  #[non_exhaustive]
+  CredentialSchemaViolation {
+    /// The id of the violated schema.
+    schema: String,
+    /// The JSON pointer to the violating value in the credential subject.
+    instance_path: String,
+    /// A description of the violation.
+    message: String,
+  },
+  /// Indicates that the JSON Schema referenced by the credential could not be loaded or compiled.
+  #[cfg(feature = "json-schema")]
+  #[error("credential schema `{schema}` could not be loaded")]
+  #[non_exhaustive]
+  CredentialSchemaLoadError {
+    /// The id of the schema.
+    schema: String,
+    /// The cause of the failure.
+    source: Box<dyn std::error::Error + Send + Sync + 'static>,
+  },
+  /// Indicates that the status list credential referenced by the credential's status could not be fetched.
+  #[error("status list credential could not be fetched")]
+  StatusListFetchError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Indicates that the disclosed claims of an SD-JWT could not be reconstructed.
+  #[cfg(feature = "sd-jwt")]
+  #[error("the disclosures of the SD-JWT are invalid")]
+  SdJwtDecodingError(#[source] crate::sd_jwt::SdJwtError),
 }
 
 /// Specifies whether an error is related to a credential issuer or the presentation holder.
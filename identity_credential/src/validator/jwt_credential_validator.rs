@@ -0,0 +1,568 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "sd-jwt")]
+use identity_core::common::Object;
+use identity_core::convert::FromJson;
+#[cfg(feature = "sd-jwt")]
+use identity_core::convert::ToJson;
+use identity_did::CoreDID;
+use identity_did::DIDUrl;
+use identity_document::document::CoreDocument;
+use identity_document::verifiable::VerifierOptions;
+use identity_jose::jwk::Jwk;
+use identity_jose::jws::verifiers::DefaultJwsSignatureVerifier;
+use identity_jose::jws::Decoder;
+use identity_jose::jws::JwsAlgorithm;
+use identity_jose::jws::JwsHeader;
+use identity_jose::jws::JwsSignatureVerifier;
+use identity_jose::jws::Token;
+use identity_verification::VerificationMethod;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::credential::jwt_serialization::CredentialJwtClaims;
+use crate::credential::Credential;
+use crate::credential::Jwt;
+#[cfg(feature = "sd-jwt")]
+use crate::sd_jwt::SdJwt;
+#[cfg(feature = "sd-jwt")]
+use crate::sd_jwt::SdObjectDecoder;
+
+use super::errors::CompoundCredentialValidationError;
+use super::errors::SignerContext;
+use super::errors::ValidationError;
+use super::CredentialValidationOptions;
+use super::CredentialValidator;
+use super::DecodedJwtCredential;
+use super::FailFast;
+
+/// A type for decoding and validating [`Credential`]s encoded as JWTs.
+///
+/// The signature of the JWT is verified with the key of the verification method referenced by its `kid` header,
+/// which is looked up in the DID Document of the issuer. The cryptographic verification itself is delegated to a
+/// [`JwsSignatureVerifier`], which defaults to the [`DefaultJwsSignatureVerifier`] supporting all algorithms enabled
+/// through the features of `identity_jose`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct JwtCredentialValidator<V: JwsSignatureVerifier = DefaultJwsSignatureVerifier>(V);
+
+impl JwtCredentialValidator {
+  /// Create a new [`JwtCredentialValidator`] using the [`DefaultJwsSignatureVerifier`].
+  pub fn new() -> Self {
+    Self(DefaultJwsSignatureVerifier::default())
+  }
+}
+
+impl Default for JwtCredentialValidator {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<V: JwsSignatureVerifier> JwtCredentialValidator<V> {
+  /// Create a new [`JwtCredentialValidator`] that delegates cryptographic signature verification to the given
+  /// `signature_verifier`.
+  pub fn with_signature_verifier(signature_verifier: V) -> Self {
+    Self(signature_verifier)
+  }
+
+  /// Decodes and validates a [`Credential`] issued as a JWT.
+  ///
+  /// The same properties as in [`CredentialValidator::validate`] are validated according to `options`:
+  /// - the issuer's signature on the JWS,
+  /// - the expiration date,
+  /// - the issuance date,
+  /// - the semantic structure,
+  /// - the credential status.
+  ///
+  /// The `verifier_options` of `options` restrict the verification methods that may be used to verify the
+  /// signature by their `method_scope`.
+  ///
+  /// # Warning
+  /// The caller must ensure that `issuer` represents an up-to-date DID Document. As with
+  /// [`CredentialValidator::validate`], the lack of an error is in of itself not enough to conclude that the
+  /// credential can be trusted.
+  ///
+  /// # Errors
+  /// An error is returned whenever a validated condition is not satisfied. If the JWS cannot be decoded or its
+  /// signature cannot be verified, no further validations are carried out.
+  pub fn validate<DOC, T>(
+    &self,
+    credential_jwt: &Jwt,
+    issuer: &DOC,
+    options: &CredentialValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<DecodedJwtCredential<T>, CompoundCredentialValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+    T: ToOwned<Owned = T> + Serialize + DeserializeOwned,
+  {
+    let issuers: &[CoreDocument] = std::slice::from_ref(issuer.as_ref());
+    let decoded_credential: DecodedJwtCredential<T> = self
+      .verify_signature(credential_jwt, issuers, &options.verifier_options)
+      .map_err(|err| CompoundCredentialValidationError {
+        validation_errors: vec![err],
+      })?;
+
+    CredentialValidator::validate_with_signature_check(
+      &decoded_credential.credential,
+      issuers,
+      options,
+      None,
+      fail_fast,
+      || Ok(()),
+    )?;
+
+    Ok(decoded_credential)
+  }
+
+  /// Decodes the JWS and verifies its signature using the DID Document of a trusted issuer.
+  ///
+  /// The DID Document is selected by the DID of the `kid` header of the JWS, which must match the issuer of the
+  /// decoded credential.
+  ///
+  /// # Warning
+  /// The caller must ensure that the DID Documents of the trusted issuers are up-to-date.
+  ///
+  /// # Errors
+  /// An error is returned if the JWS cannot be decoded, none of the trusted issuers contains the referenced
+  /// verification method, the signature is invalid, or the credential was not issued by the signer.
+  pub fn verify_signature<DOC, T>(
+    &self,
+    credential: &Jwt,
+    trusted_issuers: &[DOC],
+    options: &VerifierOptions,
+  ) -> Result<DecodedJwtCredential<T>, ValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+    T: ToOwned<Owned = T> + Serialize + DeserializeOwned,
+  {
+    self.verify_signature_with_claims(credential.as_str(), trusted_issuers, options, |claims| {
+      CredentialJwtClaims::<'_, T>::from_json_slice(claims)
+        .map_err(|err| {
+          ValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+        })?
+        .try_into_credential()
+        .map_err(ValidationError::CredentialStructure)
+    })
+  }
+
+  /// Verifies the signature of the JWT of an SD-JWT using the DID Document of a trusted issuer and reconstructs the
+  /// credential from the presented disclosures.
+  ///
+  /// The disclosures are decoded by `decoder`, which determines the supported hash algorithms. Claims that were
+  /// concealed by the issuer and not disclosed by the holder are absent from the returned credential. The key binding
+  /// JWT of `sd_jwt`, if any, is not verified.
+  ///
+  /// # Errors
+  ///
+  /// In addition to the errors of [`JwtCredentialValidator::verify_signature`], an error is returned if the
+  /// disclosures do not match the digests of the JWT.
+  #[cfg(feature = "sd-jwt")]
+  pub fn verify_sd_jwt_signature<DOC, T>(
+    &self,
+    sd_jwt: &SdJwt,
+    trusted_issuers: &[DOC],
+    options: &VerifierOptions,
+    decoder: &SdObjectDecoder,
+  ) -> Result<DecodedJwtCredential<T>, ValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+    T: ToOwned<Owned = T> + Serialize + DeserializeOwned,
+  {
+    self.verify_signature_with_claims(sd_jwt.jwt(), trusted_issuers, options, |claims| {
+      let claims: Object = Object::from_json_slice(claims).map_err(|err| {
+        ValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+      })?;
+      let decoded: Vec<u8> = decoder
+        .decode(&claims, sd_jwt.disclosures())
+        .map_err(ValidationError::SdJwtDecodingError)?
+        .to_json_vec()
+        .map_err(|err| {
+          ValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+        })?;
+
+      CredentialJwtClaims::<'_, T>::from_json_slice(&decoded)
+        .map_err(|err| {
+          ValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+        })?
+        .try_into_credential()
+        .map_err(ValidationError::CredentialStructure)
+    })
+  }
+
+  /// Decodes and validates a [`Credential`] issued as an SD-JWT, reconstructing its claims from the presented
+  /// disclosures.
+  ///
+  /// The same properties as in [`JwtCredentialValidator::validate`] are validated on the reconstructed credential.
+  /// Disclosures are decoded with the hash algorithms supported by [`SdObjectDecoder::new`].
+  ///
+  /// # Errors
+  ///
+  /// An error is returned whenever a validated condition is not satisfied. If the JWS cannot be decoded, its
+  /// signature cannot be verified, or the disclosures are invalid, no further validations are carried out.
+  #[cfg(feature = "sd-jwt")]
+  pub fn validate_sd_jwt<DOC, T>(
+    &self,
+    sd_jwt: &SdJwt,
+    issuer: &DOC,
+    options: &CredentialValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<DecodedJwtCredential<T>, CompoundCredentialValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+    T: ToOwned<Owned = T> + Serialize + DeserializeOwned,
+  {
+    let issuers: &[CoreDocument] = std::slice::from_ref(issuer.as_ref());
+    let decoded_credential: DecodedJwtCredential<T> = self
+      .verify_sd_jwt_signature(sd_jwt, issuers, &options.verifier_options, &SdObjectDecoder::new())
+      .map_err(|err| CompoundCredentialValidationError {
+        validation_errors: vec![err],
+      })?;
+
+    CredentialValidator::validate_with_signature_check(
+      &decoded_credential.credential,
+      issuers,
+      options,
+      None,
+      fail_fast,
+      || Ok(()),
+    )?;
+
+    Ok(decoded_credential)
+  }
+
+  /// Decodes the JWS, verifies its signature using the DID Document of a trusted issuer and parses its claims
+  /// with `parse_claims`.
+  fn verify_signature_with_claims<DOC, T, F>(
+    &self,
+    jws: &str,
+    trusted_issuers: &[DOC],
+    options: &VerifierOptions,
+    parse_claims: F,
+  ) -> Result<DecodedJwtCredential<T>, ValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+    F: FnOnce(&[u8]) -> Result<Credential<T>, ValidationError>,
+  {
+    let verify_fn = |protected: Option<&JwsHeader>,
+                     _unprotected: Option<&JwsHeader>,
+                     signing_input: &[u8],
+                     signature: &[u8]|
+     -> Result<(), ValidationError> {
+      let method_id: DIDUrl = Self::extract_method_id(protected)?;
+      let alg: JwsAlgorithm = protected
+        .and_then(JwsHeader::alg)
+        .ok_or(ValidationError::JwsDecodingError(
+          identity_jose::error::Error::MissingParam("alg"),
+        ))?;
+
+      let issuer: &CoreDocument = trusted_issuers
+        .iter()
+        .map(AsRef::as_ref)
+        .find(|issuer| issuer.id() == method_id.did())
+        .ok_or(ValidationError::DocumentMismatch(SignerContext::Issuer))?;
+      let method: &VerificationMethod =
+        issuer
+          .resolve_method(&method_id, options.method_scope)
+          .ok_or(ValidationError::MethodDataLookupError {
+            source: None,
+            message: "could not find the verification method referenced by the kid header",
+            signer_ctx: SignerContext::Issuer,
+          })?;
+      let public_key: &Jwk =
+        method
+          .data()
+          .try_public_key_jwk()
+          .map_err(|err| ValidationError::MethodDataLookupError {
+            source: Some(err.into()),
+            message: "the verification method does not contain a public key jwk",
+            signer_ctx: SignerContext::Issuer,
+          })?;
+
+      self
+        .0
+        .verify(alg, signing_input, signature, public_key)
+        .map_err(|err| ValidationError::Signature {
+          source: err.into(),
+          signer_ctx: SignerContext::Issuer,
+        })
+    };
+
+    let token: Token<'_> = Decoder::new()
+      .decode(&verify_fn, jws.as_bytes())
+      .map_err(|err| match err {
+        // Recover the error returned by `verify_fn`.
+        identity_jose::error::Error::SignatureVerificationError(source) => match source.downcast::<ValidationError>() {
+          Ok(err) => *err,
+          Err(source) => ValidationError::Signature {
+            source,
+            signer_ctx: SignerContext::Issuer,
+          },
+        },
+        err => ValidationError::JwsDecodingError(err),
+      })?;
+
+    let credential: Credential<T> = parse_claims(&token.claims)?;
+
+    // The credential must have been signed by its issuer.
+    let header: JwsHeader = token
+      .protected
+      .expect("the signature was verified using the protected header");
+    let method_id: DIDUrl = Self::extract_method_id(Some(&header))?;
+    let issuer_did: CoreDID = CredentialValidator::extract_issuer(&credential)?;
+    if &issuer_did != method_id.did() {
+      return Err(ValidationError::DocumentMismatch(SignerContext::Issuer));
+    }
+
+    Ok(DecodedJwtCredential {
+      credential,
+      header: Box::new(header),
+    })
+  }
+
+  /// Parses the `kid` header parameter as the id of a verification method.
+  fn extract_method_id(protected: Option<&JwsHeader>) -> Result<DIDUrl, ValidationError> {
+    let kid: &str = protected
+      .and_then(|header| header.kid())
+      .ok_or(ValidationError::MethodDataLookupError {
+        source: None,
+        message: "missing kid in protected header",
+        signer_ctx: SignerContext::Issuer,
+      })?;
+    DIDUrl::parse(kid).map_err(|err| ValidationError::MethodDataLookupError {
+      source: Some(err.into()),
+      message: "the kid header is not a valid DID URL",
+      signer_ctx: SignerContext::Issuer,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Duration;
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::crypto::KeyPair;
+  use identity_did::DID;
+  use identity_jose::jws::verifiers::EdDSAJwsSignatureVerifier;
+  use identity_verification::MethodScope;
+
+  use crate::validator::test_utils;
+
+  use super::*;
+
+  struct Setup {
+    issuer_doc: CoreDocument,
+    issuer_key: KeyPair,
+    credential: Credential,
+    validator: JwtCredentialValidator<EdDSAJwsSignatureVerifier>,
+  }
+
+  impl Setup {
+    fn new() -> Self {
+      let (issuer_doc, issuer_key) = test_utils::generate_jwk_document_with_keys();
+      let (subject_doc, _) = test_utils::generate_document_with_keys();
+      let credential: Credential = test_utils::generate_credential(
+        &issuer_doc,
+        &[subject_doc],
+        Timestamp::parse("2020-01-01T00:00:00Z").unwrap(),
+        Timestamp::parse("2023-01-01T00:00:00Z").unwrap(),
+      );
+      Self {
+        issuer_doc,
+        issuer_key,
+        credential,
+        validator: JwtCredentialValidator::with_signature_verifier(EdDSAJwsSignatureVerifier::new()),
+      }
+    }
+
+    fn method_id(&self) -> String {
+      self.issuer_doc.id().to_url().join("#key-1").unwrap().to_string()
+    }
+
+    fn options(&self) -> CredentialValidationOptions {
+      CredentialValidationOptions::default()
+        .latest_issuance_date(self.credential.issuance_date)
+        .earliest_expiry_date(self.credential.expiration_date.unwrap())
+    }
+  }
+
+  fn single_error(err: CompoundCredentialValidationError) -> ValidationError {
+    let mut validation_errors: Vec<ValidationError> = err.validation_errors;
+    assert_eq!(validation_errors.len(), 1);
+    validation_errors.remove(0)
+  }
+
+  #[tokio::test]
+  async fn validate_valid_jwt() {
+    let setup: Setup = Setup::new();
+    let jwt: Jwt = test_utils::sign_credential_jwt(&setup.credential, &setup.method_id(), &setup.issuer_key).await;
+
+    let decoded: DecodedJwtCredential<Object> = setup
+      .validator
+      .validate(&jwt, &setup.issuer_doc, &setup.options(), FailFast::AllErrors)
+      .unwrap();
+    assert_eq!(decoded.credential, setup.credential);
+    assert_eq!(decoded.header.kid(), Some(setup.method_id().as_str()));
+  }
+
+  #[tokio::test]
+  async fn validate_invalid_signature() {
+    let setup: Setup = Setup::new();
+    let (_, other_key) = test_utils::generate_jwk_document_with_keys();
+    let jwt: Jwt = test_utils::sign_credential_jwt(&setup.credential, &setup.method_id(), &other_key).await;
+
+    let err: CompoundCredentialValidationError = setup
+      .validator
+      .validate::<_, Object>(&jwt, &setup.issuer_doc, &setup.options(), FailFast::AllErrors)
+      .unwrap_err();
+    assert!(matches!(single_error(err), ValidationError::Signature { .. }));
+  }
+
+  #[tokio::test]
+  async fn validate_expired_jwt() {
+    let setup: Setup = Setup::new();
+    let jwt: Jwt = test_utils::sign_credential_jwt(&setup.credential, &setup.method_id(), &setup.issuer_key).await;
+    let options: CredentialValidationOptions = setup.options().earliest_expiry_date(
+      setup
+        .credential
+        .expiration_date
+        .unwrap()
+        .checked_add(Duration::seconds(1))
+        .unwrap(),
+    );
+
+    let err: CompoundCredentialValidationError = setup
+      .validator
+      .validate::<_, Object>(&jwt, &setup.issuer_doc, &options, FailFast::AllErrors)
+      .unwrap_err();
+    assert!(matches!(single_error(err), ValidationError::ExpirationDate));
+  }
+
+  #[tokio::test]
+  async fn validate_kid_of_other_document() {
+    let setup: Setup = Setup::new();
+    let (other_doc, other_key) = test_utils::generate_jwk_document_with_keys();
+    let other_method_id: String = other_doc.id().to_url().join("#key-1").unwrap().to_string();
+    let jwt: Jwt = test_utils::sign_credential_jwt(&setup.credential, &other_method_id, &other_key).await;
+
+    // The signer's document is unknown.
+    let err: CompoundCredentialValidationError = setup
+      .validator
+      .validate::<_, Object>(&jwt, &setup.issuer_doc, &setup.options(), FailFast::AllErrors)
+      .unwrap_err();
+    assert!(matches!(
+      single_error(err),
+      ValidationError::DocumentMismatch(SignerContext::Issuer)
+    ));
+
+    // The signature is valid, but the signer is not the issuer of the credential.
+    let err: ValidationError = setup
+      .validator
+      .verify_signature::<_, Object>(&jwt, &[&setup.issuer_doc, &other_doc], &VerifierOptions::default())
+      .unwrap_err();
+    assert!(matches!(err, ValidationError::DocumentMismatch(SignerContext::Issuer)));
+  }
+
+  #[tokio::test]
+  async fn validate_method_scope() {
+    let setup: Setup = Setup::new();
+    let jwt: Jwt = test_utils::sign_credential_jwt(&setup.credential, &setup.method_id(), &setup.issuer_key).await;
+    let options: CredentialValidationOptions = setup
+      .options()
+      .verifier_options(VerifierOptions::new().method_scope(MethodScope::assertion_method()));
+
+    let err: CompoundCredentialValidationError = setup
+      .validator
+      .validate::<_, Object>(&jwt, &setup.issuer_doc, &options, FailFast::AllErrors)
+      .unwrap_err();
+    assert!(matches!(
+      single_error(err),
+      ValidationError::MethodDataLookupError { .. }
+    ));
+  }
+
+  #[test]
+  fn validate_malformed_jwt() {
+    let setup: Setup = Setup::new();
+    let err: CompoundCredentialValidationError = setup
+      .validator
+      .validate::<_, Object>(
+        &Jwt::new("not.a.jwt".to_owned()),
+        &setup.issuer_doc,
+        &setup.options(),
+        FailFast::AllErrors,
+      )
+      .unwrap_err();
+    assert!(matches!(single_error(err), ValidationError::JwsDecodingError(_)));
+  }
+
+  #[cfg(feature = "sd-jwt")]
+  #[tokio::test]
+  async fn validate_sd_jwt() {
+    use crate::sd_jwt::SdJwt;
+    use crate::sd_jwt::SdObjectEncoder;
+    use identity_core::common::Value;
+
+    let setup: Setup = Setup::new();
+    let mut encoder: SdObjectEncoder =
+      SdObjectEncoder::try_from_json(&setup.credential.serialize_jwt().unwrap()).unwrap();
+    let disclosures: Vec<String> = [
+      "/vc/credentialSubject/degree/type",
+      "/vc/credentialSubject/degree",
+      "/vc/credentialSubject/GPA",
+    ]
+    .into_iter()
+    .map(|path| encoder.conceal(path, None).unwrap().into_string())
+    .collect();
+    encoder.add_sd_alg_property();
+    let jwt: Jwt = test_utils::sign_jwt(&encoder.try_to_string().unwrap(), &setup.method_id(), &setup.issuer_key).await;
+    let sd_jwt: SdJwt = SdJwt::new(jwt.as_str().to_owned(), disclosures, None);
+
+    // The holder discloses the degree without its type.
+    let presentation: SdJwt = sd_jwt
+      .present(|disclosure| disclosure.claim_name() == Some("degree"))
+      .unwrap();
+    let decoded: DecodedJwtCredential<Object> = setup
+      .validator
+      .validate_sd_jwt(
+        &SdJwt::parse(&presentation.presentation()).unwrap(),
+        &setup.issuer_doc,
+        &setup.options(),
+        FailFast::AllErrors,
+      )
+      .unwrap();
+    let subject: &Object = &decoded.credential.credential_subject.first().unwrap().properties;
+    assert_eq!(subject.get("name"), Some(&Value::from("Alice")));
+    assert!(subject.get("GPA").is_none());
+    assert_eq!(
+      subject["degree"],
+      Value::Object(std::iter::once(("name".to_owned(), Value::from("Bachelor of Science and Arts"))).collect())
+    );
+
+    // Disclosing every claim yields the original credential.
+    let decoded: DecodedJwtCredential<Object> = setup
+      .validator
+      .validate_sd_jwt(&sd_jwt, &setup.issuer_doc, &setup.options(), FailFast::AllErrors)
+      .unwrap();
+    assert_eq!(decoded.credential, setup.credential);
+
+    // Disclosures not issued with the JWT are rejected.
+    let mut forged: Vec<String> = presentation.disclosures().to_vec();
+    forged.push(
+      crate::sd_jwt::Disclosure::new("salt".to_owned(), Some("GPA".to_owned()), Value::from("4.0")).into_string(),
+    );
+    let err: CompoundCredentialValidationError = setup
+      .validator
+      .validate_sd_jwt::<_, Object>(
+        &SdJwt::new(jwt.as_str().to_owned(), forged, None),
+        &setup.issuer_doc,
+        &setup.options(),
+        FailFast::AllErrors,
+      )
+      .unwrap_err();
+    assert!(matches!(single_error(err), ValidationError::SdJwtDecodingError(_)));
+  }
+}
@@ -4,13 +4,25 @@
 //! Verifiable Credential and Presentation validators.
 
 pub use self::credential_validator::CredentialValidator;
+pub use self::decoded_jwt_credential::DecodedJwtCredential;
 pub use self::domain_linkage_validator::DomainLinkageValidator;
 pub use self::errors::CompoundCredentialValidationError;
 pub use self::errors::CompoundPresentationValidationError;
 pub use self::errors::DomainLinkageValidationError;
 pub use self::errors::SignerContext;
 pub use self::errors::ValidationError;
+pub use self::jwt_credential_validator::JwtCredentialValidator;
 pub use self::presentation_validator::PresentationValidator;
+#[cfg(feature = "json-schema")]
+pub use self::schema_loader::InMemorySchemaLoader;
+#[cfg(feature = "json-schema")]
+pub use self::schema_loader::SchemaLoader;
+pub use self::status_checker::CredentialStatusChecker;
+pub use self::status_checker::CredentialStatusCheckers;
+#[cfg(feature = "revocation-bitmap")]
+pub use self::status_checker::RevocationBitmapStatusChecker;
+#[cfg(feature = "status-list-2021")]
+pub use self::status_checker::StatusList2021StatusChecker;
 pub use self::validation_options::CredentialValidationOptions;
 pub use self::validation_options::FailFast;
 pub use self::validation_options::PresentationValidationOptions;
@@ -18,9 +30,14 @@ pub use self::validation_options::StatusCheck;
 pub use self::validation_options::SubjectHolderRelationship;
 
 mod credential_validator;
+mod decoded_jwt_credential;
 mod domain_linkage_validator;
 mod errors;
+mod jwt_credential_validator;
 mod presentation_validator;
+#[cfg(feature = "json-schema")]
+mod schema_loader;
+mod status_checker;
 #[cfg(test)]
 mod test_utils;
 mod validation_options;
@@ -494,6 +494,99 @@ mod tests {
     ));
   }
 
+  #[test]
+  fn test_full_validation_custom_status_checker() {
+    use crate::credential::Issuer;
+    use crate::credential::Status;
+    use crate::validator::CredentialStatusCheckers;
+    use crate::validator::StatusCheck;
+
+    let mut setup = TestSetup::new();
+    setup.credential_bar.credential_status = Some(Status::new(
+      Url::parse("https://example.com/status/1").unwrap(),
+      "CustomStatus2023".to_owned(),
+    ));
+    setup
+      .issuer_bar_doc
+      .signer(setup.issuer_bar_key.private())
+      .options(ProofOptions::default())
+      .method(setup.issuer_bar_doc.methods(None).first().unwrap().id())
+      .sign(&mut setup.credential_bar)
+      .unwrap();
+    setup
+      .issuer_foo_doc
+      .signer(setup.issuer_foo_key.private())
+      .options(ProofOptions::default())
+      .method(setup.issuer_foo_doc.methods(None).first().unwrap().id())
+      .sign(&mut setup.credential_foo)
+      .unwrap();
+    let TestSetup {
+      issuer_foo_doc,
+      issuer_bar_doc,
+      subject_foo_doc,
+      subject_foo_key,
+      credential_foo,
+      credential_bar,
+      ..
+    } = setup;
+
+    let mut presentation = build_presentation(&subject_foo_doc, [credential_foo, credential_bar].to_vec());
+    subject_foo_doc
+      .signer(subject_foo_key.private())
+      .options(ProofOptions::new().challenge("some challenge".to_owned()))
+      .method(subject_foo_doc.methods(None).first().unwrap().id())
+      .sign(&mut presentation)
+      .unwrap();
+
+    let validate = |checkers: CredentialStatusCheckers| {
+      let credential_validation_options = CredentialValidationOptions::default()
+        .earliest_expiry_date(Timestamp::parse("2021-01-01T00:00:00Z").unwrap())
+        .latest_issuance_date(Timestamp::parse("2030-01-01T00:00:00Z").unwrap())
+        .status_check(StatusCheck::Strict)
+        .status_checkers(checkers);
+      let presentation_validation_options = PresentationValidationOptions::default()
+        .shared_validation_options(credential_validation_options)
+        .presentation_verifier_options(VerifierOptions::default().challenge("some challenge".to_owned()))
+        .subject_holder_relationship(SubjectHolderRelationship::SubjectOnNonTransferable);
+      PresentationValidator::validate(
+        &presentation,
+        &subject_foo_doc,
+        &[&issuer_foo_doc, &issuer_bar_doc],
+        &presentation_validation_options,
+        FailFast::AllErrors,
+      )
+    };
+
+    // Unsupported status type.
+    let error = validate(CredentialStatusCheckers::default()).unwrap_err();
+    assert_eq!(error.credential_errors.len(), 1);
+    assert!(matches!(
+      error.credential_errors.get(&1).unwrap().validation_errors.as_slice(),
+      [ValidationError::InvalidStatus(_)]
+    ));
+
+    // The registered checker is called with the status and issuer of the credential.
+    let expected_issuer: Url = Url::parse(issuer_bar_doc.id().as_str()).unwrap();
+    let checkers = CredentialStatusCheckers::default().with(
+      "CustomStatus2023",
+      move |status: &Status, issuer: &Issuer, trusted_issuers: &[&CoreDocument]| {
+        assert_eq!(status.id.as_str(), "https://example.com/status/1");
+        assert_eq!(issuer.url(), &expected_issuer);
+        assert_eq!(trusted_issuers.len(), 2);
+        Err(ValidationError::Revoked)
+      },
+    );
+    let error = validate(checkers).unwrap_err();
+    assert!(matches!(
+      error.credential_errors.get(&1).unwrap().validation_errors.as_slice(),
+      [ValidationError::Revoked]
+    ));
+
+    let checkers = CredentialStatusCheckers::default()
+      .with("CustomStatus2023", |_: &Status, _: &Issuer, _: &[&CoreDocument]| Ok(()));
+    assert!(validate(checkers).is_ok());
+  }
+
   #[test]
   fn test_subject_holder_relationship_check() {
     // create a first credential
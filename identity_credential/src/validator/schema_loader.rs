@@ -0,0 +1,56 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use identity_core::common::Url;
+use identity_core::common::Value;
+
+use crate::credential::Schema;
+
+/// Loads the JSON Schema referenced by a [`credentialSchema`](https://www.w3.org/TR/vc-data-model/#data-schemas)
+/// entry of a credential.
+///
+/// Used by the [`CredentialValidator`](crate::validator::CredentialValidator) to validate credential subjects when
+/// set in [`CredentialValidationOptions::schema_loader`](crate::validator::CredentialValidationOptions).
+pub trait SchemaLoader: Debug + Send + Sync {
+  /// Returns the JSON Schema referenced by `schema`.
+  fn load(&self, schema: &Schema) -> Result<Value, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+/// A [`SchemaLoader`] returning schemas from memory by their id, which may be used in tests
+/// or when all trusted schemas are known upfront.
+#[derive(Clone, Debug, Default)]
+pub struct InMemorySchemaLoader {
+  schemas: HashMap<Url, Value>,
+}
+
+impl InMemorySchemaLoader {
+  /// Creates a new, empty `InMemorySchemaLoader`.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds the JSON `schema` under the given `id`, replacing any previous schema with that id.
+  pub fn insert(&mut self, id: Url, schema: Value) -> Option<Value> {
+    self.schemas.insert(id, schema)
+  }
+
+  /// Adds the JSON `schema` under the given `id`, replacing any previous schema with that id.
+  #[must_use]
+  pub fn with_schema(mut self, id: Url, schema: Value) -> Self {
+    self.insert(id, schema);
+    self
+  }
+}
+
+impl SchemaLoader for InMemorySchemaLoader {
+  fn load(&self, schema: &Schema) -> Result<Value, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    self
+      .schemas
+      .get(&schema.id)
+      .cloned()
+      .ok_or_else(|| format!("unknown schema `{}`", schema.id).into())
+  }
+}
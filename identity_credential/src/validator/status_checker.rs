@@ -0,0 +1,318 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use identity_document::document::CoreDocument;
+
+use crate::credential::Issuer;
+use crate::credential::Status;
+
+use super::errors::ValidationError;
+
+/// Checks a [`credentialStatus`](https://www.w3.org/TR/vc-data-model/#status) of a specific type.
+///
+/// Implementations are registered by status `type` in [`CredentialStatusCheckers`], which the
+/// [`CredentialValidator`](crate::validator::CredentialValidator) and
+/// [`PresentationValidator`](crate::validator::PresentationValidator) dispatch to.
+pub trait CredentialStatusChecker: Send + Sync {
+  /// Checks the `status` of a credential issued by `issuer`.
+  ///
+  /// `trusted_issuers` are the DID Documents passed to the validator. An error should be returned
+  /// if the credential is revoked or its status cannot be determined.
+  fn check_status(
+    &self,
+    status: &Status,
+    issuer: &Issuer,
+    trusted_issuers: &[&CoreDocument],
+  ) -> Result<(), ValidationError>;
+}
+
+impl<F> CredentialStatusChecker for F
+where
+  F: Fn(&Status, &Issuer, &[&CoreDocument]) -> Result<(), ValidationError> + Send + Sync,
+{
+  fn check_status(
+    &self,
+    status: &Status,
+    issuer: &Issuer,
+    trusted_issuers: &[&CoreDocument],
+  ) -> Result<(), ValidationError> {
+    self(status, issuer, trusted_issuers)
+  }
+}
+
+/// A registry of [`CredentialStatusChecker`]s keyed by the status `type` they handle.
+///
+/// The default registry contains the built-in checker for `RevocationBitmap2022` if the `revocation-bitmap` feature
+/// is enabled. A `StatusList2021StatusChecker` is not registered by default, since it requires the referenced
+/// status list credentials to be fetched beforehand.
+#[derive(Clone)]
+pub struct CredentialStatusCheckers {
+  checkers: HashMap<String, Arc<dyn CredentialStatusChecker>>,
+}
+
+impl CredentialStatusCheckers {
+  /// Creates a registry containing the built-in checkers.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Creates a registry without any checkers.
+  pub fn empty() -> Self {
+    Self {
+      checkers: HashMap::new(),
+    }
+  }
+
+  /// Registers `checker` for statuses of the given `type_`, replacing any previously registered checker.
+  ///
+  /// Returns the replaced checker, if any.
+  pub fn insert(
+    &mut self,
+    type_: impl Into<String>,
+    checker: impl CredentialStatusChecker + 'static,
+  ) -> Option<Arc<dyn CredentialStatusChecker>> {
+    self.checkers.insert(type_.into(), Arc::new(checker))
+  }
+
+  /// Registers `checker` for statuses of the given `type_`, replacing any previously registered checker.
+  #[must_use]
+  pub fn with(mut self, type_: impl Into<String>, checker: impl CredentialStatusChecker + 'static) -> Self {
+    self.insert(type_, checker);
+    self
+  }
+
+  /// Removes the checker registered for `type_`.
+  pub fn remove(&mut self, type_: &str) -> Option<Arc<dyn CredentialStatusChecker>> {
+    self.checkers.remove(type_)
+  }
+
+  /// Returns the checker registered for `type_`.
+  pub fn get(&self, type_: &str) -> Option<&dyn CredentialStatusChecker> {
+    self.checkers.get(type_).map(AsRef::as_ref)
+  }
+
+  /// Returns `true` if a checker is registered for `type_`.
+  pub fn contains(&self, type_: &str) -> bool {
+    self.checkers.contains_key(type_)
+  }
+
+  /// Returns `true` if no checker is registered.
+  pub fn is_empty(&self) -> bool {
+    self.checkers.is_empty()
+  }
+}
+
+impl Default for CredentialStatusCheckers {
+  fn default() -> Self {
+    #[allow(unused_mut)]
+    let mut checkers: Self = Self::empty();
+    #[cfg(feature = "revocation-bitmap")]
+    checkers.insert(
+      crate::revocation::RevocationBitmap::TYPE,
+      revocation_bitmap::RevocationBitmapStatusChecker,
+    );
+    checkers
+  }
+}
+
+impl Debug for CredentialStatusCheckers {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_set().entries(self.checkers.keys()).finish()
+  }
+}
+
+#[cfg(feature = "revocation-bitmap")]
+pub use self::revocation_bitmap::RevocationBitmapStatusChecker;
+
+#[cfg(feature = "revocation-bitmap")]
+mod revocation_bitmap {
+  use identity_did::CoreDID;
+  use identity_did::DIDUrl;
+  use identity_document::document::CoreDocument;
+  use identity_document::service::Service;
+
+  use crate::credential::Issuer;
+  use crate::credential::RevocationBitmapStatus;
+  use crate::credential::Status;
+  use crate::revocation::RevocationBitmap;
+  use crate::revocation::StatusPurpose;
+  use crate::validator::SignerContext;
+  use crate::validator::ValidationError;
+
+  use super::CredentialStatusChecker;
+
+  /// The built-in [`CredentialStatusChecker`] for `RevocationBitmap2022`, which checks the status
+  /// against the matching service in the issuer's DID Document.
+  ///
+  /// A set index results in [`ValidationError::Revoked`] or [`ValidationError::Suspended`],
+  /// depending on the `statusPurpose` of the status, which must match that of the service.
+  #[derive(Clone, Copy, Debug, Default)]
+  #[non_exhaustive]
+  pub struct RevocationBitmapStatusChecker;
+
+  impl CredentialStatusChecker for RevocationBitmapStatusChecker {
+    fn check_status(
+      &self,
+      status: &Status,
+      issuer: &Issuer,
+      trusted_issuers: &[&CoreDocument],
+    ) -> Result<(), ValidationError> {
+      let status: RevocationBitmapStatus =
+        RevocationBitmapStatus::try_from(status.clone()).map_err(ValidationError::InvalidStatus)?;
+
+      // Check the credential index against the issuer's DID Document.
+      let issuer_did: CoreDID = CoreDID::parse(issuer.url().as_str()).map_err(|err| ValidationError::SignerUrl {
+        signer_ctx: SignerContext::Issuer,
+        source: err.into(),
+      })?;
+      let issuer: &CoreDocument = trusted_issuers
+        .iter()
+        .find(|issuer| issuer.id() == &issuer_did)
+        .ok_or(ValidationError::DocumentMismatch(SignerContext::Issuer))?;
+
+      let issuer_service_url: DIDUrl = status.id().map_err(ValidationError::InvalidStatus)?;
+      let purpose: StatusPurpose = status.purpose().map_err(ValidationError::InvalidStatus)?;
+
+      // The purpose of the status must match the purpose of the referenced bitmap.
+      let service: &Service = issuer
+        .resolve_service(&issuer_service_url)
+        .ok_or(ValidationError::ServiceLookupError)?;
+      if RevocationBitmap::service_purpose(service).map_err(|_| ValidationError::ServiceLookupError)? != purpose {
+        return Err(ValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
+          "the referenced service is not a bitmap with purpose '{purpose}'"
+        ))));
+      }
+
+      // Check whether index is set.
+      let revocation_bitmap: RevocationBitmap =
+        RevocationBitmap::try_from(service).map_err(|_| ValidationError::ServiceLookupError)?;
+      let index: u32 = status.index().map_err(ValidationError::InvalidStatus)?;
+      match (revocation_bitmap.is_revoked(index), purpose) {
+        (false, _) => Ok(()),
+        (true, StatusPurpose::Revocation) => Err(ValidationError::Revoked),
+        (true, StatusPurpose::Suspension) => Err(ValidationError::Suspended),
+      }
+    }
+  }
+}
+
+#[cfg(feature = "status-list-2021")]
+pub use self::status_list_2021::StatusList2021StatusChecker;
+
+#[cfg(feature = "status-list-2021")]
+mod status_list_2021 {
+  use std::collections::HashMap;
+
+  use identity_core::common::Url;
+  use identity_did::CoreDID;
+  use identity_document::document::CoreDocument;
+
+  use crate::credential::Issuer;
+  use crate::credential::Status;
+  use crate::revocation::StatusList2021Credential;
+  use crate::revocation::StatusList2021Entry;
+  use crate::revocation::StatusPurpose;
+  use crate::validator::SignerContext;
+  use crate::validator::ValidationError;
+
+  use super::CredentialStatusChecker;
+
+  /// The built-in [`CredentialStatusChecker`] for `StatusList2021Entry`, which checks the status against
+  /// status list credentials that were fetched beforehand.
+  ///
+  /// The status list credential must be issued by the issuer of the credential, who must be one of the trusted
+  /// issuers, and have the same `statusPurpose` as the entry. A set index results in [`ValidationError::Revoked`] or
+  /// [`ValidationError::Suspended`], depending on that purpose.
+  ///
+  /// An entry referencing a status list credential that was not added is rejected. Either register a checker with
+  /// the required status list credentials or use
+  /// [`CredentialValidator::check_status_with_status_list_2021`](crate::validator::CredentialValidator::check_status_with_status_list_2021)
+  /// to fetch them.
+  #[derive(Clone, Debug, Default)]
+  pub struct StatusList2021StatusChecker {
+    status_lists: HashMap<Url, StatusList2021Credential>,
+  }
+
+  impl StatusList2021StatusChecker {
+    /// Creates a checker without any status list credentials.
+    pub fn new() -> Self {
+      Self::default()
+    }
+
+    /// Adds the status list credential referenced by entries with the `statusListCredential` `url`, replacing any
+    /// previously added one.
+    ///
+    /// The caller is responsible for verifying the proof of `list_credential`.
+    pub fn insert(&mut self, url: Url, list_credential: StatusList2021Credential) -> Option<StatusList2021Credential> {
+      self.status_lists.insert(url, list_credential)
+    }
+
+    /// Adds the status list credential referenced by entries with the `statusListCredential` `url`, replacing any
+    /// previously added one.
+    ///
+    /// The caller is responsible for verifying the proof of `list_credential`.
+    #[must_use]
+    pub fn with(mut self, url: Url, list_credential: StatusList2021Credential) -> Self {
+      self.insert(url, list_credential);
+      self
+    }
+  }
+
+  impl CredentialStatusChecker for StatusList2021StatusChecker {
+    fn check_status(
+      &self,
+      status: &Status,
+      issuer: &Issuer,
+      trusted_issuers: &[&CoreDocument],
+    ) -> Result<(), ValidationError> {
+      let entry: StatusList2021Entry =
+        StatusList2021Entry::try_from(status.clone()).map_err(ValidationError::InvalidStatus)?;
+      let list_url: Url = entry.status_list_credential().map_err(ValidationError::InvalidStatus)?;
+      let list_credential: &StatusList2021Credential = self.status_lists.get(&list_url).ok_or_else(|| {
+        ValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
+          "the status list credential '{list_url}' must be fetched to check the status"
+        )))
+      })?;
+
+      if list_credential.credential().issuer.url() != issuer.url() {
+        return Err(ValidationError::InvalidStatus(crate::Error::InvalidStatus(
+          "the status list credential was not issued by the credential issuer".to_owned(),
+        )));
+      }
+
+      // The status list credential must be issued by a trusted issuer.
+      let issuer_did: CoreDID = CoreDID::parse(issuer.url().as_str()).map_err(|err| ValidationError::SignerUrl {
+        signer_ctx: SignerContext::Issuer,
+        source: err.into(),
+      })?;
+      if !trusted_issuers.iter().any(|issuer| issuer.id() == &issuer_did) {
+        return Err(ValidationError::DocumentMismatch(SignerContext::Issuer));
+      }
+
+      let purpose: StatusPurpose = entry.purpose().map_err(ValidationError::InvalidStatus)?;
+      if list_credential.purpose() != purpose {
+        return Err(ValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
+          "expected a status list with purpose '{purpose}', got '{}'",
+          list_credential.purpose()
+        ))));
+      }
+
+      let index: usize = entry.index().map_err(ValidationError::InvalidStatus)?;
+      let is_set: bool = list_credential
+        .status_list()
+        .and_then(|status_list| status_list.get(index))
+        .map_err(|err| ValidationError::InvalidStatus(crate::Error::InvalidStatus(err.to_string())))?;
+
+      match (is_set, purpose) {
+        (false, _) => Ok(()),
+        (true, StatusPurpose::Revocation) => Err(ValidationError::Revoked),
+        (true, StatusPurpose::Suspension) => Err(ValidationError::Suspended),
+      }
+    }
+  }
+}
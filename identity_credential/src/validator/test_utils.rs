@@ -5,17 +5,28 @@ use identity_core::common::Object;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
 use identity_core::convert::FromJson;
+use identity_core::crypto::Ed25519;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::KeyType;
+use identity_core::crypto::Sign;
 use identity_core::json;
 use identity_core::utils::BaseEncoding;
 use identity_did::CoreDID;
 use identity_did::DID;
 use identity_document::document::CoreDocument;
+use identity_jose::jwk::EdCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jwk::JwkParamsOkp;
+use identity_jose::jws::Encoder;
+use identity_jose::jws::JwsAlgorithm;
+use identity_jose::jws::JwsHeader;
+use identity_jose::jws::Recipient;
+use identity_jose::jwu;
 use identity_verification::VerificationMethod;
 
 use crate::credential::Credential;
 use crate::credential::CredentialBuilder;
+use crate::credential::Jwt;
 use crate::credential::Subject;
 
 pub(super) fn generate_document_with_keys() -> (CoreDocument, KeyPair) {
@@ -72,3 +83,44 @@ pub(super) fn credential_setup() -> (CoreDocument, KeyPair, Credential) {
   let credential = generate_credential(&issuer_doc, &[subject_doc], issuance_date, expiration_date);
   (issuer_doc, issuer_key, credential)
 }
+
+// generates a document with a single `JsonWebKey2020` verification method with the fragment `#key-1`
+pub(super) fn generate_jwk_document_with_keys() -> (CoreDocument, KeyPair) {
+  let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+  let did: CoreDID = CoreDID::parse(format!("did:example:{}", BaseEncoding::encode_base58(keypair.public()))).unwrap();
+
+  let mut params: JwkParamsOkp = JwkParamsOkp::new();
+  params.crv = EdCurve::Ed25519.name().to_owned();
+  params.x = jwu::encode_b64(keypair.public());
+  let mut jwk: Jwk = Jwk::from_params(params);
+  jwk.set_alg(JwsAlgorithm::EdDSA.name());
+
+  let document: CoreDocument = CoreDocument::builder(Object::new())
+    .id(did.clone())
+    .verification_method(VerificationMethod::new_from_jwk(did, jwk, Some("key-1")).unwrap())
+    .build()
+    .unwrap();
+  (document, keypair)
+}
+
+// signs the credential as a JWT with the EdDSA algorithm, setting the `kid` header to `method_id`
+pub(super) async fn sign_credential_jwt(credential: &Credential, method_id: &str, keypair: &KeyPair) -> Jwt {
+  sign_jwt(&credential.serialize_jwt().unwrap(), method_id, keypair).await
+}
+
+pub(super) async fn sign_jwt(claims: &str, method_id: &str, keypair: &KeyPair) -> Jwt {
+  let mut header: JwsHeader = JwsHeader::new();
+  header.set_alg(JwsAlgorithm::EdDSA);
+  header.set_kid(method_id);
+
+  let sign_fn = |_protected, _unprotected, message: Vec<u8>| async move {
+    Ed25519::sign(&message, keypair.private().as_ref()).map(jwu::encode_b64)
+  };
+
+  Encoder::new()
+    .recipient(Recipient::new().protected(&header))
+    .encode(&sign_fn, claims.as_bytes())
+    .await
+    .map(Jwt::new)
+    .unwrap()
+}
@@ -6,6 +6,9 @@ use identity_document::verifiable::VerifierOptions;
 use serde::Deserialize;
 use serde::Serialize;
 
+use super::CredentialStatusChecker;
+use super::CredentialStatusCheckers;
+
 /// Options to declare validation criteria for credentials.
 #[non_exhaustive]
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -28,6 +31,23 @@ pub struct CredentialValidationOptions {
   #[serde(default)]
   pub status: StatusCheck,
 
+  /// The checkers the [`credentialStatus`](https://www.w3.org/TR/vc-data-model/#status) is dispatched to by type.
+  ///
+  /// A status whose type has no registered checker is unsupported and handled according to [`Self::status`].
+  ///
+  /// Default: [`CredentialStatusCheckers::default`].
+  #[serde(skip)]
+  pub status_checkers: CredentialStatusCheckers,
+
+  /// Validates each credential subject against the `JsonSchemaValidator2018` and `JsonSchema2023`
+  /// [`credentialSchema`](https://www.w3.org/TR/vc-data-model/#data-schemas) entries of the credential,
+  /// with schemas obtained from this loader.
+  ///
+  /// Default: `None`, which skips schema validation.
+  #[cfg(feature = "json-schema")]
+  #[serde(skip)]
+  pub schema_loader: Option<std::sync::Arc<dyn super::SchemaLoader>>,
+
   /// Options which affect the verification of the signature on the credential.
   #[serde(default)]
   pub verifier_options: VerifierOptions,
@@ -64,6 +84,25 @@ impl CredentialValidationOptions {
     self.verifier_options = options;
     self
   }
+
+  /// Set the checkers the credential status is dispatched to by type.
+  pub fn status_checkers(mut self, checkers: CredentialStatusCheckers) -> Self {
+    self.status_checkers = checkers;
+    self
+  }
+
+  /// Register a checker for credential statuses of the given `type_`.
+  pub fn status_checker(mut self, type_: impl Into<String>, checker: impl CredentialStatusChecker + 'static) -> Self {
+    self.status_checkers.insert(type_, checker);
+    self
+  }
+
+  /// Enable validation of the credential subjects against the credential's JSON Schemas, loaded with `loader`.
+  #[cfg(feature = "json-schema")]
+  pub fn schema_loader(mut self, loader: impl super::SchemaLoader + 'static) -> Self {
+    self.schema_loader = Some(std::sync::Arc::new(loader));
+    self
+  }
 }
 
 /// Controls validation behaviour when checking whether or not a credential has been revoked by its
@@ -74,7 +113,8 @@ pub enum StatusCheck {
   /// Validate the status if supported, reject any unsupported
   /// [`credentialStatus`](https://www.w3.org/TR/vc-data-model/#status) types.
   ///
-  /// Only `RevocationBitmap2022` is currently supported.
+  /// A status type is supported if a checker is registered for it in
+  /// [`CredentialValidationOptions::status_checkers`].
   ///
   /// This is the default.
   Strict = 0,
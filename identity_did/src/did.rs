@@ -102,7 +102,7 @@ pub trait DID:
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
 #[repr(transparent)]
-#[serde(into = "BaseDIDUrl", try_from = "BaseDIDUrl")]
+#[serde(into = "BaseDIDUrl", try_from = "String")]
 /// A wrapper around [`BaseDIDUrl`](BaseDIDUrl).
 pub struct CoreDID(BaseDIDUrl);
 
@@ -113,7 +113,7 @@ impl CoreDID {
   ///
   /// Returns `Err` if the input is not a valid [`DID`].
   pub fn parse(input: impl AsRef<str>) -> Result<Self, Error> {
-    let base_did_url: BaseDIDUrl = BaseDIDUrl::parse(input).map_err(Error::from)?;
+    let base_did_url: BaseDIDUrl = parse_base_did_url(input.as_ref())?;
     Self::try_from_base_did(base_did_url)
   }
 
@@ -146,9 +146,20 @@ impl CoreDID {
   }
 
   /// Validates whether a string is a valid [`DID`] method-id.
+  ///
+  /// Percent-encoded characters are permitted, as long as each `%` is followed by two hexadecimal digits.
   pub fn valid_method_id(value: &str) -> Result<(), Error> {
-    if !value.chars().all(is_char_method_id) {
-      return Err(Error::InvalidMethodId);
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+      let valid: bool = if ch == '%' {
+        matches!(chars.next(), Some(ch) if ch.is_ascii_hexdigit())
+          && matches!(chars.next(), Some(ch) if ch.is_ascii_hexdigit())
+      } else {
+        is_char_method_id(ch)
+      };
+      if !valid {
+        return Err(Error::InvalidMethodId);
+      }
     }
     Ok(())
   }
@@ -283,6 +294,24 @@ impl KeyComparable for CoreDID {
   }
 }
 
+/// Parses a [`BaseDIDUrl`], additionally permitting percent-encoded characters in the method-id.
+///
+/// [`BaseDIDUrl::parse`] rejects any `%`, so the input is parsed with each `%` of the method-id replaced by `.`,
+/// which leaves all segment offsets intact, and the original method-id is restored afterwards.
+pub(crate) fn parse_base_did_url(input: &str) -> Result<BaseDIDUrl, Error> {
+  let method_id_end: usize = input.find(['/', '?', '#']).unwrap_or(input.len());
+  if !input[..method_id_end].contains('%') {
+    return BaseDIDUrl::parse(input).map_err(Error::from);
+  }
+
+  let masked: String = input[..method_id_end].replace('%', ".") + &input[method_id_end..];
+  let mut base_did_url: BaseDIDUrl = BaseDIDUrl::parse(masked).map_err(Error::from)?;
+  let method_id: &str = &input[method_id_end - base_did_url.method_id().len()..method_id_end];
+  CoreDID::valid_method_id(method_id)?;
+  base_did_url.set_method_id(method_id);
+  Ok(base_did_url)
+}
+
 /// Checks whether a character satisfies DID method name constraints:
 /// { 0-9 | a-z }
 #[inline(always)]
@@ -326,6 +355,10 @@ mod tests {
       CoreDID::parse("did:iota:main:123456890").unwrap(),
       "did:iota:main:123456890"
     );
+    assert_eq!(
+      CoreDID::parse("did:web:localhost%3A8443").unwrap(),
+      "did:web:localhost%3A8443"
+    );
   }
 
   #[test]
@@ -333,6 +366,8 @@ mod tests {
     assert!(CoreDID::parse("").is_err());
     assert!(CoreDID::parse("did:").is_err());
     assert!(CoreDID::parse("dad:example:123456890").is_err());
+    assert!(CoreDID::parse("did:example:123%3").is_err());
+    assert!(CoreDID::parse("did:example:123%zz").is_err());
   }
 
   proptest::proptest! {
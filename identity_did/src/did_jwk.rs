@@ -0,0 +1,200 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use identity_jose::jwk::Jwk;
+use identity_jose::jwk::JwkParams;
+use identity_jose::jwu::decode_b64_json;
+use identity_jose::jwu::encode_b64_json;
+
+use crate::CoreDID;
+use crate::Error;
+use crate::DID;
+
+/// A [`DID`] of the [did:jwk](https://github.com/quartzjer/did-jwk/blob/main/spec.md) method, whose method-specific
+/// id is the base64url encoding of a public [`Jwk`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+#[repr(transparent)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+pub struct DIDJwk(CoreDID);
+
+impl DIDJwk {
+  /// The did:jwk method name.
+  pub const METHOD: &'static str = "jwk";
+
+  /// Creates the [`DIDJwk`] of the given public key.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `jwk` contains private key material or is a symmetric key.
+  pub fn new(jwk: &Jwk) -> Result<Self, Error> {
+    check_jwk(jwk)?;
+    let method_id: String = encode_b64_json(jwk).map_err(|_| Error::Other("unable to encode did:jwk public key"))?;
+
+    CoreDID::parse(format!("did:{}:{}", Self::METHOD, method_id)).map(Self)
+  }
+
+  /// Parses a [`DIDJwk`] from the given `input`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `input` is not a valid did:jwk.
+  pub fn parse(input: impl AsRef<str>) -> Result<Self, Error> {
+    CoreDID::parse(input).and_then(Self::try_from)
+  }
+
+  /// Returns the encoded public key.
+  pub fn jwk(&self) -> Jwk {
+    Self::decode_method_id(self.method_id()).expect("a DIDJwk is valid by construction")
+  }
+
+  /// Decodes the public key of a did:jwk method-specific id.
+  fn decode_method_id(method_id: &str) -> Result<Jwk, Error> {
+    let jwk: Jwk = decode_b64_json(method_id).map_err(|_| Error::InvalidMethodId)?;
+    check_jwk(&jwk)?;
+    Ok(jwk)
+  }
+}
+
+/// Checks that `jwk` may be encoded in a did:jwk.
+fn check_jwk(jwk: &Jwk) -> Result<(), Error> {
+  if matches!(jwk.params(), JwkParams::Oct(_)) {
+    return Err(Error::Other("did:jwk does not support symmetric keys"));
+  }
+  if !jwk.is_public() {
+    return Err(Error::Other("did:jwk public key contains private key material"));
+  }
+  Ok(())
+}
+
+impl AsRef<CoreDID> for DIDJwk {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl From<DIDJwk> for CoreDID {
+  fn from(did: DIDJwk) -> Self {
+    did.0
+  }
+}
+
+impl From<DIDJwk> for String {
+  fn from(did: DIDJwk) -> Self {
+    did.0.into()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDJwk {
+  type Error = Error;
+
+  fn try_from(did: CoreDID) -> Result<Self, Self::Error> {
+    if did.method() != Self::METHOD {
+      return Err(Error::InvalidMethodName);
+    }
+    Self::decode_method_id(did.method_id())?;
+    Ok(Self(did))
+  }
+}
+
+impl TryFrom<&str> for DIDJwk {
+  type Error = Error;
+
+  fn try_from(other: &str) -> Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl TryFrom<String> for DIDJwk {
+  type Error = Error;
+
+  fn try_from(other: String) -> Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl FromStr for DIDJwk {
+  type Err = Error;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    Self::parse(string)
+  }
+}
+
+impl Display for DIDJwk {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    Display::fmt(&self.0, f)
+  }
+}
+
+impl Debug for DIDJwk {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    Debug::fmt(&self.0, f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_jose::jwk::JwkParamsOct;
+  use identity_jose::jwk::JwkParamsOkp;
+  use identity_jose::jwk::JwkUse;
+
+  use super::*;
+
+  // Test vector from the did:jwk specification.
+  const P256: &str = "did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9";
+
+  fn okp_jwk() -> Jwk {
+    let mut params: JwkParamsOkp = JwkParamsOkp::new();
+    params.crv = "X25519".to_owned();
+    params.x = "3p7bfXt9wbTTW2HC7OQ1Nz-DQ8hbeGdNrfx-FG-IK08".to_owned();
+    let mut jwk: Jwk = Jwk::from_params(params);
+    jwk.set_use(JwkUse::Encryption);
+    jwk
+  }
+
+  #[test]
+  fn test_parse() {
+    let did_jwk: DIDJwk = DIDJwk::parse(P256).unwrap();
+    let jwk: Jwk = did_jwk.jwk();
+    assert_eq!(
+      jwk.try_ec_params().unwrap().x,
+      "acbIQiuMs3i8_uszEjJ2tpTtRM4EU3yz91PH6CdH2V0"
+    );
+    assert_eq!(did_jwk.as_str(), P256);
+  }
+
+  #[test]
+  fn test_new_round_trip() {
+    let jwk: Jwk = okp_jwk();
+    let did_jwk: DIDJwk = DIDJwk::new(&jwk).unwrap();
+    assert_eq!(did_jwk.jwk(), jwk);
+    assert_eq!(DIDJwk::parse(did_jwk.as_str()).unwrap(), did_jwk);
+  }
+
+  #[test]
+  fn test_invalid() {
+    // Wrong method.
+    assert!(DIDJwk::parse(P256.replace("did:jwk", "did:example")).is_err());
+    // Not a JWK.
+    assert!(DIDJwk::parse("did:jwk:eyJmb28iOiJiYXIifQ").is_err());
+    // Private keys are rejected.
+    let mut jwk: Jwk = okp_jwk();
+    jwk.try_okp_params_mut().unwrap().d = Some("private".to_owned());
+    assert!(DIDJwk::new(&jwk).is_err());
+    // Symmetric keys are rejected.
+    assert!(DIDJwk::new(&Jwk::from_params(JwkParamsOct::new())).is_err());
+  }
+
+  #[test]
+  fn test_serde() {
+    let did_jwk: DIDJwk = DIDJwk::parse(P256).unwrap();
+    let json: String = serde_json::to_string(&did_jwk).unwrap();
+    assert_eq!(json, format!("\"{P256}\""));
+    assert_eq!(serde_json::from_str::<DIDJwk>(&json).unwrap(), did_jwk);
+  }
+}
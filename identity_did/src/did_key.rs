@@ -0,0 +1,289 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use identity_core::utils::BaseEncoding;
+
+use crate::CoreDID;
+use crate::Error;
+use crate::DID;
+
+/// The type of the public key encoded in a [`DIDKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum DIDKeyType {
+  /// An Ed25519 public key.
+  Ed25519,
+  /// An X25519 public key.
+  X25519,
+  /// A compressed P-256 public key.
+  P256,
+  /// A compressed secp256k1 public key.
+  Secp256k1,
+}
+
+impl DIDKeyType {
+  /// Returns the [multicodec](https://github.com/multiformats/multicodec/blob/master/table.csv) code of public
+  /// keys of this type.
+  pub const fn multicodec(self) -> u64 {
+    match self {
+      Self::Ed25519 => 0xed,
+      Self::X25519 => 0xec,
+      Self::P256 => 0x1200,
+      Self::Secp256k1 => 0xe7,
+    }
+  }
+
+  /// Returns the length in bytes of public keys of this type.
+  pub const fn public_key_len(self) -> usize {
+    match self {
+      Self::Ed25519 | Self::X25519 => 32,
+      Self::P256 | Self::Secp256k1 => 33,
+    }
+  }
+
+  fn from_multicodec(code: u64) -> Option<Self> {
+    [Self::Ed25519, Self::X25519, Self::P256, Self::Secp256k1]
+      .into_iter()
+      .find(|key_type| key_type.multicodec() == code)
+  }
+
+  fn check_public_key(self, public_key: &[u8]) -> Result<(), Error> {
+    if public_key.len() != self.public_key_len() {
+      return Err(Error::Other("invalid did:key public key length"));
+    }
+    // Elliptic curve points must be in compressed form.
+    if matches!(self, Self::P256 | Self::Secp256k1) && !matches!(public_key[0], 0x02 | 0x03) {
+      return Err(Error::Other("did:key public key is not a compressed point"));
+    }
+    Ok(())
+  }
+}
+
+/// A [`DID`] of the [did:key](https://w3c-ccg.github.io/did-method-key/) method, whose method-specific id is the
+/// base58-btc multibase encoding of a multicodec-prefixed public key.
+///
+/// Ed25519, X25519, P-256 and secp256k1 public keys are supported.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+#[repr(transparent)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+pub struct DIDKey(CoreDID);
+
+impl DIDKey {
+  /// The did:key method name.
+  pub const METHOD: &'static str = "key";
+
+  /// Creates the [`DIDKey`] of the given public key.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the length of `public_key` does not match `key_type`, or an elliptic curve key is not in
+  /// compressed form.
+  pub fn new(key_type: DIDKeyType, public_key: &[u8]) -> Result<Self, Error> {
+    key_type.check_public_key(public_key)?;
+
+    let mut data: Vec<u8> = encode_varint(key_type.multicodec());
+    data.extend_from_slice(public_key);
+    let did: String = format!("did:{}:{}", Self::METHOD, BaseEncoding::encode_multibase(&data, None));
+
+    CoreDID::parse(did).map(Self)
+  }
+
+  /// Parses a [`DIDKey`] from the given `input`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `input` is not a valid did:key or encodes an unsupported key type.
+  pub fn parse(input: impl AsRef<str>) -> Result<Self, Error> {
+    CoreDID::parse(input).and_then(Self::try_from)
+  }
+
+  /// Returns the type of the encoded public key.
+  pub fn key_type(&self) -> DIDKeyType {
+    self.decode().0
+  }
+
+  /// Returns the encoded public key.
+  pub fn public_key(&self) -> Vec<u8> {
+    self.decode().1
+  }
+
+  fn decode(&self) -> (DIDKeyType, Vec<u8>) {
+    Self::decode_method_id(self.method_id()).expect("a DIDKey is valid by construction")
+  }
+
+  /// Decodes the type and public key of a did:key method-specific id.
+  fn decode_method_id(method_id: &str) -> Result<(DIDKeyType, Vec<u8>), Error> {
+    // Only base58-btc is permitted.
+    if !method_id.starts_with('z') {
+      return Err(Error::InvalidMethodId);
+    }
+    let data: Vec<u8> = BaseEncoding::decode_multibase(method_id).map_err(|_| Error::InvalidMethodId)?;
+    let (code, public_key): (u64, &[u8]) = decode_varint(&data).ok_or(Error::InvalidMethodId)?;
+    let key_type: DIDKeyType =
+      DIDKeyType::from_multicodec(code).ok_or(Error::Other("unsupported did:key public key type"))?;
+    key_type.check_public_key(public_key)?;
+
+    Ok((key_type, public_key.to_vec()))
+  }
+}
+
+/// Encodes `value` as an unsigned varint.
+fn encode_varint(mut value: u64) -> Vec<u8> {
+  let mut bytes: Vec<u8> = Vec::new();
+  loop {
+    let byte: u8 = (value & 0x7f) as u8;
+    value >>= 7;
+    if value == 0 {
+      bytes.push(byte);
+      return bytes;
+    }
+    bytes.push(byte | 0x80);
+  }
+}
+
+/// Decodes an unsigned varint prefix of `data`, returning the value and the remaining bytes.
+fn decode_varint(data: &[u8]) -> Option<(u64, &[u8])> {
+  let mut value: u64 = 0;
+  // Multiformats limit varints to nine bytes.
+  for (index, byte) in data.iter().take(9).enumerate() {
+    value |= u64::from(byte & 0x7f) << (7 * index);
+    if byte & 0x80 == 0 {
+      return Some((value, &data[index + 1..]));
+    }
+  }
+  None
+}
+
+impl AsRef<CoreDID> for DIDKey {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl From<DIDKey> for CoreDID {
+  fn from(did: DIDKey) -> Self {
+    did.0
+  }
+}
+
+impl From<DIDKey> for String {
+  fn from(did: DIDKey) -> Self {
+    did.0.into()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDKey {
+  type Error = Error;
+
+  fn try_from(did: CoreDID) -> Result<Self, Self::Error> {
+    if did.method() != Self::METHOD {
+      return Err(Error::InvalidMethodName);
+    }
+    Self::decode_method_id(did.method_id())?;
+    Ok(Self(did))
+  }
+}
+
+impl TryFrom<&str> for DIDKey {
+  type Error = Error;
+
+  fn try_from(other: &str) -> Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl TryFrom<String> for DIDKey {
+  type Error = Error;
+
+  fn try_from(other: String) -> Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl FromStr for DIDKey {
+  type Err = Error;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    Self::parse(string)
+  }
+}
+
+impl Display for DIDKey {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    Display::fmt(&self.0, f)
+  }
+}
+
+impl Debug for DIDKey {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    Debug::fmt(&self.0, f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Test vectors from the did:key specification.
+  const ED25519: &str = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+  const X25519: &str = "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F";
+  const P256: &str = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+  const SECP256K1: &str = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+
+  #[test]
+  fn test_parse_key_types() {
+    for (did, key_type) in [
+      (ED25519, DIDKeyType::Ed25519),
+      (X25519, DIDKeyType::X25519),
+      (P256, DIDKeyType::P256),
+      (SECP256K1, DIDKeyType::Secp256k1),
+    ] {
+      let did_key: DIDKey = DIDKey::parse(did).unwrap();
+      assert_eq!(did_key.key_type(), key_type);
+      assert_eq!(did_key.public_key().len(), key_type.public_key_len());
+      // Round trip through the public key.
+      assert_eq!(DIDKey::new(key_type, &did_key.public_key()).unwrap(), did_key);
+      assert_eq!(did_key.as_str(), did);
+    }
+  }
+
+  #[test]
+  fn test_parse_invalid() {
+    // Wrong method.
+    assert!(DIDKey::parse("did:example:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp").is_err());
+    // Not base58-btc.
+    assert!(DIDKey::parse("did:key:f6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp").is_err());
+    // Truncated key.
+    assert!(DIDKey::parse("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDoo").is_err());
+    // Unsupported multicodec (bls12_381-g2-pub).
+    let bls: String = format!("did:key:{}", BaseEncoding::encode_multibase(&[0xeb, 0x01, 0x00], None));
+    assert!(DIDKey::parse(bls).is_err());
+    // Uncompressed elliptic curve keys are rejected.
+    assert!(DIDKey::new(DIDKeyType::P256, &[0x04; 33]).is_err());
+  }
+
+  #[test]
+  fn test_varint() {
+    for value in [0x00, 0x7f, 0xe7, 0xec, 0xed, 0x1200, u64::from(u32::MAX)] {
+      let mut encoded: Vec<u8> = encode_varint(value);
+      encoded.push(0xff);
+      assert_eq!(decode_varint(&encoded), Some((value, &[0xff][..])));
+    }
+    assert_eq!(encode_varint(0x1200), vec![0x80, 0x24]);
+    assert_eq!(decode_varint(&[0x80]), None);
+  }
+
+  #[test]
+  fn test_serde() {
+    let did_key: DIDKey = DIDKey::parse(ED25519).unwrap();
+    let json: String = serde_json::to_string(&did_key).unwrap();
+    assert_eq!(json, format!("\"{ED25519}\""));
+    assert_eq!(serde_json::from_str::<DIDKey>(&json).unwrap(), did_key);
+    assert!(serde_json::from_str::<DIDKey>("\"did:example:123\"").is_err());
+  }
+}
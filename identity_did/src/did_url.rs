@@ -18,6 +18,7 @@ use identity_core::diff::Diff;
 use identity_core::diff::DiffString;
 
 use crate::did::is_char_method_id;
+use crate::did::parse_base_did_url;
 use crate::did::CoreDID;
 use crate::did::DID;
 use crate::Error;
@@ -285,7 +286,7 @@ impl DIDUrl {
 
   /// Parse a [`DIDUrl`] from a string.
   pub fn parse(input: impl AsRef<str>) -> Result<Self, Error> {
-    let did_url: BaseDIDUrl = BaseDIDUrl::parse(input)?;
+    let did_url: BaseDIDUrl = parse_base_did_url(input.as_ref())?;
     Self::from_base_did_url(did_url)
   }
 
@@ -393,7 +394,7 @@ impl DIDUrl {
     }
 
     // Parse DID Url.
-    let base_did_url: BaseDIDUrl = BaseDIDUrl::parse(self.to_string())?.join(segment)?;
+    let base_did_url: BaseDIDUrl = parse_base_did_url(&self.to_string())?.join(segment)?;
     Self::from_base_did_url(base_did_url)
   }
 
@@ -617,6 +618,22 @@ mod tests {
     assert_eq!(did_url.fragment().unwrap(), "fragment");
   }
 
+  #[rustfmt::skip]
+  #[test]
+  fn test_did_url_percent_encoded_method_id() {
+    let did_url = DIDUrl::parse("did:web:example.com%3A3000/path?query#fragment").unwrap();
+    assert_eq!(did_url.did().as_str(), "did:web:example.com%3A3000");
+    assert_eq!(did_url.path().unwrap(), "/path");
+    assert_eq!(did_url.query().unwrap(), "query");
+    assert_eq!(did_url.fragment().unwrap(), "fragment");
+
+    let did_url = DIDUrl::parse("did:web:example.com%3A3000").unwrap();
+    assert_eq!(did_url.join("#key-1").unwrap().to_string(), "did:web:example.com%3A3000#key-1");
+
+    assert!(DIDUrl::parse("did:web:example.com%3#key-1").is_err());
+    assert!(DIDUrl::parse("did:web:example.com/%3A").is_err());
+  }
+
   #[test]
   fn test_did_url_invalid() {
     assert!(DIDUrl::parse("did:example:1234567890/invalid{path}").is_err());
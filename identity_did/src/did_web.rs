@@ -0,0 +1,258 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use identity_core::common::Url;
+
+use crate::CoreDID;
+use crate::Error;
+use crate::DID;
+
+/// The percent-encoding of the `:` separating the host and port of a did:web domain.
+const PORT_SEPARATOR: &str = "%3A";
+
+/// A [`DID`] of the [did:web](https://w3c-ccg.github.io/did-method-web/) method, whose method-specific id is a domain
+/// name, optionally followed by a percent-encoded port and a `:`-separated path.
+///
+/// The DID Document of a [`DIDWeb`] is published at the `https` URL returned by [`DIDWeb::document_url`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+#[repr(transparent)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+pub struct DIDWeb(CoreDID);
+
+impl DIDWeb {
+  /// The did:web method name.
+  pub const METHOD: &'static str = "web";
+
+  /// Creates the [`DIDWeb`] identifying the given `https` URL, e.g. `https://example.com:8443/user/alice` is
+  /// identified by `did:web:example.com%3A8443:user:alice`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `url` does not use `https`, has no host, or contains credentials, a query or a fragment.
+  pub fn from_url(url: &Url) -> Result<Self, Error> {
+    if url.scheme() != "https" {
+      return Err(Error::Other("did:web requires an https url"));
+    }
+    if !url.username().is_empty() || url.password().is_some() || url.query().is_some() || url.fragment().is_some() {
+      return Err(Error::Other("did:web url must only consist of a host, port and path"));
+    }
+    let host: &str = url.host_str().ok_or(Error::Other("did:web url has no host"))?;
+
+    let mut method_id: String = host.to_owned();
+    if let Some(port) = url.port() {
+      method_id.push_str(&format!("{PORT_SEPARATOR}{port}"));
+    }
+    for segment in url
+      .path_segments()
+      .into_iter()
+      .flatten()
+      .filter(|segment| !segment.is_empty())
+    {
+      method_id.push(':');
+      method_id.push_str(&segment.replace(':', PORT_SEPARATOR));
+    }
+
+    Self::parse(format!("did:{}:{}", Self::METHOD, method_id))
+  }
+
+  /// Parses a [`DIDWeb`] from the given `input`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if `input` is not a valid did:web.
+  pub fn parse(input: impl AsRef<str>) -> Result<Self, Error> {
+    CoreDID::parse(input).and_then(Self::try_from)
+  }
+
+  /// Returns the `https` URL of the DID Document, as defined by the
+  /// [did:web specification](https://w3c-ccg.github.io/did-method-web/#read-resolve).
+  ///
+  /// A DID without a path resolves to `https://<domain>/.well-known/did.json`, a DID with a path to
+  /// `https://<domain>/<path>/did.json`.
+  pub fn document_url(&self) -> Url {
+    Self::document_url_of(self.method_id()).expect("a DIDWeb is valid by construction")
+  }
+
+  /// Returns the domain name of the DID, without the port.
+  pub fn domain(&self) -> String {
+    self.document_url().host_str().unwrap_or_default().to_owned()
+  }
+
+  /// Derives the DID Document URL of a did:web method-specific id.
+  fn document_url_of(method_id: &str) -> Result<Url, Error> {
+    let mut segments = method_id.split(':');
+    let domain: &str = segments
+      .next()
+      .filter(|domain| !domain.is_empty())
+      .ok_or(Error::InvalidMethodId)?;
+    // Percent-encodings are case-insensitive, so the port separator may also be written as `%3a`.
+    let domain: String = match domain.to_ascii_uppercase().find(PORT_SEPARATOR) {
+      Some(index) => format!("{}:{}", &domain[..index], &domain[index + PORT_SEPARATOR.len()..]),
+      None => domain.to_owned(),
+    };
+    // Only the port separator may be percent-encoded in the domain.
+    if domain.contains('%') {
+      return Err(Error::InvalidMethodId);
+    }
+    let path: Vec<&str> = segments.collect();
+    if path.iter().any(|segment| segment.is_empty()) {
+      return Err(Error::InvalidMethodId);
+    }
+
+    let mut url: String = format!("https://{domain}");
+    if path.is_empty() {
+      url.push_str("/.well-known");
+    } else {
+      for segment in path {
+        url.push('/');
+        url.push_str(segment);
+      }
+    }
+    url.push_str("/did.json");
+
+    let url: Url = Url::parse(url).map_err(|_| Error::InvalidMethodId)?;
+    // Reject domains that the URL parser would interpret as credentials, a path, a query or a fragment.
+    if url.host_str().is_none() || !url.username().is_empty() || url.query().is_some() || url.fragment().is_some() {
+      return Err(Error::InvalidMethodId);
+    }
+    Ok(url)
+  }
+}
+
+impl AsRef<CoreDID> for DIDWeb {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl From<DIDWeb> for CoreDID {
+  fn from(did: DIDWeb) -> Self {
+    did.0
+  }
+}
+
+impl From<DIDWeb> for String {
+  fn from(did: DIDWeb) -> Self {
+    did.0.into()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDWeb {
+  type Error = Error;
+
+  fn try_from(did: CoreDID) -> Result<Self, Self::Error> {
+    if did.method() != Self::METHOD {
+      return Err(Error::InvalidMethodName);
+    }
+    Self::document_url_of(did.method_id())?;
+    Ok(Self(did))
+  }
+}
+
+impl TryFrom<&str> for DIDWeb {
+  type Error = Error;
+
+  fn try_from(other: &str) -> Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl TryFrom<String> for DIDWeb {
+  type Error = Error;
+
+  fn try_from(other: String) -> Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl FromStr for DIDWeb {
+  type Err = Error;
+
+  fn from_str(string: &str) -> Result<Self, Self::Err> {
+    Self::parse(string)
+  }
+}
+
+impl Display for DIDWeb {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    Display::fmt(&self.0, f)
+  }
+}
+
+impl Debug for DIDWeb {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    Debug::fmt(&self.0, f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_document_url() {
+    for (did, url) in [
+      (
+        "did:web:w3c-ccg.github.io",
+        "https://w3c-ccg.github.io/.well-known/did.json",
+      ),
+      (
+        "did:web:w3c-ccg.github.io:user:alice",
+        "https://w3c-ccg.github.io/user/alice/did.json",
+      ),
+      (
+        "did:web:example.com%3A3000:user:alice",
+        "https://example.com:3000/user/alice/did.json",
+      ),
+      (
+        "did:web:example.com%3a3000:user:alice",
+        "https://example.com:3000/user/alice/did.json",
+      ),
+    ] {
+      let did_web: DIDWeb = DIDWeb::parse(did).unwrap();
+      assert_eq!(did_web.document_url().as_str(), url);
+    }
+    assert_eq!(
+      DIDWeb::parse("did:web:example.com%3A3000").unwrap().domain(),
+      "example.com"
+    );
+  }
+
+  #[test]
+  fn test_from_url() {
+    for (url, did) in [
+      ("https://example.com", "did:web:example.com"),
+      (
+        "https://example.com:3000/user/alice/",
+        "did:web:example.com%3A3000:user:alice",
+      ),
+    ] {
+      let did_web: DIDWeb = DIDWeb::from_url(&Url::parse(url).unwrap()).unwrap();
+      assert_eq!(did_web.as_str(), did);
+    }
+    // The default https port is omitted.
+    let did_web: DIDWeb = DIDWeb::from_url(&Url::parse("https://example.com:443").unwrap()).unwrap();
+    assert_eq!(did_web.as_str(), "did:web:example.com");
+
+    assert!(DIDWeb::from_url(&Url::parse("http://example.com").unwrap()).is_err());
+    assert!(DIDWeb::from_url(&Url::parse("https://example.com/?query=1").unwrap()).is_err());
+    assert!(DIDWeb::from_url(&Url::parse("https://user@example.com").unwrap()).is_err());
+  }
+
+  #[test]
+  fn test_parse_invalid() {
+    // Wrong method.
+    assert!(DIDWeb::parse("did:example:example.com").is_err());
+    // Empty path segment.
+    assert!(DIDWeb::parse("did:web:example.com::alice").is_err());
+    assert!(DIDWeb::parse("did:web:example.com:").is_err());
+    // Percent-encoded characters other than the port separator in the domain.
+    assert!(DIDWeb::parse("did:web:example.com%2Fpath").is_err());
+    assert!(DIDWeb::parse("did:web:example.com%40evil.com").is_err());
+  }
+}
@@ -19,7 +19,10 @@
 
 #[allow(clippy::module_inception)]
 mod did;
+mod did_jwk;
+mod did_key;
 mod did_url;
+mod did_web;
 mod error;
 
 pub use crate::did_url::DIDUrl;
@@ -27,4 +30,8 @@ pub use crate::did_url::RelativeDIDUrl;
 pub use ::did_url::DID as BaseDIDUrl;
 pub use did::CoreDID;
 pub use did::DID;
+pub use did_jwk::DIDJwk;
+pub use did_key::DIDKey;
+pub use did_key::DIDKeyType;
+pub use did_web::DIDWeb;
 pub use error::Error;
@@ -0,0 +1,108 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::DIDJwk;
+use identity_did::DIDUrl;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkUse;
+use identity_verification::MethodRef;
+use identity_verification::VerificationMethod;
+
+use crate::document::CoreDocument;
+use crate::document::DocumentBuilder;
+use crate::error::Error;
+use crate::error::Result;
+
+impl CoreDocument {
+  /// Expands a [`DIDJwk`] into the DID Document it represents, as defined by the
+  /// [did:jwk specification](https://github.com/quartzjer/did-jwk/blob/main/spec.md#read).
+  ///
+  /// The document contains a single `JsonWebKey2020` verification method identified by `<did>#0`, which is referenced
+  /// according to the `use` of the encoded key:
+  /// - `sig` keys by the `authentication`, `assertionMethod`, `capabilityInvocation` and `capabilityDelegation`
+  ///   relationships,
+  /// - `enc` keys by the `keyAgreement` relationship,
+  /// - keys without a `use` by all of the above.
+  ///
+  /// Expansion is performed offline and never fails for a valid [`DIDJwk`].
+  pub fn expand_did_jwk(did_jwk: DIDJwk) -> Result<Self> {
+    let jwk: Jwk = did_jwk.jwk();
+    let key_use: Option<JwkUse> = jwk.use_();
+    let method: VerificationMethod =
+      VerificationMethod::new_from_jwk(did_jwk.clone(), jwk, Some("0")).map_err(Error::InvalidKeyData)?;
+    let method_id: DIDUrl = method.id().clone();
+
+    let mut builder: DocumentBuilder = CoreDocument::builder(Default::default())
+      .id(did_jwk.into())
+      .verification_method(method);
+    if key_use != Some(JwkUse::Encryption) {
+      builder = builder
+        .authentication(MethodRef::Refer(method_id.clone()))
+        .assertion_method(MethodRef::Refer(method_id.clone()))
+        .capability_invocation(MethodRef::Refer(method_id.clone()))
+        .capability_delegation(MethodRef::Refer(method_id.clone()));
+    }
+    if key_use != Some(JwkUse::Signature) {
+      builder = builder.key_agreement(MethodRef::Refer(method_id));
+    }
+
+    builder.build()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_verification::MethodScope;
+  use identity_verification::MethodType;
+
+  use super::*;
+
+  const P256: &str = "did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9";
+  const X25519_ENC: &str = "did:jwk:eyJrdHkiOiJPS1AiLCJjcnYiOiJYMjU1MTkiLCJ1c2UiOiJlbmMiLCJ4IjoiM3A3YmZYdDl3YlRUVzJIQzdPUTFOei1EUThoYmVHZE5yZngtRkctSUswOCJ9";
+
+  fn scopes() -> [MethodScope; 5] {
+    [
+      MethodScope::authentication(),
+      MethodScope::assertion_method(),
+      MethodScope::capability_invocation(),
+      MethodScope::capability_delegation(),
+      MethodScope::key_agreement(),
+    ]
+  }
+
+  #[test]
+  fn test_expand_without_use() {
+    let did_jwk: DIDJwk = DIDJwk::parse(P256).unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_jwk(did_jwk.clone()).unwrap();
+    assert_eq!(document.id(), did_jwk.as_ref());
+
+    let method: &VerificationMethod = document.resolve_method("#0", None).unwrap();
+    assert_eq!(method.type_(), &MethodType::JSON_WEB_KEY_2020);
+    assert_eq!(method.data().public_key_jwk(), Some(&did_jwk.jwk()));
+    for scope in scopes() {
+      assert!(document.resolve_method("#0", Some(scope)).is_some());
+    }
+  }
+
+  #[test]
+  fn test_expand_encryption_key() {
+    let document: CoreDocument = CoreDocument::expand_did_jwk(DIDJwk::parse(X25519_ENC).unwrap()).unwrap();
+    let [authentication, assertion, invocation, delegation, key_agreement] = scopes();
+    assert!(document.resolve_method("#0", Some(key_agreement)).is_some());
+    for scope in [authentication, assertion, invocation, delegation] {
+      assert!(document.resolve_method("#0", Some(scope)).is_none());
+    }
+  }
+
+  #[test]
+  fn test_expand_signature_key() {
+    let mut jwk: Jwk = DIDJwk::parse(P256).unwrap().jwk();
+    jwk.set_use(JwkUse::Signature);
+    let document: CoreDocument = CoreDocument::expand_did_jwk(DIDJwk::new(&jwk).unwrap()).unwrap();
+    let [authentication, assertion, invocation, delegation, key_agreement] = scopes();
+    for scope in [authentication, assertion, invocation, delegation] {
+      assert!(document.resolve_method("#0", Some(scope)).is_some());
+    }
+    assert!(document.resolve_method("#0", Some(key_agreement)).is_none());
+  }
+}
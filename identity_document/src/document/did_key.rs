@@ -0,0 +1,139 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::DIDKey;
+use identity_did::DIDKeyType;
+use identity_did::DIDUrl;
+use identity_did::DID;
+use identity_verification::MethodData;
+use identity_verification::MethodRef;
+use identity_verification::MethodType;
+use identity_verification::VerificationMethod;
+
+use crate::document::CoreDocument;
+use crate::document::DocumentBuilder;
+use crate::error::Error;
+use crate::error::Result;
+
+impl CoreDocument {
+  /// Expands a [`DIDKey`] into the DID Document it represents, as defined by the
+  /// [did:key specification](https://w3c-ccg.github.io/did-method-key/#document-creation-algorithm).
+  ///
+  /// The document contains a single verification method identified by `<did>#<method-specific-id>`:
+  /// - Ed25519 keys are expanded to an `Ed25519VerificationKey2018` method,
+  /// - X25519 keys to an `X25519KeyAgreementKey2019` method,
+  /// - P-256 and secp256k1 keys to a `Multikey` method, whose `publicKeyMultibase` is the method-specific id.
+  ///
+  /// The method of an X25519 key is referenced by the `keyAgreement` relationship. The methods of all other keys are
+  /// referenced by the `authentication`, `assertionMethod`, `capabilityInvocation` and `capabilityDelegation`
+  /// relationships.
+  ///
+  /// Expansion is performed offline and never fails for a valid [`DIDKey`].
+  pub fn expand_did_key(did_key: DIDKey) -> Result<Self> {
+    let method_id: DIDUrl = did_key.clone().join(format!("#{}", did_key.method_id()))?;
+    let key_type: DIDKeyType = did_key.key_type();
+    let (method_type, method_data): (MethodType, MethodData) = match key_type {
+      DIDKeyType::Ed25519 => (
+        MethodType::ED25519_VERIFICATION_KEY_2018,
+        MethodData::new_multibase(did_key.public_key()),
+      ),
+      DIDKeyType::X25519 => (
+        MethodType::X25519_KEY_AGREEMENT_KEY_2019,
+        MethodData::new_multibase(did_key.public_key()),
+      ),
+      _ => (
+        MethodType::MULTIKEY,
+        MethodData::PublicKeyMultibase(did_key.method_id().to_owned()),
+      ),
+    };
+
+    let method: VerificationMethod = VerificationMethod::builder(Default::default())
+      .id(method_id.clone())
+      .controller(did_key.clone().into())
+      .type_(method_type)
+      .data(method_data)
+      .build()
+      .map_err(Error::InvalidKeyData)?;
+
+    let builder: DocumentBuilder = CoreDocument::builder(Default::default())
+      .id(did_key.into())
+      .verification_method(method);
+    let builder: DocumentBuilder = if key_type == DIDKeyType::X25519 {
+      builder.key_agreement(MethodRef::Refer(method_id))
+    } else {
+      builder
+        .authentication(MethodRef::Refer(method_id.clone()))
+        .assertion_method(MethodRef::Refer(method_id.clone()))
+        .capability_invocation(MethodRef::Refer(method_id.clone()))
+        .capability_delegation(MethodRef::Refer(method_id))
+    };
+
+    builder.build()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_verification::MethodScope;
+
+  use super::*;
+
+  #[test]
+  fn test_expand_ed25519() {
+    let did_key: DIDKey = DIDKey::parse("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_key(did_key.clone()).unwrap();
+    assert_eq!(document.id(), did_key.as_ref());
+
+    let fragment: &str = "#z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+    let method: &VerificationMethod = document.resolve_method(fragment, None).unwrap();
+    assert_eq!(method.type_(), &MethodType::ED25519_VERIFICATION_KEY_2018);
+    assert_eq!(method.data().try_decode().unwrap(), did_key.public_key());
+    for scope in [
+      MethodScope::authentication(),
+      MethodScope::assertion_method(),
+      MethodScope::capability_invocation(),
+      MethodScope::capability_delegation(),
+    ] {
+      assert!(document.resolve_method(fragment, Some(scope)).is_some());
+    }
+    assert!(document
+      .resolve_method(fragment, Some(MethodScope::key_agreement()))
+      .is_none());
+  }
+
+  #[test]
+  fn test_expand_x25519() {
+    let did_key: DIDKey = DIDKey::parse("did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_key(did_key).unwrap();
+    let fragment: &str = "#z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F";
+    let method: &VerificationMethod = document
+      .resolve_method(fragment, Some(MethodScope::key_agreement()))
+      .unwrap();
+    assert_eq!(method.type_(), &MethodType::X25519_KEY_AGREEMENT_KEY_2019);
+    assert!(document
+      .resolve_method(fragment, Some(MethodScope::authentication()))
+      .is_none());
+  }
+
+  #[test]
+  fn test_expand_elliptic_curve_keys() {
+    for did in [
+      "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+      "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme",
+    ] {
+      let did_key: DIDKey = DIDKey::parse(did).unwrap();
+      let document: CoreDocument = CoreDocument::expand_did_key(did_key.clone()).unwrap();
+      let method: &VerificationMethod = document
+        .resolve_method(
+          format!("#{}", did_key.method_id()).as_str(),
+          Some(MethodScope::assertion_method()),
+        )
+        .unwrap();
+      assert_eq!(method.type_(), &MethodType::MULTIKEY);
+      assert_eq!(
+        method.data(),
+        &MethodData::PublicKeyMultibase(did_key.method_id().to_owned())
+      );
+    }
+  }
+}
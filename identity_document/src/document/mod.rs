@@ -11,3 +11,5 @@ pub use self::core_document::CoreDocument;
 pub(crate) use core_document::CoreDocumentData;
 mod builder;
 mod core_document;
+mod did_jwk;
+mod did_key;
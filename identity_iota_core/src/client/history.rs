@@ -0,0 +1,98 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::str::FromStr;
+
+use identity_core::common::Timestamp;
+
+use crate::block::output::AliasOutput;
+use crate::block::output::OutputId;
+use crate::block::BlockId;
+use crate::Error;
+use crate::IotaDocument;
+use crate::Result;
+
+/// A state of an Alias Output as it was included in the ledger, possibly already spent.
+///
+/// See [`IotaIdentityClient::get_alias_output_record`](crate::IotaIdentityClient::get_alias_output_record()).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AliasOutputRecord {
+  /// The id of the output.
+  pub output_id: OutputId,
+  /// The Alias Output.
+  pub alias_output: AliasOutput,
+  /// The id of the block whose transaction created the output.
+  pub block_id: BlockId,
+  /// The Unix timestamp of the milestone that confirmed the block.
+  pub milestone_timestamp: u32,
+  /// The id of the Alias Output that was transitioned into this output, or `None` if the output created the alias.
+  pub previous_output_id: Option<OutputId>,
+}
+
+/// A historical version of a DID document, as returned by
+/// [`IotaIdentityClientExt::resolve_did_history`](crate::IotaIdentityClientExt::resolve_did_history()).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DIDHistoryEntry {
+  /// The DID document, which is empty and deactivated if the state metadata of the Alias Output was empty.
+  pub document: IotaDocument,
+  /// The state index of the Alias Output that contained the document.
+  pub state_index: u32,
+  /// The id of the Alias Output that contained the document.
+  pub output_id: OutputId,
+  /// The id of the block whose transaction published the document.
+  pub block_id: BlockId,
+  /// The timestamp of the milestone that confirmed the block.
+  pub milestone_timestamp: Timestamp,
+}
+
+/// Selects a historical version of a DID document, see
+/// [`IotaIdentityClientExt::resolve_did_version`](crate::IotaIdentityClientExt::resolve_did_version()).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IotaDIDVersion {
+  /// The latest version with the given state index of the Alias Output.
+  StateIndex(u32),
+  /// The version contained in the Alias Output with the given id.
+  OutputId(OutputId),
+  /// The latest version confirmed at or before the given time.
+  Time(Timestamp),
+}
+
+impl IotaDIDVersion {
+  /// Parses a `versionId`, which is either a state index or the hex-encoded id of an Alias Output.
+  ///
+  /// # Errors
+  ///
+  /// [`Error::OutputIdConversionError`] if `version_id` is neither a state index nor an output id.
+  pub fn parse_version_id(version_id: &str) -> Result<Self> {
+    if let Ok(state_index) = version_id.parse::<u32>() {
+      return Ok(Self::StateIndex(state_index));
+    }
+    OutputId::from_str(version_id)
+      .map(Self::OutputId)
+      .map_err(|err| Error::OutputIdConversionError(format!("invalid version id `{version_id}`: {err}")))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::block::payload::transaction::TransactionId;
+
+  use super::*;
+
+  #[test]
+  fn test_parse_version_id() {
+    assert_eq!(
+      IotaDIDVersion::parse_version_id("42").unwrap(),
+      IotaDIDVersion::StateIndex(42)
+    );
+
+    let output_id: OutputId = OutputId::new(TransactionId::new([7; 32]), 1).unwrap();
+    assert_eq!(
+      IotaDIDVersion::parse_version_id(&output_id.to_string()).unwrap(),
+      IotaDIDVersion::OutputId(output_id)
+    );
+
+    assert!(IotaDIDVersion::parse_version_id("-1").is_err());
+    assert!(IotaDIDVersion::parse_version_id("0x1234").is_err());
+  }
+}
@@ -1,6 +1,10 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
+use identity_core::common::Timestamp;
+
 use crate::block::protocol::ProtocolParameters;
 
 use crate::block::address::Address;
@@ -14,8 +18,11 @@ use crate::block::output::Feature;
 use crate::block::output::OutputId;
 use crate::block::output::RentStructure;
 use crate::block::output::UnlockCondition;
+use crate::AliasOutputRecord;
+use crate::DIDHistoryEntry;
 use crate::Error;
 use crate::IotaDID;
+use crate::IotaDIDVersion;
 use crate::IotaDocument;
 use crate::NetworkName;
 use crate::Result;
@@ -28,6 +35,17 @@ pub trait IotaIdentityClient {
   async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)>;
   /// Get the protocol parameters of the node we are trying to connect to.
   async fn get_protocol_parameters(&self) -> Result<ProtocolParameters>;
+  /// Fetch the Alias Output of the given Alias identifier with the given [`OutputId`], even if it was already spent,
+  /// together with the block and milestone that included it and the output it was transitioned from.
+  ///
+  /// This is required by [`IotaIdentityClientExt::resolve_did_history`]. The default implementation returns
+  /// [`Error::AliasOutputHistoryError`].
+  async fn get_alias_output_record(&self, alias_id: AliasId, output_id: OutputId) -> Result<AliasOutputRecord> {
+    let _ = (alias_id, output_id);
+    Err(Error::AliasOutputHistoryError(
+      "the client does not support fetching historical alias outputs",
+    ))
+  }
 }
 
 /// An extension trait that provides helper functions for publication
@@ -150,6 +168,83 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
     IotaDocument::unpack_from_output(did, &alias_output, true)
   }
 
+  /// Resolve every historical version of the [`IotaDocument`] of the given `did`, ordered from the oldest to the
+  /// latest version.
+  ///
+  /// Versions are resolved by walking the history of the associated Alias Output, so every state and governance
+  /// transition yields an entry. Empty state metadata yields an empty, deactivated document.
+  ///
+  /// NOTE: nodes prune spent outputs, so a permanode is required to resolve older versions.
+  ///
+  /// # Errors
+  ///
+  /// - [`NetworkMismatch`](Error::NetworkMismatch) if the network of the DID and client differ.
+  /// - [`AliasOutputHistoryError`](Error::AliasOutputHistoryError) if the history is inconsistent or the client does
+  ///   not support fetching historical outputs.
+  async fn resolve_did_history(&self, did: &IotaDID) -> Result<Vec<DIDHistoryEntry>> {
+    validate_network(self, did).await?;
+
+    let alias_id: AliasId = AliasId::from(did);
+    let (mut output_id, _) = self.get_alias_output(alias_id).await?;
+
+    let mut history: Vec<DIDHistoryEntry> = Vec::new();
+    let mut visited: HashSet<OutputId> = HashSet::new();
+    loop {
+      if !visited.insert(output_id) {
+        return Err(Error::AliasOutputHistoryError("alias output history contains a cycle"));
+      }
+
+      let record: AliasOutputRecord = self.get_alias_output_record(alias_id, output_id).await?;
+      let milestone_timestamp: Timestamp = Timestamp::from_unix(i64::from(record.milestone_timestamp))
+        .map_err(|err| Error::SerializationError("invalid milestone timestamp", Some(err)))?;
+      history.push(DIDHistoryEntry {
+        document: IotaDocument::unpack_from_output(did, &record.alias_output, true)?,
+        state_index: record.alias_output.state_index(),
+        output_id: record.output_id,
+        block_id: record.block_id,
+        milestone_timestamp,
+      });
+
+      match record.previous_output_id {
+        Some(previous_output_id) => output_id = previous_output_id,
+        None => break,
+      }
+    }
+
+    history.reverse();
+
+    // Link each version to the next one.
+    let mut next_version_id: Option<String> = None;
+    for entry in history.iter_mut().rev() {
+      let version_id: String = entry.output_id.to_string();
+      entry.document.metadata.version_id = Some(version_id.clone());
+      entry.document.metadata.next_version_id = next_version_id.replace(version_id);
+    }
+
+    Ok(history)
+  }
+
+  /// Resolve the historical version of the [`IotaDocument`] of the given `did` selected by `version`.
+  ///
+  /// The `versionId` and `nextVersionId` of the document metadata identify the resolved version and the version
+  /// that superseded it, if any. See also [`resolve_did_history`](Self::resolve_did_history).
+  ///
+  /// # Errors
+  ///
+  /// - [`DIDVersionNotFound`](Error::DIDVersionNotFound) if no version matches `version`.
+  /// - Any error of [`resolve_did_history`](Self::resolve_did_history).
+  async fn resolve_did_version(&self, did: &IotaDID, version: &IotaDIDVersion) -> Result<IotaDocument> {
+    let mut history: Vec<DIDHistoryEntry> = self.resolve_did_history(did).await?;
+    let index: Option<usize> = match version {
+      IotaDIDVersion::StateIndex(state_index) => history.iter().rposition(|entry| entry.state_index == *state_index),
+      IotaDIDVersion::OutputId(output_id) => history.iter().position(|entry| entry.output_id == *output_id),
+      IotaDIDVersion::Time(time) => history.iter().rposition(|entry| entry.milestone_timestamp <= *time),
+    };
+    index
+      .map(|index| history.swap_remove(index).document)
+      .ok_or(Error::DIDVersionNotFound)
+  }
+
   /// Fetches the [`AliasOutput`] associated with the given DID.
   ///
   /// # Errors
@@ -216,3 +311,177 @@ where
   };
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use crate::block::address::Ed25519Address;
+  use crate::block::payload::transaction::TransactionId;
+  use crate::block::BlockId;
+
+  use super::*;
+
+  /// A client serving a fixed Alias Output history.
+  struct MockClient {
+    protocol_parameters: ProtocolParameters,
+    latest_output_id: OutputId,
+    records: HashMap<OutputId, AliasOutputRecord>,
+  }
+
+  #[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+  #[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+  impl IotaIdentityClient for MockClient {
+    async fn get_alias_output(&self, _alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+      let record: &AliasOutputRecord = &self.records[&self.latest_output_id];
+      Ok((record.output_id, record.alias_output.clone()))
+    }
+
+    async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
+      Ok(self.protocol_parameters.clone())
+    }
+
+    async fn get_alias_output_record(&self, _alias_id: AliasId, output_id: OutputId) -> Result<AliasOutputRecord> {
+      self
+        .records
+        .get(&output_id)
+        .cloned()
+        .ok_or(Error::AliasOutputHistoryError("output not found"))
+    }
+  }
+
+  fn output_id(index: u8) -> OutputId {
+    OutputId::new(TransactionId::new([index; 32]), 0).unwrap()
+  }
+
+  fn mock_client(cyclic: bool) -> (MockClient, IotaDID) {
+    let protocol_parameters: ProtocolParameters = ProtocolParameters::default();
+    let network: NetworkName = NetworkName::try_from(protocol_parameters.bech32_hrp().to_owned()).unwrap();
+    let did: IotaDID = IotaDID::new(&[1; 32], &network);
+    // An alias output may not be controlled by its own alias address.
+    let address: Address = Address::Ed25519(Ed25519Address::new([2; 32]));
+
+    let mut records: HashMap<OutputId, AliasOutputRecord> = HashMap::new();
+    for state_index in 0..3u8 {
+      // The output creating the alias has a null alias id.
+      let alias_id: AliasId = if state_index == 0 {
+        AliasId::null()
+      } else {
+        AliasId::from(&did)
+      };
+      let mut document: IotaDocument = IotaDocument::new_with_id(did.clone());
+      document.metadata.updated = Some(Timestamp::from_unix(i64::from(state_index)).unwrap());
+      // The last state deactivates the document.
+      let state_metadata: Vec<u8> = if state_index == 2 {
+        Vec::new()
+      } else {
+        document.pack().unwrap()
+      };
+      let alias_output: AliasOutput = AliasOutputBuilder::new_with_amount(1_000_000, alias_id)
+        .unwrap()
+        .with_state_index(u32::from(state_index))
+        .with_state_metadata(state_metadata)
+        .add_unlock_condition(UnlockCondition::StateControllerAddress(
+          StateControllerAddressUnlockCondition::new(address),
+        ))
+        .add_unlock_condition(UnlockCondition::GovernorAddress(GovernorAddressUnlockCondition::new(
+          address,
+        )))
+        .finish(protocol_parameters.token_supply())
+        .unwrap();
+
+      let previous_output_id: Option<OutputId> = match state_index {
+        0 if cyclic => Some(output_id(2)),
+        0 => None,
+        _ => Some(output_id(state_index - 1)),
+      };
+      records.insert(
+        output_id(state_index),
+        AliasOutputRecord {
+          output_id: output_id(state_index),
+          alias_output,
+          block_id: BlockId::new([state_index; 32]),
+          milestone_timestamp: 1_000 * u32::from(state_index),
+          previous_output_id,
+        },
+      );
+    }
+
+    let client: MockClient = MockClient {
+      protocol_parameters,
+      latest_output_id: output_id(2),
+      records,
+    };
+    (client, did)
+  }
+
+  #[tokio::test]
+  async fn test_resolve_did_history() {
+    let (client, did): (MockClient, IotaDID) = mock_client(false);
+    let history: Vec<DIDHistoryEntry> = client.resolve_did_history(&did).await.unwrap();
+
+    assert_eq!(history.len(), 3);
+    for (index, entry) in history.iter().enumerate() {
+      assert_eq!(entry.state_index, index as u32);
+      assert_eq!(entry.output_id, output_id(index as u8));
+      assert_eq!(entry.block_id, BlockId::new([index as u8; 32]));
+      assert_eq!(entry.milestone_timestamp.to_unix(), 1_000 * index as i64);
+      assert_eq!(entry.document.id(), &did);
+    }
+    assert_eq!(history[1].document.metadata.updated.unwrap().to_unix(), 1);
+    assert_eq!(history[2].document.metadata.deactivated, Some(true));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_did_version() {
+    let (client, did): (MockClient, IotaDID) = mock_client(false);
+
+    let document: IotaDocument = client
+      .resolve_did_version(&did, &IotaDIDVersion::StateIndex(1))
+      .await
+      .unwrap();
+    assert_eq!(document.metadata.version_id, Some(output_id(1).to_string()));
+    assert_eq!(document.metadata.next_version_id, Some(output_id(2).to_string()));
+
+    let document: IotaDocument = client
+      .resolve_did_version(&did, &IotaDIDVersion::OutputId(output_id(0)))
+      .await
+      .unwrap();
+    assert_eq!(document.metadata.version_id, Some(output_id(0).to_string()));
+
+    // The latest version confirmed before the given time.
+    let time: Timestamp = Timestamp::from_unix(1_500).unwrap();
+    let document: IotaDocument = client
+      .resolve_did_version(&did, &IotaDIDVersion::Time(time))
+      .await
+      .unwrap();
+    assert_eq!(document.metadata.version_id, Some(output_id(1).to_string()));
+
+    // The latest version has no successor.
+    let document: IotaDocument = client
+      .resolve_did_version(&did, &IotaDIDVersion::StateIndex(2))
+      .await
+      .unwrap();
+    assert_eq!(document.metadata.next_version_id, None);
+
+    for version in [
+      IotaDIDVersion::StateIndex(3),
+      IotaDIDVersion::OutputId(output_id(3)),
+      IotaDIDVersion::Time(Timestamp::from_unix(-1).unwrap()),
+    ] {
+      assert!(matches!(
+        client.resolve_did_version(&did, &version).await.unwrap_err(),
+        Error::DIDVersionNotFound
+      ));
+    }
+  }
+
+  #[tokio::test]
+  async fn test_resolve_did_history_cycle() {
+    let (client, did): (MockClient, IotaDID) = mock_client(true);
+    assert!(matches!(
+      client.resolve_did_history(&did).await.unwrap_err(),
+      Error::AliasOutputHistoryError(_)
+    ));
+  }
+}
@@ -1,13 +1,17 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+
 use iota_client::api::input_selection::Burn;
 use iota_client::block::output::dto::OutputDto;
+use iota_client::block::output::dto::OutputMetadataDto;
 use iota_client::block::protocol::ProtocolParameters;
 use iota_client::secret::SecretManager;
 use iota_client::Client;
 
 use crate::block::address::Address;
+use crate::block::input::Input;
 use crate::block::output::unlock_condition::AddressUnlockCondition;
 use crate::block::output::AliasId;
 use crate::block::output::AliasOutput;
@@ -15,9 +19,13 @@ use crate::block::output::BasicOutputBuilder;
 use crate::block::output::Output;
 use crate::block::output::OutputId;
 use crate::block::output::UnlockCondition;
+use crate::block::payload::transaction::TransactionEssence;
+use crate::block::payload::Payload;
 use crate::block::Block;
+use crate::block::BlockId;
 use crate::client::identity_client::validate_network;
 use crate::error::Result;
+use crate::AliasOutputRecord;
 use crate::Error;
 use crate::IotaDID;
 use crate::IotaDocument;
@@ -143,6 +151,76 @@ impl IotaIdentityClient for Client {
       Err(Error::NotAnAliasOutput(output_id))
     }
   }
+
+  async fn get_alias_output_record(&self, alias_id: AliasId, output_id: OutputId) -> Result<AliasOutputRecord> {
+    let token_supply: u64 = <Self as IotaIdentityClientExt>::get_token_supply(self).await?;
+    let (output, metadata): (Output, OutputMetadataDto) =
+      get_output_with_metadata(self, output_id, token_supply).await?;
+    let alias_output: AliasOutput = match output {
+      Output::Alias(alias_output) => alias_output,
+      _ => return Err(Error::NotAnAliasOutput(output_id)),
+    };
+    let block_id: BlockId = BlockId::from_str(&metadata.block_id)
+      .map_err(|_| Error::AliasOutputHistoryError("invalid block id in output metadata"))?;
+
+    // An Alias Output with a null id created the alias, so it has no predecessor.
+    let previous_output_id: Option<OutputId> = if alias_output.alias_id().is_null() {
+      None
+    } else {
+      let block: Block = self.get_block(&block_id).await.map_err(Error::DIDResolutionError)?;
+      Some(previous_alias_output_id(self, &block, alias_id, token_supply).await?)
+    };
+
+    Ok(AliasOutputRecord {
+      output_id,
+      alias_output,
+      block_id,
+      milestone_timestamp: metadata.milestone_timestamp_booked,
+      previous_output_id,
+    })
+  }
+}
+
+/// Fetches the output with the given `output_id`, even if it was already spent, together with its metadata.
+async fn get_output_with_metadata(
+  client: &Client,
+  output_id: OutputId,
+  token_supply: u64,
+) -> Result<(Output, OutputMetadataDto)> {
+  let response = client.get_output(&output_id).await.map_err(Error::DIDResolutionError)?;
+  let output: Output = Output::try_from_dto(&response.output, token_supply).map_err(Error::OutputConversionError)?;
+  Ok((output, response.metadata))
+}
+
+/// Returns the id of the Alias Output with the given `alias_id` consumed by the transaction in `block`.
+async fn previous_alias_output_id(
+  client: &Client,
+  block: &Block,
+  alias_id: AliasId,
+  token_supply: u64,
+) -> Result<OutputId> {
+  let essence = match block.payload() {
+    Some(Payload::Transaction(transaction_payload)) => match transaction_payload.essence() {
+      TransactionEssence::Regular(essence) => essence,
+    },
+    _ => return Err(Error::AliasOutputHistoryError("expected a transaction payload")),
+  };
+
+  for input in essence.inputs().iter() {
+    let input_id: OutputId = match input {
+      Input::Utxo(utxo_input) => *utxo_input.output_id(),
+      Input::Treasury(_) => continue,
+    };
+    if let (Output::Alias(alias_output), _) = get_output_with_metadata(client, input_id, token_supply).await? {
+      if alias_output.alias_id().or_from_output_id(&input_id) == alias_id {
+        return Ok(input_id);
+      }
+    }
+  }
+
+  Err(Error::AliasOutputHistoryError(
+    "no alias output input found for the transaction",
+  ))
 }
 
 /// Publishes an `alias_output`.
@@ -0,0 +1,406 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use identity_core::common::Timestamp;
+
+use crate::block::address::Address;
+use crate::block::output::AliasId;
+use crate::block::output::AliasOutput;
+use crate::block::output::OutputId;
+use crate::block::payload::transaction::TransactionId;
+use crate::block::protocol::ProtocolParameters;
+use crate::block::BlockId;
+use crate::AliasOutputRecord;
+use crate::Error;
+use crate::IotaDID;
+use crate::IotaDocument;
+use crate::IotaIdentityClient;
+use crate::NetworkName;
+use crate::Result;
+
+/// An in-memory ledger of Alias Outputs implementing [`IotaIdentityClient`], for testing without a node.
+///
+/// Publishing simulates the validation of Alias Output transitions performed by the network, without signatures:
+/// the `signer` of a transition stands in for the address unlocking the consumed output.
+/// - A new Alias Output has a null [`AliasId`] and state index zero, and anyone may publish it. Its [`AliasId`] is
+///   derived from the id of the output.
+/// - A state transition increments the state index and is signed by the state controller. It may not change the unlock
+///   conditions or immutable features.
+/// - A governance transition keeps the state index and is signed by the governor. It may not change the state
+///   metadata, amount or immutable features.
+/// - Only the governor may destroy an Alias Output.
+///
+/// Every published output is kept, so the Alias Output history can be resolved with
+/// [`resolve_did_history`](crate::IotaIdentityClientExt::resolve_did_history).
+#[derive(Debug)]
+pub struct MemoryLedger {
+  protocol_parameters: ProtocolParameters,
+  state: Mutex<LedgerState>,
+}
+
+#[derive(Debug, Default)]
+struct LedgerState {
+  /// The ids of the unspent Alias Outputs.
+  unspent: HashMap<AliasId, OutputId>,
+  /// Every published Alias Output, including spent ones.
+  records: HashMap<OutputId, AliasOutputRecord>,
+  /// The number of published transactions, used to derive unique output and block ids.
+  transaction_count: u64,
+  /// The timestamp of the latest milestone.
+  milestone_timestamp: u32,
+}
+
+impl MemoryLedger {
+  /// Creates an empty ledger with the default [`ProtocolParameters`].
+  pub fn new() -> Self {
+    Self::with_protocol_parameters(ProtocolParameters::default())
+  }
+
+  /// Creates an empty ledger with the given [`ProtocolParameters`].
+  pub fn with_protocol_parameters(protocol_parameters: ProtocolParameters) -> Self {
+    Self {
+      protocol_parameters,
+      state: Mutex::new(LedgerState::default()),
+    }
+  }
+
+  /// Returns the network name of the ledger, which is the Bech32 human-readable part of its protocol parameters.
+  pub fn network_name(&self) -> Result<NetworkName> {
+    NetworkName::try_from(self.protocol_parameters.bech32_hrp().to_owned())
+  }
+
+  /// Publishes the given `alias_output` as if unlocked by `signer`, and returns the DID document it contains.
+  ///
+  /// # Errors
+  ///
+  /// [`Error::MemoryLedgerError`] if the transition is invalid, see [`MemoryLedger`].
+  pub fn publish_did_output(&self, signer: &Address, alias_output: AliasOutput) -> Result<IotaDocument> {
+    let network: NetworkName = self.network_name()?;
+    let (output_id, alias_output): (OutputId, AliasOutput) = self.publish_alias_output(signer, alias_output)?;
+    let alias_id: AliasId = alias_output.alias_id().or_from_output_id(&output_id);
+    let did: IotaDID = IotaDID::new(alias_id.deref(), &network);
+    IotaDocument::unpack_from_output(&did, &alias_output, true)
+  }
+
+  /// Publishes the given `alias_output` as if unlocked by `signer`, and returns its id together with the output.
+  ///
+  /// # Errors
+  ///
+  /// [`Error::MemoryLedgerError`] if the transition is invalid, see [`MemoryLedger`].
+  pub fn publish_alias_output(&self, signer: &Address, alias_output: AliasOutput) -> Result<(OutputId, AliasOutput)> {
+    let mut state: MutexGuard<'_, LedgerState> = self.lock();
+
+    let previous_output_id: Option<OutputId> = if alias_output.alias_id().is_null() {
+      if alias_output.state_index() != 0 {
+        return Err(Error::MemoryLedgerError(
+          "a new alias output must have state index zero",
+        ));
+      }
+      None
+    } else {
+      let alias_id: AliasId = *alias_output.alias_id();
+      let previous_output_id: OutputId = *state
+        .unspent
+        .get(&alias_id)
+        .ok_or(Error::MemoryLedgerError("alias output not found"))?;
+      let previous: &AliasOutput = &state.records[&previous_output_id].alias_output;
+      validate_transition(signer, previous, &alias_output)?;
+      Some(previous_output_id)
+    };
+
+    let (output_id, block_id): (OutputId, BlockId) = state.next_ids();
+    let alias_id: AliasId = alias_output.alias_id().or_from_output_id(&output_id);
+    let milestone_timestamp: u32 = state.next_milestone_timestamp();
+    state.unspent.insert(alias_id, output_id);
+    state.records.insert(
+      output_id,
+      AliasOutputRecord {
+        output_id,
+        alias_output: alias_output.clone(),
+        block_id,
+        milestone_timestamp,
+        previous_output_id,
+      },
+    );
+
+    Ok((output_id, alias_output))
+  }
+
+  /// Destroys the Alias Output containing the given `did` as if unlocked by `signer`.
+  ///
+  /// # Errors
+  ///
+  /// [`Error::MemoryLedgerError`] if the Alias Output does not exist or `signer` is not its governor.
+  pub fn delete_did_output(&self, signer: &Address, did: &IotaDID) -> Result<()> {
+    let alias_id: AliasId = AliasId::from(did);
+    let mut state: MutexGuard<'_, LedgerState> = self.lock();
+
+    let output_id: OutputId = *state
+      .unspent
+      .get(&alias_id)
+      .ok_or(Error::MemoryLedgerError("alias output not found"))?;
+    if state.records[&output_id].alias_output.governor_address() != signer {
+      return Err(Error::MemoryLedgerError(
+        "only the governor may destroy an alias output",
+      ));
+    }
+
+    state.unspent.remove(&alias_id);
+    Ok(())
+  }
+
+  fn lock(&self) -> MutexGuard<'_, LedgerState> {
+    // A panic while holding the lock cannot leave the state inconsistent, since it is only modified after validation.
+    self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+impl Default for MemoryLedger {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl LedgerState {
+  fn next_ids(&mut self) -> (OutputId, BlockId) {
+    self.transaction_count += 1;
+    let mut bytes: [u8; 32] = [0; 32];
+    bytes[..8].copy_from_slice(&self.transaction_count.to_be_bytes());
+    let output_id: OutputId = OutputId::new(TransactionId::new(bytes), 0).expect("output index zero is valid");
+    (output_id, BlockId::new(bytes))
+  }
+
+  /// Returns the current time, but never less than the timestamp of the previous milestone.
+  fn next_milestone_timestamp(&mut self) -> u32 {
+    let now: u32 = u32::try_from(Timestamp::now_utc().to_unix()).unwrap_or(u32::MAX);
+    self.milestone_timestamp = self.milestone_timestamp.max(now);
+    self.milestone_timestamp
+  }
+}
+
+/// Validates the transition from `previous` to `next` unlocked by `signer`.
+fn validate_transition(signer: &Address, previous: &AliasOutput, next: &AliasOutput) -> Result<()> {
+  if previous.immutable_features() != next.immutable_features() {
+    return Err(Error::MemoryLedgerError("immutable features may not change"));
+  }
+
+  if previous.state_index().checked_add(1) == Some(next.state_index()) {
+    if previous.state_controller_address() != signer {
+      return Err(Error::MemoryLedgerError(
+        "only the state controller may perform a state transition",
+      ));
+    }
+    if previous.unlock_conditions() != next.unlock_conditions() {
+      return Err(Error::MemoryLedgerError(
+        "a state transition may not change the unlock conditions",
+      ));
+    }
+  } else if next.state_index() == previous.state_index() {
+    if previous.governor_address() != signer {
+      return Err(Error::MemoryLedgerError(
+        "only the governor may perform a governance transition",
+      ));
+    }
+    if previous.state_metadata() != next.state_metadata() || previous.amount() != next.amount() {
+      return Err(Error::MemoryLedgerError(
+        "a governance transition may not change the state metadata or amount",
+      ));
+    }
+  } else {
+    return Err(Error::MemoryLedgerError(
+      "the state index must be incremented by one or left unchanged",
+    ));
+  }
+
+  Ok(())
+}
+
+#[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+#[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+impl IotaIdentityClient for MemoryLedger {
+  async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+    let state: MutexGuard<'_, LedgerState> = self.lock();
+    let output_id: OutputId = *state.unspent.get(&alias_id).ok_or(Error::AliasOutputNotFound)?;
+    Ok((output_id, state.records[&output_id].alias_output.clone()))
+  }
+
+  async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
+    Ok(self.protocol_parameters.clone())
+  }
+
+  async fn get_alias_output_record(&self, alias_id: AliasId, output_id: OutputId) -> Result<AliasOutputRecord> {
+    self
+      .lock()
+      .records
+      .get(&output_id)
+      .filter(|record| record.alias_output.alias_id().or_from_output_id(&output_id) == alias_id)
+      .cloned()
+      .ok_or(Error::AliasOutputHistoryError("alias output not found"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Url;
+
+  use crate::block::address::Ed25519Address;
+  use crate::block::output::unlock_condition::GovernorAddressUnlockCondition;
+  use crate::block::output::unlock_condition::StateControllerAddressUnlockCondition;
+  use crate::block::output::AliasOutputBuilder;
+  use crate::block::output::UnlockCondition;
+  use crate::DIDHistoryEntry;
+  use crate::IotaIdentityClientExt;
+
+  use super::*;
+
+  fn state_controller() -> Address {
+    Address::Ed25519(Ed25519Address::new([1; 32]))
+  }
+
+  fn governor() -> Address {
+    Address::Ed25519(Ed25519Address::new([2; 32]))
+  }
+
+  fn alias_output(
+    amount: u64,
+    alias_id: AliasId,
+    state_index: u32,
+    state_metadata: Vec<u8>,
+    governor: Address,
+  ) -> AliasOutput {
+    AliasOutputBuilder::new_with_amount(amount, alias_id)
+      .unwrap()
+      .with_state_index(state_index)
+      .with_state_metadata(state_metadata)
+      .add_unlock_condition(UnlockCondition::StateControllerAddress(
+        StateControllerAddressUnlockCondition::new(state_controller()),
+      ))
+      .add_unlock_condition(UnlockCondition::GovernorAddress(GovernorAddressUnlockCondition::new(
+        governor,
+      )))
+      .finish(ProtocolParameters::default().token_supply())
+      .unwrap()
+  }
+
+  /// Publishes a new DID whose state controller and governor differ.
+  async fn create_did(ledger: &MemoryLedger) -> IotaDocument {
+    let document: IotaDocument = IotaDocument::new(&ledger.network_name().unwrap());
+    let new_output: AliasOutput = ledger.new_did_output(state_controller(), document, None).await.unwrap();
+    let new_output: AliasOutput = alias_output(
+      new_output.amount(),
+      AliasId::null(),
+      0,
+      new_output.state_metadata().to_vec(),
+      governor(),
+    );
+    ledger.publish_did_output(&governor(), new_output).unwrap()
+  }
+
+  #[tokio::test]
+  async fn test_did_lifecycle() {
+    let ledger: MemoryLedger = MemoryLedger::new();
+    let document: IotaDocument = create_did(&ledger).await;
+    let did: IotaDID = document.id().clone();
+    assert!(!did.is_placeholder());
+    assert_eq!(ledger.resolve_did(&did).await.unwrap(), document);
+
+    // Update.
+    let mut updated: IotaDocument = document.clone();
+    updated
+      .also_known_as_mut()
+      .append(Url::parse("did:example:abc").unwrap());
+    let update: AliasOutput = ledger.update_did_output(updated.clone()).await.unwrap();
+    ledger.publish_did_output(&state_controller(), update).unwrap();
+    let resolved: IotaDocument = ledger.resolve_did(&did).await.unwrap();
+    assert_eq!(resolved.also_known_as(), updated.also_known_as());
+
+    // Deactivate.
+    let deactivation: AliasOutput = ledger.deactivate_did_output(&did).await.unwrap();
+    ledger.publish_did_output(&state_controller(), deactivation).unwrap();
+    assert_eq!(ledger.resolve_did(&did).await.unwrap().metadata.deactivated, Some(true));
+
+    // Reactivate.
+    let reactivation: AliasOutput = ledger.update_did_output(updated).await.unwrap();
+    ledger.publish_did_output(&state_controller(), reactivation).unwrap();
+    assert_eq!(ledger.resolve_did(&did).await.unwrap().metadata.deactivated, None);
+
+    let history: Vec<DIDHistoryEntry> = ledger.resolve_did_history(&did).await.unwrap();
+    assert_eq!(
+      history.iter().map(|entry| entry.state_index).collect::<Vec<_>>(),
+      [0, 1, 2, 3]
+    );
+    assert_eq!(history[2].document.metadata.deactivated, Some(true));
+
+    // Delete.
+    assert!(ledger.delete_did_output(&state_controller(), &did).is_err());
+    ledger.delete_did_output(&governor(), &did).unwrap();
+    assert!(matches!(
+      ledger.resolve_did(&did).await.unwrap_err(),
+      Error::AliasOutputNotFound
+    ));
+    assert!(ledger.delete_did_output(&governor(), &did).is_err());
+  }
+
+  #[tokio::test]
+  async fn test_transition_rules() {
+    let ledger: MemoryLedger = MemoryLedger::new();
+    let document: IotaDocument = create_did(&ledger).await;
+    let alias_id: AliasId = AliasId::from(document.id());
+    let (_, current): (OutputId, AliasOutput) = ledger.get_alias_output(alias_id).await.unwrap();
+    let state_metadata: Vec<u8> = current.state_metadata().to_vec();
+
+    // Only the state controller may update the state.
+    let update: AliasOutput = ledger.update_did_output(document).await.unwrap();
+    assert!(ledger.publish_did_output(&governor(), update).is_err());
+
+    // The state controller may not change the governor.
+    let governance_change: AliasOutput = alias_output(
+      current.amount(),
+      alias_id,
+      1,
+      state_metadata.clone(),
+      state_controller(),
+    );
+    assert!(ledger
+      .publish_did_output(&state_controller(), governance_change)
+      .is_err());
+
+    // The state index must be incremented by one.
+    let skipped: AliasOutput = alias_output(current.amount(), alias_id, 2, state_metadata.clone(), governor());
+    assert!(ledger.publish_did_output(&state_controller(), skipped).is_err());
+
+    // The governor may change the unlock conditions, but not the state.
+    let state_change: AliasOutput = alias_output(current.amount(), alias_id, 0, Vec::new(), state_controller());
+    assert!(ledger.publish_did_output(&governor(), state_change).is_err());
+    let governance_transition: AliasOutput = alias_output(
+      current.amount(),
+      alias_id,
+      0,
+      state_metadata.clone(),
+      state_controller(),
+    );
+    assert!(ledger
+      .publish_did_output(&state_controller(), governance_transition.clone())
+      .is_err());
+    let document: IotaDocument = ledger.publish_did_output(&governor(), governance_transition).unwrap();
+    assert_eq!(
+      document.metadata.governor_address,
+      document.metadata.state_controller_address
+    );
+
+    // Unknown aliases cannot be updated.
+    let unknown: AliasOutput = alias_output(
+      current.amount(),
+      AliasId::new([3; 32]),
+      1,
+      state_metadata,
+      governor(),
+    );
+    assert!(ledger.publish_did_output(&state_controller(), unknown).is_err());
+  }
+}
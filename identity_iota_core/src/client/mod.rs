@@ -1,12 +1,20 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub use history::AliasOutputRecord;
+pub use history::DIDHistoryEntry;
+pub use history::IotaDIDVersion;
 pub use identity_client::IotaIdentityClient;
 pub use identity_client::IotaIdentityClientExt;
+#[cfg(feature = "memory-ledger")]
+pub use memory_ledger::MemoryLedger;
 
 #[cfg(feature = "iota-client")]
 pub use self::iota_client::IotaClientExt;
 
+mod history;
 mod identity_client;
 #[cfg(feature = "iota-client")]
 mod iota_client;
+#[cfg(feature = "memory-ledger")]
+mod memory_ledger;
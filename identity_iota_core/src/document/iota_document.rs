@@ -499,6 +499,32 @@ mod iota_document_revocation {
         .unrevoke_credentials(service_query, indices)
         .map_err(Error::RevocationError)
     }
+
+    /// If the document has a [`RevocationBitmap`](identity_credential::revocation::RevocationBitmap)
+    /// service with the [`StatusPurpose::Suspension`](identity_credential::revocation::StatusPurpose) purpose
+    /// identified by `service_query`, suspend all specified `indices`.
+    pub fn suspend_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> Result<()>
+    where
+      Q: Into<DIDUrlQuery<'query>>,
+    {
+      self
+        .core_document_mut()
+        .suspend_credentials(service_query, indices)
+        .map_err(Error::RevocationError)
+    }
+
+    /// If the document has a [`RevocationBitmap`](identity_credential::revocation::RevocationBitmap)
+    /// service with the [`StatusPurpose::Suspension`](identity_credential::revocation::StatusPurpose) purpose
+    /// identified by `service_query`, unsuspend all specified `indices`.
+    pub fn unsuspend_credentials<'query, 'me, Q>(&'me mut self, service_query: Q, indices: &[u32]) -> Result<()>
+    where
+      Q: Into<DIDUrlQuery<'query>>,
+    {
+      self
+        .core_document_mut()
+        .unsuspend_credentials(service_query, indices)
+        .map_err(Error::RevocationError)
+    }
   }
 }
 
@@ -27,6 +27,16 @@ pub struct IotaDocumentMetadata {
   /// Bech32-encoded address of the state controller unlock condition.
   #[serde(rename = "stateControllerAddress", skip_serializing_if = "Option::is_none")]
   pub state_controller_address: Option<String>,
+  /// Identifies the version of the document, which is the id of the Alias Output containing it.
+  ///
+  /// Only set when the document was resolved from the Alias Output history.
+  #[serde(rename = "versionId", skip_serializing_if = "Option::is_none")]
+  pub version_id: Option<String>,
+  /// Identifies the next version of the document, if the document has been updated since.
+  ///
+  /// Only set when the document was resolved from the Alias Output history.
+  #[serde(rename = "nextVersionId", skip_serializing_if = "Option::is_none")]
+  pub next_version_id: Option<String>,
   #[serde(flatten)]
   pub properties: Object,
 }
@@ -42,6 +52,8 @@ impl IotaDocumentMetadata {
       deactivated: None,
       governor_address: None,
       state_controller_address: None,
+      version_id: None,
+      next_version_id: None,
       properties: Object::default(),
     }
   }
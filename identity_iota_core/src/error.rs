@@ -33,6 +33,11 @@ pub enum Error {
   ProtocolParametersError(#[source] iota_client::Error),
   #[error("invalid state metadata {0}")]
   InvalidStateMetadata(&'static str),
+  #[error("state metadata encoding: {0}")]
+  StateMetadataEncodingError(
+    &'static str,
+    #[source] Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  ),
   #[cfg(feature = "revocation-bitmap")]
   #[error("credential revocation error")]
   RevocationError(#[source] identity_credential::revocation::RevocationError),
@@ -45,6 +50,18 @@ pub enum Error {
   #[cfg(feature = "iota-client")]
   #[error("converting a DTO to an output failed")]
   OutputConversionError(#[source] iota_client::block::DtoError),
+  #[cfg(feature = "client")]
+  #[error("alias output history: {0}")]
+  AliasOutputHistoryError(&'static str),
+  #[cfg(feature = "client")]
+  #[error("the requested DID document version does not exist")]
+  DIDVersionNotFound,
+  #[cfg(feature = "client")]
+  #[error("alias output not found")]
+  AliasOutputNotFound,
+  #[cfg(feature = "memory-ledger")]
+  #[error("memory ledger: {0}")]
+  MemoryLedgerError(&'static str),
   #[error("conversion to an OutputId failed: {0}")]
   OutputIdConversionError(String),
   #[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
@@ -53,3 +70,16 @@ pub enum Error {
   #[error("could not sign the data")]
   SigningError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
+
+impl Error {
+  /// Returns `true` if the error is caused by a DID, Alias Output or DID document version that does not exist.
+  pub fn is_not_found(&self) -> bool {
+    match self {
+      #[cfg(feature = "client")]
+      Self::AliasOutputNotFound | Self::DIDVersionNotFound => true,
+      #[cfg(feature = "iota-client")]
+      Self::DIDResolutionError(iota_client::Error::NotFound { .. }) => true,
+      _ => false,
+    }
+  }
+}
@@ -0,0 +1,68 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+
+use crate::error::Result;
+use crate::Error;
+
+const BUFFER_SIZE: usize = 4096;
+const QUALITY: u32 = 11; // compression level
+const WINDOWS_SIZE: u32 = 22;
+/// The maximum size of decompressed data, which bounds the memory used to decompress untrusted state metadata.
+const MAX_DECOMPRESSED_SIZE: usize = 1024 * 1024;
+
+pub(crate) fn compress_brotli<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>> {
+  let mut buf = Vec::new();
+  let mut compressor = brotli::CompressorReader::new(input.as_ref(), BUFFER_SIZE, QUALITY, WINDOWS_SIZE);
+  compressor
+    .read_to_end(&mut buf)
+    .map_err(|err| Error::StateMetadataEncodingError("brotli compression failed", Some(Box::new(err))))?;
+  Ok(buf)
+}
+
+pub(crate) fn decompress_brotli<T: AsRef<[u8]> + ?Sized>(input: &T) -> Result<Vec<u8>> {
+  // Read at most one byte more than permitted to detect oversized data without decompressing all of it.
+  let mut decompressor = brotli::Decompressor::new(input.as_ref(), BUFFER_SIZE).take(MAX_DECOMPRESSED_SIZE as u64 + 1);
+  let mut buf = Vec::new();
+  decompressor
+    .read_to_end(&mut buf)
+    .map_err(|err| Error::StateMetadataEncodingError("brotli decompression failed", Some(Box::new(err))))?;
+  if buf.len() > MAX_DECOMPRESSED_SIZE {
+    return Err(Error::StateMetadataEncodingError(
+      "brotli decompressed data exceeds the maximum size",
+      None,
+    ));
+  }
+  Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_brotli_roundtrip() {
+    let data: &str = r#"{"doc":{"id":"did:0:0"},"meta":{"created":"2023-01-01T00:00:00Z"}}"#;
+    let compressed: Vec<u8> = compress_brotli(data).unwrap();
+    let decompressed: Vec<u8> = decompress_brotli(&compressed).unwrap();
+    assert_eq!(decompressed, data.as_bytes());
+  }
+
+  #[test]
+  fn test_decompress_too_large() {
+    let data: Vec<u8> = vec![b' '; MAX_DECOMPRESSED_SIZE];
+    assert_eq!(decompress_brotli(&compress_brotli(&data).unwrap()).unwrap(), data);
+
+    let data: Vec<u8> = vec![b' '; MAX_DECOMPRESSED_SIZE + 1];
+    assert!(matches!(
+      decompress_brotli(&compress_brotli(data).unwrap()).unwrap_err(),
+      Error::StateMetadataEncodingError(_, None)
+    ));
+  }
+
+  #[test]
+  fn test_decompress_invalid() {
+    assert!(decompress_brotli(&[0xff; 16]).is_err());
+  }
+}
@@ -15,6 +15,8 @@ use crate::IotaDID;
 use crate::IotaDocument;
 use crate::IotaDocumentMetadata;
 
+use super::compression_brotli::compress_brotli;
+use super::compression_brotli::decompress_brotli;
 use super::StateMetadataEncoding;
 use super::StateMetadataVersion;
 
@@ -78,12 +80,27 @@ impl StateMetadataDocument {
     // Unset Governor and State Controller Addresses to avoid bloating the payload
     self.metadata.governor_address = None;
     self.metadata.state_controller_address = None;
+    // Version identifiers are derived from the Alias Output history on resolution.
+    self.metadata.version_id = None;
+    self.metadata.next_version_id = None;
     *self.document.controller_mut() = None;
 
     let encoded_message_data: Vec<u8> = match encoding {
       StateMetadataEncoding::Json => self
         .to_json_vec()
         .map_err(|err| Error::SerializationError("failed to serialize document to JSON", Some(err)))?,
+      StateMetadataEncoding::JsonBrotli => compress_brotli(
+        self
+          .to_json_vec()
+          .map_err(|err| Error::SerializationError("failed to serialize document to JSON", Some(err)))?,
+      )?,
+      StateMetadataEncoding::Cbor => {
+        let mut buffer: Vec<u8> = Vec::new();
+        ciborium::ser::into_writer(&self, &mut buffer).map_err(|err| {
+          Error::StateMetadataEncodingError("failed to serialize document to CBOR", Some(Box::new(err)))
+        })?;
+        buffer
+      }
     };
 
     // Prepend flags and length.
@@ -160,6 +177,20 @@ impl StateMetadataDocument {
           Some(err),
         )
       }),
+      StateMetadataEncoding::JsonBrotli => {
+        StateMetadataDocument::from_json_slice(&decompress_brotli(data)?).map_err(|err| {
+          Error::SerializationError(
+            "state metadata decoding: failed to deserialize JSON document",
+            Some(err),
+          )
+        })
+      }
+      StateMetadataEncoding::Cbor => ciborium::de::from_reader(data).map_err(|err| {
+        Error::StateMetadataEncodingError(
+          "state metadata decoding: failed to deserialize CBOR document",
+          Some(Box::new(err)),
+        )
+      }),
     }
   }
 }
@@ -416,6 +447,114 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_packing_roundtrip_compact_encodings() {
+    let TestSetup { document, did_self, .. } = test_document();
+
+    for encoding in [StateMetadataEncoding::JsonBrotli, StateMetadataEncoding::Cbor] {
+      let packed: Vec<u8> = document.clone().pack_with_encoding(encoding).unwrap();
+      assert_eq!(&packed[0..3], DID_MARKER);
+      assert_eq!(packed[4], encoding as u8);
+
+      let unpacked: IotaDocument = StateMetadataDocument::unpack(&packed)
+        .unwrap()
+        .into_iota_document(&did_self)
+        .unwrap();
+      // The controller is unset when packing.
+      assert!(unpacked.controller().next().is_none());
+      assert_eq!(unpacked.metadata, document.metadata);
+      assert_eq!(unpacked.also_known_as(), document.also_known_as());
+      assert_eq!(
+        unpacked.core_document().verification_method(),
+        document.core_document().verification_method()
+      );
+      assert_eq!(
+        unpacked.core_document().authentication(),
+        document.core_document().authentication()
+      );
+      assert_eq!(unpacked.service(), document.service());
+      assert_eq!(unpacked.properties(), document.properties());
+    }
+  }
+
+  #[test]
+  fn test_unpack_invalid_compact_encodings() {
+    let TestSetup { document, .. } = test_document();
+
+    for encoding in [StateMetadataEncoding::JsonBrotli, StateMetadataEncoding::Cbor] {
+      let mut packed: Vec<u8> = document.clone().pack_with_encoding(encoding).unwrap();
+      // Truncate the payload and fix up the length prefix.
+      packed.truncate(packed.len() - 8);
+      let length: [u8; 2] = ((packed.len() - 7) as u16).to_le_bytes();
+      packed[5] = length[0];
+      packed[6] = length[1];
+      assert!(StateMetadataDocument::unpack(&packed).is_err());
+    }
+
+    // Plain JSON flagged as Brotli.
+    let mut packed: Vec<u8> = document.pack_with_encoding(StateMetadataEncoding::Json).unwrap();
+    packed[4] = StateMetadataEncoding::JsonBrotli as u8;
+    assert!(StateMetadataDocument::unpack(&packed).is_err());
+
+    // Unknown encoding.
+    packed[4] = u8::MAX;
+    assert!(StateMetadataDocument::unpack(&packed).is_err());
+  }
+
+  #[test]
+  fn test_compact_encoding_size() {
+    let TestSetup {
+      mut document, did_self, ..
+    } = test_document();
+
+    let sizes = |document: &IotaDocument| -> [usize; 3] {
+      [
+        StateMetadataEncoding::Json,
+        StateMetadataEncoding::JsonBrotli,
+        StateMetadataEncoding::Cbor,
+      ]
+      .map(|encoding| document.clone().pack_with_encoding(encoding).unwrap().len())
+    };
+
+    let [json, brotli, cbor] = sizes(&document);
+    assert!(brotli < json, "brotli: {brotli}, json: {json}");
+    assert!(cbor < json, "cbor: {cbor}, json: {json}");
+
+    // Documents with many methods and services compress better, since identifiers repeat.
+    for index in 0..8 {
+      let keypair: KeyPair = KeyPair::new(KeyType::Ed25519).unwrap();
+      document
+        .insert_method(
+          VerificationMethod::new(
+            did_self.clone(),
+            keypair.type_(),
+            keypair.public(),
+            &format!("key-{index}"),
+          )
+          .unwrap(),
+          MethodScope::VerificationMethod,
+        )
+        .unwrap();
+      assert!(document
+        .insert_service(
+          Service::builder(Object::new())
+            .id(did_self.to_url().join(format!("#linked-domain-{index}")).unwrap())
+            .type_("LinkedDomains")
+            .service_endpoint(Url::parse(format!("https://domain-{index}.example.com")).unwrap())
+            .build()
+            .unwrap()
+        )
+        .is_ok());
+    }
+
+    let [json_large, brotli_large, cbor_large] = sizes(&document);
+    assert!(cbor_large < json_large, "cbor: {cbor_large}, json: {json_large}");
+    assert!(
+      brotli_large * 3 < json_large * 2,
+      "brotli: {brotli_large}, json: {json_large}"
+    );
+  }
+
   #[test]
   fn test_pack_format() {
     // Changing the serialization is a breaking change!
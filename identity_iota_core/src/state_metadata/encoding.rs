@@ -6,10 +6,18 @@ use num_traits::FromPrimitive;
 use crate::Error;
 
 /// Indicates the encoding of a DID document in state metadata.
+///
+/// The compact encodings reduce the size of the state metadata, and hence the storage deposit required by the Alias
+/// Output containing the document.
 #[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, num_derive::FromPrimitive)]
 pub enum StateMetadataEncoding {
+  /// Plain JSON.
   #[default]
   Json = 0,
+  /// JSON compressed with Brotli.
+  JsonBrotli = 1,
+  /// CBOR, see [RFC 8949](https://www.rfc-editor.org/rfc/rfc8949).
+  Cbor = 2,
 }
 
 impl TryFrom<u8> for StateMetadataEncoding {
@@ -33,4 +33,8 @@ pub enum Error {
   SignatureCreationError(#[source] Box<dyn std::error::Error + Send + Sync>),
   #[error("signature verification error")]
   SignatureVerificationError(#[source] Box<dyn std::error::Error + Send + Sync>),
+  #[error("encryption error")]
+  EncryptionError(#[source] Box<dyn std::error::Error + Send + Sync>),
+  #[error("decryption error")]
+  DecryptionError(#[source] Box<dyn std::error::Error + Send + Sync>),
 }
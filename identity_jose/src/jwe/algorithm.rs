@@ -0,0 +1,75 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Supported algorithms for the JSON Web Encryption `alg` claim.
+///
+/// [More Info](https://www.iana.org/assignments/jose/jose.xhtml#web-signature-encryption-algorithms)
+///
+/// [ECDH-1PU (draft)](https://datatracker.ietf.org/doc/html/draft-madden-jose-ecdh-1pu-04)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize)]
+#[allow(non_camel_case_types)]
+pub enum JweAlgorithm {
+  /// Elliptic Curve Diffie-Hellman Ephemeral Static key agreement using Concat KDF
+  #[serde(rename = "ECDH-ES")]
+  ECDH_ES,
+  /// ECDH-ES using Concat KDF and CEK wrapped with "A256KW"
+  #[serde(rename = "ECDH-ES+A256KW")]
+  ECDH_ES_A256KW,
+  /// ECDH One-Pass Unified Model using one-pass KDF
+  #[serde(rename = "ECDH-1PU")]
+  ECDH_1PU,
+}
+
+impl JweAlgorithm {
+  pub const ALL: &'static [Self] = &[Self::ECDH_ES, Self::ECDH_ES_A256KW, Self::ECDH_1PU];
+
+  /// Returns the JWE algorithm as a `str` slice.
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::ECDH_ES => "ECDH-ES",
+      Self::ECDH_ES_A256KW => "ECDH-ES+A256KW",
+      Self::ECDH_1PU => "ECDH-1PU",
+    }
+  }
+
+  /// Returns `true` if the algorithm derives the content encryption key directly
+  /// from the key agreement rather than wrapping a separately generated key.
+  ///
+  /// Direct key agreement yields an empty JWE Encrypted Key and cannot be used
+  /// with more than one recipient.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7518#section-4.6)
+  pub const fn is_direct_key_agreement(self) -> bool {
+    match self {
+      Self::ECDH_ES => true,
+      Self::ECDH_ES_A256KW => false,
+      Self::ECDH_1PU => true,
+    }
+  }
+}
+
+impl FromStr for JweAlgorithm {
+  type Err = crate::error::Error;
+
+  fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+    match string {
+      "ECDH-ES" => Ok(Self::ECDH_ES),
+      "ECDH-ES+A256KW" => Ok(Self::ECDH_ES_A256KW),
+      "ECDH-1PU" => Ok(Self::ECDH_1PU),
+      _ => Err(Error::UnsupportedAlgorithm),
+    }
+  }
+}
+
+impl Display for JweAlgorithm {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    f.write_str(self.name())
+  }
+}
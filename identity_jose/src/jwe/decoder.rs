@@ -0,0 +1,355 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::create_aad;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweFormat;
+use crate::jwe::JweHeader;
+use crate::jwt::JwtHeaderSet;
+use crate::jwu::check_slice_param;
+use crate::jwu::decode_b64;
+use crate::jwu::decode_b64_json;
+use crate::jwu::filter_non_empty_bytes;
+use crate::jwu::parse_utf8;
+use crate::jwu::validate_jwe_headers;
+
+type HeaderSet<'a> = JwtHeaderSet<'a, JweHeader>;
+
+/// The protected JWE header.
+pub type DecoderProtectedHeader<'a> = &'a JweHeader;
+/// The shared unprotected JWE header.
+pub type DecoderUnprotectedHeader<'a> = &'a JweHeader;
+/// The per-recipient unprotected JWE header.
+pub type DecoderRecipientHeader<'a> = &'a JweHeader;
+/// The encrypted content encryption key as a slice.
+pub type DecoderEncryptedKey<'a> = &'a [u8];
+
+const COMPACT_SEGMENTS: usize = 5;
+
+/// A decrypted JWE.
+///
+/// Contains the decoded headers of the recipient that was able to decrypt the content,
+/// the additional authenticated data and the plaintext.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+  pub protected: Option<JweHeader>,
+  pub unprotected: Option<JweHeader>,
+  pub header: Option<JweHeader>,
+  pub aad: Option<Vec<u8>>,
+  pub plaintext: Vec<u8>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JweRecipient<'a> {
+  header: Option<JweHeader>,
+  encrypted_key: Option<&'a str>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct General<'a> {
+  protected: Option<&'a str>,
+  unprotected: Option<JweHeader>,
+  #[serde(borrow)]
+  recipients: Vec<JweRecipient<'a>>,
+  aad: Option<&'a str>,
+  iv: &'a str,
+  ciphertext: &'a str,
+  tag: &'a str,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Flatten<'a> {
+  protected: Option<&'a str>,
+  unprotected: Option<JweHeader>,
+  header: Option<JweHeader>,
+  encrypted_key: Option<&'a str>,
+  aad: Option<&'a str>,
+  iv: &'a str,
+  ciphertext: &'a str,
+  tag: &'a str,
+}
+
+/// The segments of a JWE common to all serialization formats.
+struct Expanded<'a> {
+  protected: Option<&'a str>,
+  unprotected: Option<JweHeader>,
+  recipients: Vec<JweRecipient<'a>>,
+  aad: Option<&'a str>,
+  iv: &'a str,
+  ciphertext: &'a str,
+  tag: &'a str,
+}
+
+// =============================================================================
+// =============================================================================
+
+/// The [`Decoder`] allows decoding a raw JWE into a [`Token`], verifying
+/// the structure of the JWE and decrypting its content.
+///
+/// When attempting to decode a raw JWE, the decoder will check for the expected format, algorithms
+/// and crits which can be set via their respective setters.
+///
+/// This API does not have any cryptography built-in. Rather, the content encryption key is
+/// recovered and the content is decrypted through closures that are passed to the
+/// [`decode`](Decoder::decode) method, so that users can implement the algorithms of their choice.
+pub struct Decoder {
+  /// The expected format of the encoded token.
+  format: JweFormat,
+  /// A list of permitted key management algorithms.
+  algs: Option<Vec<JweAlgorithm>>,
+  /// A list of permitted content encryption algorithms.
+  encs: Option<Vec<JweEncryption>>,
+  /// A list of permitted extension parameters.
+  crits: Option<Vec<String>>,
+}
+
+impl Decoder {
+  pub fn new() -> Self {
+    Self {
+      format: JweFormat::Compact,
+      algs: None,
+      encs: None,
+      crits: None,
+    }
+  }
+
+  pub fn format(mut self, value: JweFormat) -> Self {
+    self.format = value;
+    self
+  }
+
+  pub fn algorithm(mut self, value: JweAlgorithm) -> Self {
+    self.algs.get_or_insert_with(Vec::new).push(value);
+    self
+  }
+
+  pub fn encryption(mut self, value: JweEncryption) -> Self {
+    self.encs.get_or_insert_with(Vec::new).push(value);
+    self
+  }
+
+  pub fn critical(mut self, value: impl Into<String>) -> Self {
+    self.crits.get_or_insert_with(Vec::new).push(value.into());
+    self
+  }
+
+  /// Decode the given `data` which is a base64url-encoded JWE.
+  ///
+  /// The `key_fn` closure is called for every recipient of the JWE with the protected, shared unprotected
+  /// and per-recipient headers as well as the encrypted key, which is `None` for direct key agreement.
+  /// It must return the content encryption key or an error if the recipient's key is not available or the
+  /// `alg` parameter describes a key management algorithm that it cannot handle.
+  ///
+  /// The `decrypt_fn` closure is called with the content encryption algorithm, the content encryption key,
+  /// the initialization vector, the additional authenticated data, the ciphertext and the authentication tag,
+  /// and must return the plaintext or an error if decryption fails.
+  ///
+  /// Only one recipient is required to decrypt the content successfully. If no recipient succeeds,
+  /// the error of the last attempted recipient is returned.
+  pub fn decode<KFUN, DFUN, ERR>(&self, key_fn: &KFUN, decrypt_fn: &DFUN, data: &[u8]) -> Result<Token>
+  where
+    KFUN: Fn(
+      Option<DecoderProtectedHeader<'_>>,
+      Option<DecoderUnprotectedHeader<'_>>,
+      Option<DecoderRecipientHeader<'_>>,
+      Option<DecoderEncryptedKey<'_>>,
+    ) -> std::result::Result<Vec<u8>, ERR>,
+    DFUN: Fn(JweEncryption, &[u8], &[u8], &[u8], &[u8], &[u8]) -> std::result::Result<Vec<u8>, ERR>,
+    ERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    let expanded: Expanded<'_> = self.expand(data)?;
+
+    let protected: Option<JweHeader> = expanded.protected.map(decode_b64_json).transpose()?;
+    let unprotected: Option<JweHeader> = expanded.unprotected;
+
+    let enc: JweEncryption = HeaderSet::new()
+      .protected(protected.as_ref())
+      .unprotected(unprotected.as_ref())
+      .try_enc()?;
+
+    check_slice_param("enc", self.encs.as_deref(), &enc)?;
+
+    let aad: Vec<u8> = create_aad(expanded.protected, expanded.aad);
+    let iv: Vec<u8> = decode_b64(expanded.iv)?;
+    let ciphertext: Vec<u8> = decode_b64(expanded.ciphertext)?;
+    let tag: Vec<u8> = decode_b64(expanded.tag)?;
+
+    let recipients: usize = expanded.recipients.len();
+    let mut last_error: Option<Error> = None;
+
+    for recipient in expanded.recipients {
+      let result: Result<Vec<u8>> = self.decode_one(
+        key_fn,
+        decrypt_fn,
+        enc,
+        protected.as_ref(),
+        unprotected.as_ref(),
+        &recipient,
+        recipients,
+        &aad,
+        &iv,
+        &ciphertext,
+        &tag,
+      );
+
+      match result {
+        Ok(plaintext) => {
+          return Ok(Token {
+            protected,
+            unprotected,
+            header: recipient.header,
+            aad: expanded.aad.map(decode_b64).transpose()?,
+            plaintext,
+          });
+        }
+        Err(err) => last_error = Some(err),
+      }
+    }
+
+    // Surface the reason why the last recipient could not decrypt the content, if there was any recipient.
+    Err(last_error.unwrap_or(Error::InvalidContent("recipient not found")))
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn decode_one<KFUN, DFUN, ERR>(
+    &self,
+    key_fn: &KFUN,
+    decrypt_fn: &DFUN,
+    enc: JweEncryption,
+    protected: Option<&JweHeader>,
+    unprotected: Option<&JweHeader>,
+    recipient: &JweRecipient<'_>,
+    recipients: usize,
+    aad: &[u8],
+    iv: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8],
+  ) -> Result<Vec<u8>>
+  where
+    KFUN: Fn(
+      Option<DecoderProtectedHeader<'_>>,
+      Option<DecoderUnprotectedHeader<'_>>,
+      Option<DecoderRecipientHeader<'_>>,
+      Option<DecoderEncryptedKey<'_>>,
+    ) -> std::result::Result<Vec<u8>, ERR>,
+    DFUN: Fn(JweEncryption, &[u8], &[u8], &[u8], &[u8], &[u8]) -> std::result::Result<Vec<u8>, ERR>,
+    ERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    validate_jwe_headers(protected, unprotected, recipient.header.as_ref(), self.crits.as_deref())?;
+
+    // The "enc" parameter MUST be the same for all recipients.
+    if recipient.header.as_ref().and_then(JweHeader::enc).is_some() {
+      return Err(Error::InvalidParam("per-recipient `enc` parameter"));
+    }
+
+    let alg: JweAlgorithm = HeaderSet::new()
+      .protected(protected)
+      .unprotected(unprotected)
+      .header(recipient.header.as_ref())
+      .try_alg()?;
+
+    check_slice_param("alg", self.algs.as_deref(), &alg)?;
+
+    let encrypted_key: Option<Vec<u8>> = filter_non_empty_bytes(recipient.encrypted_key)
+      .map(decode_b64)
+      .transpose()?;
+
+    match (alg.is_direct_key_agreement(), &encrypted_key) {
+      (true, _) if recipients > 1 => {
+        return Err(Error::InvalidContent(
+          "direct key agreement doesn't support multiple recipients",
+        ))
+      }
+      (true, Some(_)) => {
+        return Err(Error::InvalidContent(
+          "unexpected encrypted key for direct key agreement",
+        ))
+      }
+      (false, None) => return Err(Error::InvalidContent("missing encrypted key")),
+      _ => {}
+    }
+
+    let cek: Zeroizing<Vec<u8>> = key_fn(
+      protected,
+      unprotected,
+      recipient.header.as_ref(),
+      encrypted_key.as_deref(),
+    )
+    .map(Zeroizing::new)
+    .map_err(|err| Error::DecryptionError(err.into()))?;
+
+    if cek.len() != enc.key_len() {
+      return Err(Error::InvalidContent("invalid content encryption key length"));
+    }
+
+    decrypt_fn(enc, &cek, iv, aad, ciphertext, tag).map_err(|err| Error::DecryptionError(err.into()))
+  }
+
+  fn expand<'a>(&self, data: &'a [u8]) -> Result<Expanded<'a>> {
+    match self.format {
+      JweFormat::Compact => {
+        let split: Vec<&[u8]> = data.split(|byte| *byte == b'.').collect();
+
+        if split.len() != COMPACT_SEGMENTS {
+          return Err(Error::InvalidContent("invalid segments count"));
+        }
+
+        Ok(Expanded {
+          protected: Some(parse_utf8(split[0])?),
+          unprotected: None,
+          recipients: vec![JweRecipient {
+            header: None,
+            encrypted_key: Some(parse_utf8(split[1])?),
+          }],
+          aad: None,
+          iv: parse_utf8(split[2])?,
+          ciphertext: parse_utf8(split[3])?,
+          tag: parse_utf8(split[4])?,
+        })
+      }
+      JweFormat::General => {
+        let data: General<'_> = serde_json::from_slice(data).map_err(Error::InvalidJson)?;
+
+        Ok(Expanded {
+          protected: data.protected,
+          unprotected: data.unprotected,
+          recipients: data.recipients,
+          aad: data.aad,
+          iv: data.iv,
+          ciphertext: data.ciphertext,
+          tag: data.tag,
+        })
+      }
+      JweFormat::Flatten => {
+        let data: Flatten<'_> = serde_json::from_slice(data).map_err(Error::InvalidJson)?;
+
+        Ok(Expanded {
+          protected: data.protected,
+          unprotected: data.unprotected,
+          recipients: vec![JweRecipient {
+            header: data.header,
+            encrypted_key: data.encrypted_key,
+          }],
+          aad: data.aad,
+          iv: data.iv,
+          ciphertext: data.ciphertext,
+          tag: data.tag,
+        })
+      }
+    }
+  }
+}
+
+impl Default for Decoder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,408 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+use std::future::Future;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweFormat;
+use crate::jwe::JweHeader;
+use crate::jwe::Recipient;
+use crate::jwk::Jwk;
+use crate::jwt::JwtHeaderSet;
+use crate::jwu;
+
+type HeaderSet<'a> = JwtHeaderSet<'a, JweHeader>;
+
+/// The protected JWE header.
+pub type EncoderProtectedHeader = JweHeader;
+/// The shared unprotected JWE header.
+pub type EncoderUnprotectedHeader = JweHeader;
+/// The per-recipient unprotected JWE header.
+pub type EncoderRecipientHeader = JweHeader;
+/// The content encryption key established for previous recipients, if any.
+pub type EncoderCek = Option<Vec<u8>>;
+/// The content encryption key as a byte vector.
+pub type EncoderContentKey = Vec<u8>;
+/// The additional authenticated data as a byte vector.
+pub type EncoderAad = Vec<u8>;
+/// The plaintext to encrypt as a byte vector.
+pub type EncoderPlaintext = Vec<u8>;
+
+macro_rules! to_json {
+  ($data:expr) => {{
+    ::serde_json::to_string(&$data).map_err(Error::InvalidJson)
+  }};
+}
+
+/// The result of the key management step for a single recipient.
+pub struct EncoderKey {
+  /// The content encryption key.
+  ///
+  /// Either derived from the key agreement or, when the key is wrapped, generated
+  /// by the first recipient and passed on to all subsequent recipients.
+  pub cek: Vec<u8>,
+  /// The encrypted content encryption key, or `None` when using direct key agreement.
+  pub encrypted_key: Option<Vec<u8>>,
+  /// The ephemeral public key of the key agreement, if any.
+  pub epk: Option<Jwk>,
+}
+
+/// The result of encrypting the plaintext.
+pub struct EncoderContent {
+  /// The initialization vector.
+  pub iv: Vec<u8>,
+  /// The ciphertext.
+  pub ciphertext: Vec<u8>,
+  /// The authentication tag.
+  pub tag: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct JweRecipient {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  header: Option<JweHeader>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  encrypted_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct General<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  protected: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  unprotected: Option<&'a JweHeader>,
+  recipients: Vec<JweRecipient>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  aad: Option<String>,
+  iv: String,
+  ciphertext: String,
+  tag: String,
+}
+
+#[derive(Serialize)]
+struct Flatten<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  protected: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  unprotected: Option<&'a JweHeader>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  header: Option<JweHeader>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  encrypted_key: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  aad: Option<String>,
+  iv: String,
+  ciphertext: String,
+  tag: String,
+}
+
+// =============================================================================
+// =============================================================================
+
+/// The [`Encoder`] allows encrypting an arbitrary plaintext into a JWE.
+///
+/// When encoding, the format, headers, additional authenticated data and recipients
+/// of the resulting JWE can be set in builder-style.
+///
+/// This API does not have any cryptography built-in. Rather, key management and content
+/// encryption are performed by closures that are passed to the [`encode`](Encoder::encode)
+/// method, so that users can implement the algorithms of their choice.
+///
+/// To use a particular key for a recipient, it is recommended to set the `kid` parameter
+/// in their header. Based on that, the key management closure can then choose the appropriate
+/// key for the recipient.
+pub struct Encoder<'a> {
+  /// The output format of the encoded token.
+  format: JweFormat,
+  /// The integrity-protected JOSE header shared by all recipients.
+  protected: Option<&'a JweHeader>,
+  /// The non integrity-protected JOSE header shared by all recipients.
+  unprotected: Option<&'a JweHeader>,
+  /// Additional authenticated data, only supported by the JSON serializations.
+  aad: Option<&'a [u8]>,
+  /// Per-recipient configuration.
+  recipients: Vec<Recipient<'a>>,
+}
+
+impl<'a> Encoder<'a> {
+  pub fn new() -> Self {
+    Self {
+      format: JweFormat::Compact,
+      protected: None,
+      unprotected: None,
+      aad: None,
+      recipients: Vec::new(),
+    }
+  }
+
+  pub fn format(mut self, value: JweFormat) -> Self {
+    self.format = value;
+    self
+  }
+
+  pub fn protected(mut self, value: &'a JweHeader) -> Self {
+    self.protected = Some(value);
+    self
+  }
+
+  pub fn unprotected(mut self, value: &'a JweHeader) -> Self {
+    self.unprotected = Some(value);
+    self
+  }
+
+  pub fn aad(mut self, value: &'a [u8]) -> Self {
+    self.aad = Some(value);
+    self
+  }
+
+  pub fn recipient(mut self, recipient: Recipient<'a>) -> Self {
+    self.recipients.push(recipient);
+    self
+  }
+
+  /// Encrypt the given `plaintext` into a JWE.
+  ///
+  /// The `key_fn` closure is called once for every recipient, in the order they were added.
+  /// It is passed the shared protected and unprotected headers as well as the recipient's header,
+  /// which can be merged with [`JwtHeaderSet`]. The header parameters of particular interest are
+  /// `alg`, `enc`, `kid`, `apu` and `apv`. The last argument is the content encryption key established
+  /// for the previous recipients, which is `None` for the first recipient. The closure returns the
+  /// content encryption key of that recipient together with the encrypted key and the ephemeral public
+  /// key, if any. When the key is wrapped, the first recipient is expected to generate a random key of
+  /// [`JweEncryption::key_len`] bytes and all subsequent recipients must wrap the key they are given.
+  ///
+  /// The `encrypt_fn` closure is called once with the content encryption algorithm, the content
+  /// encryption key, the additional authenticated data and the plaintext. It is expected to generate
+  /// a fresh initialization vector and return it together with the ciphertext and authentication tag.
+  pub async fn encode<KFUN, KFUT, EFUN, EFUT, ERR>(
+    &self,
+    key_fn: &KFUN,
+    encrypt_fn: &EFUN,
+    plaintext: &[u8],
+  ) -> Result<String>
+  where
+    KFUN: Fn(
+      Option<EncoderProtectedHeader>,
+      Option<EncoderUnprotectedHeader>,
+      Option<EncoderRecipientHeader>,
+      EncoderCek,
+    ) -> KFUT,
+    KFUT: Future<Output = std::result::Result<EncoderKey, ERR>>,
+    EFUN: Fn(JweEncryption, EncoderContentKey, EncoderAad, EncoderPlaintext) -> EFUT,
+    EFUT: Future<Output = std::result::Result<EncoderContent, ERR>>,
+    ERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    if self.recipients.is_empty() {
+      return Err(Error::EncryptionError("Missing Recipients".into()));
+    }
+
+    let enc: JweEncryption = self.validate()?;
+
+    let mut cek: Option<Zeroizing<Vec<u8>>> = None;
+    let mut protected: Option<JweHeader> = self.protected.cloned();
+    let mut encoded: Vec<JweRecipient> = Vec::with_capacity(self.recipients.len());
+
+    for recipient in self.recipients.iter().copied() {
+      let alg: JweAlgorithm = HeaderSet::new()
+        .protected(self.protected)
+        .unprotected(self.unprotected)
+        .header(recipient.header)
+        .try_alg()?;
+
+      let key: EncoderKey = (key_fn)(
+        self.protected.cloned(),
+        self.unprotected.cloned(),
+        recipient.header.cloned(),
+        cek.as_deref().cloned(),
+      )
+      .await
+      .map_err(|err| Error::EncryptionError(err.into()))?;
+
+      let EncoderKey {
+        cek: recipient_cek,
+        encrypted_key,
+        epk,
+      } = key;
+      let recipient_cek: Zeroizing<Vec<u8>> = Zeroizing::new(recipient_cek);
+
+      if recipient_cek.len() != enc.key_len() {
+        return Err(Error::InvalidContent("invalid content encryption key length"));
+      }
+
+      match &cek {
+        Some(current) if *current != recipient_cek => {
+          return Err(Error::InvalidContent(
+            "recipients must share the same content encryption key",
+          ));
+        }
+        Some(_) => {}
+        None => cek = Some(recipient_cek),
+      }
+
+      match (alg.is_direct_key_agreement(), &encrypted_key) {
+        (true, Some(_)) => {
+          return Err(Error::InvalidContent(
+            "unexpected encrypted key for direct key agreement",
+          ))
+        }
+        (false, None) => return Err(Error::InvalidContent("missing encrypted key")),
+        _ => {}
+      }
+
+      // The ephemeral public key is added to the protected header for the compact
+      // serialization, and to the per-recipient header otherwise.
+      let mut header: Option<JweHeader> = recipient.header.cloned();
+      if let Some(epk) = epk {
+        if self.format == JweFormat::Compact {
+          protected.get_or_insert_with(JweHeader::new).set_epk(epk);
+        } else {
+          header.get_or_insert_with(JweHeader::new).set_epk(epk);
+        }
+      }
+
+      encoded.push(JweRecipient {
+        header,
+        encrypted_key: encrypted_key.map(jwu::encode_b64),
+      });
+    }
+    debug_assert_eq!(encoded.len(), self.recipients.len());
+
+    // SAFETY: Recipients are non-empty, so a content encryption key has been set.
+    let cek: Zeroizing<Vec<u8>> = cek.expect("content encryption key must be set");
+
+    let protected: Option<String> = protected.as_ref().map(jwu::encode_b64_json).transpose()?;
+    let aad: Option<String> = self.aad.map(jwu::encode_b64);
+    let aad_bytes: Vec<u8> = create_aad(protected.as_deref(), aad.as_deref());
+
+    let content: EncoderContent = (encrypt_fn)(enc, cek.to_vec(), aad_bytes, plaintext.to_vec())
+      .await
+      .map_err(|err| Error::EncryptionError(err.into()))?;
+
+    if content.iv.len() != enc.iv_len() {
+      return Err(Error::InvalidContent("invalid initialization vector length"));
+    }
+
+    if content.tag.len() != enc.tag_len() {
+      return Err(Error::InvalidContent("invalid authentication tag length"));
+    }
+
+    let iv: String = jwu::encode_b64(content.iv);
+    let ciphertext: String = jwu::encode_b64(content.ciphertext);
+    let tag: String = jwu::encode_b64(content.tag);
+
+    match self.format {
+      JweFormat::Compact => {
+        let protected: &str = protected.as_deref().unwrap_or_default();
+        let encrypted_key: &str = encoded[0].encrypted_key.as_deref().unwrap_or_default();
+
+        Ok(format!("{protected}.{encrypted_key}.{iv}.{ciphertext}.{tag}"))
+      }
+      JweFormat::General => {
+        to_json!(General {
+          protected: protected.as_deref(),
+          unprotected: self.unprotected,
+          recipients: encoded,
+          aad,
+          iv,
+          ciphertext,
+          tag,
+        })
+      }
+      JweFormat::Flatten => {
+        let recipient: JweRecipient = encoded.remove(0);
+
+        to_json!(Flatten {
+          protected: protected.as_deref(),
+          unprotected: self.unprotected,
+          header: recipient.header,
+          encrypted_key: recipient.encrypted_key,
+          aad,
+          iv,
+          ciphertext,
+          tag,
+        })
+      }
+    }
+  }
+
+  /// Validates the encoder configuration and returns the content encryption algorithm.
+  fn validate(&self) -> Result<JweEncryption> {
+    match (self.format, &*self.recipients) {
+      (JweFormat::Compact, &[Recipient { header: None }]) if self.unprotected.is_none() && self.aad.is_none() => {}
+      (JweFormat::Compact, _) => {
+        return Err(Error::EncryptionError(
+          "JWE Compact Serialization doesn't support multiple recipients, unprotected headers or aad".into(),
+        ));
+      }
+      (JweFormat::Flatten, &[_]) | (JweFormat::General, _) => {}
+      (JweFormat::Flatten, _) => {
+        return Err(Error::EncryptionError(
+          "JWE Flattened Serialization doesn't support multiple recipients".into(),
+        ));
+      }
+    }
+
+    // The "enc" parameter MUST be the same for all recipients.
+    if self
+      .recipients
+      .iter()
+      .any(|recipient| recipient.header.and_then(JweHeader::enc).is_some())
+    {
+      return Err(Error::InvalidParam("per-recipient `enc` parameter"));
+    }
+
+    let enc: JweEncryption = HeaderSet::new()
+      .protected(self.protected)
+      .unprotected(self.unprotected)
+      .try_enc()?;
+
+    for recipient in &self.recipients {
+      jwu::validate_jwe_headers(
+        self.protected,
+        self.unprotected,
+        recipient.header,
+        self.protected.and_then(|header| header.crit()),
+      )?;
+
+      let alg: JweAlgorithm = HeaderSet::new()
+        .protected(self.protected)
+        .unprotected(self.unprotected)
+        .header(recipient.header)
+        .try_alg()?;
+
+      if alg.is_direct_key_agreement() && self.recipients.len() > 1 {
+        return Err(Error::EncryptionError(
+          "direct key agreement doesn't support multiple recipients".into(),
+        ));
+      }
+    }
+
+    Ok(enc)
+  }
+}
+
+impl<'a> Default for Encoder<'a> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// =============================================================================
+// =============================================================================
+
+/// Creates the additional authenticated data of the content encryption.
+///
+/// See: https://tools.ietf.org/html/rfc7516#section-5.1
+pub(crate) fn create_aad(protected: Option<&str>, aad: Option<&str>) -> Vec<u8> {
+  let protected: &[u8] = protected.map(str::as_bytes).unwrap_or_default();
+
+  match aad {
+    Some(aad) => jwu::create_message(protected, aad.as_bytes()),
+    None => protected.to_vec(),
+  }
+}
@@ -0,0 +1,76 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Supported algorithms for the JSON Web Encryption `enc` claim.
+///
+/// [More Info](https://www.iana.org/assignments/jose/jose.xhtml#web-signature-encryption-algorithms)
+///
+/// [XChaCha20-Poly1305 (draft)](https://datatracker.ietf.org/doc/html/draft-amringer-jose-chacha-02)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize)]
+pub enum JweEncryption {
+  /// AES GCM using 256-bit key.
+  A256GCM,
+  /// XChaCha20-Poly1305.
+  XC20P,
+}
+
+impl JweEncryption {
+  pub const ALL: &'static [Self] = &[Self::A256GCM, Self::XC20P];
+
+  /// Returns the JWE "enc" claim as a `str` slice.
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::A256GCM => "A256GCM",
+      Self::XC20P => "XC20P",
+    }
+  }
+
+  /// Returns the length of the content encryption key in bytes.
+  pub const fn key_len(self) -> usize {
+    match self {
+      Self::A256GCM => 32,
+      Self::XC20P => 32,
+    }
+  }
+
+  /// Returns the length of the initialization vector in bytes.
+  pub const fn iv_len(self) -> usize {
+    match self {
+      Self::A256GCM => 12,
+      Self::XC20P => 24,
+    }
+  }
+
+  /// Returns the length of the authentication tag in bytes.
+  pub const fn tag_len(self) -> usize {
+    match self {
+      Self::A256GCM => 16,
+      Self::XC20P => 16,
+    }
+  }
+}
+
+impl FromStr for JweEncryption {
+  type Err = crate::error::Error;
+
+  fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+    match string {
+      "A256GCM" => Ok(Self::A256GCM),
+      "XC20P" => Ok(Self::XC20P),
+      _ => Err(Error::UnsupportedAlgorithm),
+    }
+  }
+}
+
+impl Display for JweEncryption {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    f.write_str(self.name())
+  }
+}
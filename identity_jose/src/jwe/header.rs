@@ -0,0 +1,228 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::ops::Deref;
+use core::ops::DerefMut;
+
+use crate::jose::JoseHeader;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwk::Jwk;
+use crate::jwt::JwtHeader;
+
+/// JSON Web Encryption JOSE Header.
+///
+/// [More Info](https://tools.ietf.org/html/rfc7516#section-4)
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct JweHeader {
+  /// Common JOSE Header Parameters.
+  #[serde(flatten)]
+  common: JwtHeader,
+  /// Algorithm.
+  ///
+  /// Identifies the cryptographic algorithm used to encrypt or determine the
+  /// value of the Content Encryption Key.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7516#section-4.1.1)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  alg: Option<JweAlgorithm>,
+  /// Encryption Algorithm.
+  ///
+  /// Identifies the content encryption algorithm used to perform
+  /// authenticated encryption on the plaintext to produce the ciphertext and
+  /// the Authentication Tag.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7516#section-4.1.2)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  enc: Option<JweEncryption>,
+  /// Ephemeral Public Key.
+  ///
+  /// Public key created by the originator for the use in key agreement
+  /// algorithms.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7518#section-4.6.1.1)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  epk: Option<Jwk>,
+  /// Agreement PartyUInfo.
+  ///
+  /// Value used for key derivation via Concat KDF.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7518#section-4.6.1.2)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  apu: Option<String>,
+  /// Agreement PartyVInfo.
+  ///
+  /// Value used for key derivation via Concat KDF.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7518#section-4.6.1.3)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  apv: Option<String>,
+  /// Sender Key ID.
+  ///
+  /// Identifies the static key of the sender used in `ECDH-1PU` key agreement.
+  ///
+  /// [More Info](https://datatracker.ietf.org/doc/html/draft-madden-jose-ecdh-1pu-04#section-2.2.1)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  skid: Option<String>,
+}
+
+impl JweHeader {
+  /// Create a new empty `JweHeader`.
+  pub const fn new() -> Self {
+    Self {
+      common: JwtHeader::new(),
+      alg: None,
+      enc: None,
+      epk: None,
+      apu: None,
+      apv: None,
+      skid: None,
+    }
+  }
+
+  /// Returns the value for the algorithm claim (alg).
+  pub fn alg(&self) -> Option<JweAlgorithm> {
+    self.alg
+  }
+
+  /// Sets a value for the algorithm claim (alg).
+  pub fn set_alg(&mut self, value: impl Into<JweAlgorithm>) {
+    self.alg = Some(value.into());
+  }
+
+  /// Returns the value of the encryption claim (enc).
+  pub fn enc(&self) -> Option<JweEncryption> {
+    self.enc
+  }
+
+  /// Sets a value for the encryption claim (enc).
+  pub fn set_enc(&mut self, value: impl Into<JweEncryption>) {
+    self.enc = Some(value.into());
+  }
+
+  /// Returns the value of the ephemeral public key claim (epk).
+  pub fn epk(&self) -> Option<&Jwk> {
+    self.epk.as_ref()
+  }
+
+  /// Sets a value for the ephemeral public key claim (epk).
+  pub fn set_epk(&mut self, value: impl Into<Jwk>) {
+    self.epk = Some(value.into());
+  }
+
+  /// Returns the value of the partyuinfo claim (apu).
+  pub fn apu(&self) -> Option<&str> {
+    self.apu.as_deref()
+  }
+
+  /// Sets a value for the partyuinfo claim (apu).
+  pub fn set_apu(&mut self, value: impl Into<String>) {
+    self.apu = Some(value.into());
+  }
+
+  /// Returns the value of the partyvinfo claim (apv).
+  pub fn apv(&self) -> Option<&str> {
+    self.apv.as_deref()
+  }
+
+  /// Sets a value for the partyvinfo claim (apv).
+  pub fn set_apv(&mut self, value: impl Into<String>) {
+    self.apv = Some(value.into());
+  }
+
+  /// Returns the value of the sender key ID claim (skid).
+  pub fn skid(&self) -> Option<&str> {
+    self.skid.as_deref()
+  }
+
+  /// Sets a value for the sender key ID claim (skid).
+  pub fn set_skid(&mut self, value: impl Into<String>) {
+    self.skid = Some(value.into());
+  }
+
+  // ===========================================================================
+  // ===========================================================================
+
+  pub fn has(&self, claim: &str) -> bool {
+    match claim {
+      "alg" => self.alg().is_some(),
+      "enc" => self.enc().is_some(),
+      "epk" => self.epk().is_some(),
+      "apu" => self.apu().is_some(),
+      "apv" => self.apv().is_some(),
+      "skid" => self.skid().is_some(),
+      _ => self.common.has(claim),
+    }
+  }
+
+  /// Returns `true` if none of the fields are set in both `self` and `other`.
+  pub fn is_disjoint(&self, other: &JweHeader) -> bool {
+    let has_duplicate: bool = self.alg.is_some() && other.alg.is_some()
+      || self.enc.is_some() && other.enc.is_some()
+      || self.epk.is_some() && other.epk.is_some()
+      || self.apu.is_some() && other.apu.is_some()
+      || self.apv.is_some() && other.apv.is_some()
+      || self.skid.is_some() && other.skid.is_some();
+
+    !has_duplicate && self.common.is_disjoint(other.common())
+  }
+}
+
+impl Deref for JweHeader {
+  type Target = JwtHeader;
+
+  fn deref(&self) -> &Self::Target {
+    &self.common
+  }
+}
+
+impl DerefMut for JweHeader {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.common
+  }
+}
+
+impl JoseHeader for JweHeader {
+  fn common(&self) -> &JwtHeader {
+    self
+  }
+
+  fn has_claim(&self, claim: &str) -> bool {
+    self.has(claim)
+  }
+}
+
+impl Default for JweHeader {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_header_disjoint() {
+    let header1: JweHeader = serde_json::from_value(serde_json::json!({
+      "enc": "A256GCM",
+      "apu": "QWxpY2U",
+    }))
+    .unwrap();
+    let header2: JweHeader = serde_json::from_value(serde_json::json!({
+      "alg": "ECDH-ES+A256KW",
+      "kid": "did:example:bob#key-1",
+    }))
+    .unwrap();
+    let header3: JweHeader = serde_json::from_value(serde_json::json!({
+      "alg": "ECDH-1PU",
+      "enc": "XC20P",
+    }))
+    .unwrap();
+
+    assert!(header1.is_disjoint(&header2));
+    assert!(!header1.is_disjoint(&header3));
+    assert!(!header2.is_disjoint(&header3));
+    assert!(header1.is_disjoint(&JweHeader::new()));
+  }
+}
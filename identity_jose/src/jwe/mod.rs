@@ -0,0 +1,29 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module features a high-level encoding/decoding API to create JSON Web Encryption ([JWE](https://tools.ietf.org/html/rfc7516)) tokens.
+//!
+//! Like the [JWS](crate::jws) API, the [`Encoder`] and [`Decoder`] do not have any cryptography built-in.
+//! Instead, they take care of the serialization formats, header validation and the JWE processing rules,
+//! while key management and content encryption are delegated to closures. This allows implementing the
+//! supported algorithms with any cryptographic backend, including keys that never leave a key storage.
+//!
+//! The key management closure is responsible for the key agreement (`ECDH-ES`, `ECDH-1PU`) and, if
+//! required, the key wrapping (`ECDH-ES+A256KW`) of the content encryption key for a single recipient.
+//! The content encryption closure performs the authenticated encryption (`A256GCM`, `XC20P`) of the plaintext.
+
+mod algorithm;
+mod decoder;
+mod encoder;
+mod encryption;
+mod format;
+mod header;
+mod recipient;
+
+pub use self::algorithm::*;
+pub use self::decoder::*;
+pub use self::encoder::*;
+pub use self::encryption::*;
+pub use self::format::*;
+pub use self::header::*;
+pub use self::recipient::*;
@@ -0,0 +1,32 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jwe::JweHeader;
+
+/// The recipient of a JWE.
+///
+/// The per-recipient header determines how the content encryption key is
+/// made available to that recipient, such as the key management algorithm (`alg`)
+/// or the key (`kid`) that is used.
+#[derive(Clone, Copy)]
+pub struct Recipient<'a> {
+  /// The per-recipient non integrity-protected JOSE header.
+  pub header: Option<&'a JweHeader>,
+}
+
+impl<'a> Default for Recipient<'a> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<'a> Recipient<'a> {
+  pub fn new() -> Self {
+    Self { header: None }
+  }
+
+  pub fn header(mut self, value: &'a JweHeader) -> Self {
+    self.header = Some(value);
+    self
+  }
+}
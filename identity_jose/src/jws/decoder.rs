@@ -127,6 +127,8 @@ impl<'a, 'b> Decoder<'b> {
   /// in order for the entire JWS to be considered valid, hence `verify_fn` can error
   /// on signatures it cannot verify. `verify_fn` must return an error if signature verification
   /// fails or if the `alg` parameter in the header describes a cryptographic algorithm that it cannot handle.
+  ///
+  /// If no signature could be verified, the error of the last attempted signature is returned.
   pub fn decode<FUN, ERR>(&self, verify_fn: &FUN, data: &'b [u8]) -> Result<Token<'b>>
   where
     FUN: Fn(
@@ -138,14 +140,16 @@ impl<'a, 'b> Decoder<'b> {
     ERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     self.expand(data, |payload, signatures| {
+      let mut last_error: Option<Error> = None;
       for signature in signatures {
-        if let Ok(token) = self.decode_one(verify_fn, payload, signature) {
-          return Ok(token);
+        match self.decode_one(verify_fn, payload, signature) {
+          Ok(token) => return Ok(token),
+          Err(err) => last_error = Some(err),
         }
       }
 
-      // TODO: Improve error by somehow including the error returned by decode_one if it exists.
-      Err(Error::InvalidContent("recipient not found"))
+      // Surface the reason why the last signature could not be verified, if there was any signature.
+      Err(last_error.unwrap_or(Error::InvalidContent("recipient not found")))
     })
   }
 
@@ -113,11 +113,8 @@ impl<'a> Encoder<'a> {
   pub async fn encode_serde<T, FUN, FUT, ERR>(&self, sign_fn: &FUN, claims: &T) -> Result<String>
   where
     T: Serialize,
-    FUN: Fn(Option<EncoderProtectedHeader>, Option<EncoderUnprotectedHeader>, EncoderMessage) -> FUT
-      + 'static
-      + Send
-      + Sync,
-    FUT: Future<Output = std::result::Result<EncoderSignature, ERR>> + Send,
+    FUN: Fn(Option<EncoderProtectedHeader>, Option<EncoderUnprotectedHeader>, EncoderMessage) -> FUT,
+    FUT: Future<Output = std::result::Result<EncoderSignature, ERR>>,
     ERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     self
@@ -133,11 +130,8 @@ impl<'a> Encoder<'a> {
   /// The closure also takes the bytes to be signed and is expected to return the signature as bytes.
   pub async fn encode<FUN, FUT, ERR>(&self, sign_fn: &FUN, claims: &[u8]) -> Result<String>
   where
-    FUN: Fn(Option<EncoderProtectedHeader>, Option<EncoderUnprotectedHeader>, EncoderMessage) -> FUT
-      + 'static
-      + Send
-      + Sync,
-    FUT: Future<Output = std::result::Result<EncoderSignature, ERR>> + Send,
+    FUN: Fn(Option<EncoderProtectedHeader>, Option<EncoderUnprotectedHeader>, EncoderMessage) -> FUT,
+    FUT: Future<Output = std::result::Result<EncoderSignature, ERR>>,
     ERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     if self.recipients.is_empty() {
@@ -255,11 +249,8 @@ impl<'a> Encoder<'a> {
     recipient: Recipient<'b>,
   ) -> Result<JwsSignature<'b>>
   where
-    FUN: Fn(Option<EncoderProtectedHeader>, Option<EncoderUnprotectedHeader>, EncoderMessage) -> FUT
-      + 'static
-      + Send
-      + Sync,
-    FUT: Future<Output = std::result::Result<EncoderSignature, ERR>> + Send,
+    FUN: Fn(Option<EncoderProtectedHeader>, Option<EncoderUnprotectedHeader>, EncoderMessage) -> FUT,
+    FUT: Future<Output = std::result::Result<EncoderSignature, ERR>>,
     ERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     let protected: Option<String> = recipient.protected.map(jwu::encode_b64_json).transpose()?;
@@ -102,6 +102,8 @@ mod encoder;
 mod format;
 mod header;
 mod recipient;
+mod signature_verifier;
+pub mod verifiers;
 
 pub use self::algorithm::*;
 pub use self::charset::*;
@@ -110,3 +112,4 @@ pub use self::encoder::*;
 pub use self::format::*;
 pub use self::header::*;
 pub use self::recipient::*;
+pub use self::signature_verifier::*;
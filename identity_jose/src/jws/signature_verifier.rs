@@ -0,0 +1,57 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jwk::Jwk;
+use crate::jws::JwsAlgorithm;
+
+/// Trait for cryptographically verifying a JWS signature.
+///
+/// Implementors are expected to check that `public_key` is suitable for `alg` and to return
+/// [`SignatureVerificationError::UnsupportedAlgorithm`] for algorithms they cannot handle, so that a single
+/// verifier can be shared by all components that need to verify a JWS.
+///
+/// Any closure with a matching signature implements this trait.
+pub trait JwsSignatureVerifier {
+  /// Verifies that `signature` is a valid signature of `signing_input` created with the private key corresponding
+  /// to `public_key` using the algorithm `alg`.
+  fn verify(
+    &self,
+    alg: JwsAlgorithm,
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError>;
+}
+
+impl<F> JwsSignatureVerifier for F
+where
+  F: Fn(JwsAlgorithm, &[u8], &[u8], &Jwk) -> Result<(), SignatureVerificationError>,
+{
+  fn verify(
+    &self,
+    alg: JwsAlgorithm,
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError> {
+    self(alg, signing_input, signature, public_key)
+  }
+}
+
+/// Errors that can occur when verifying a JWS signature with a [`JwsSignatureVerifier`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SignatureVerificationError {
+  #[error("unsupported jws algorithm `{0}`")]
+  UnsupportedAlgorithm(JwsAlgorithm),
+  #[error("unsupported key type")]
+  UnsupportedKeyType,
+  #[error("unsupported key parameters")]
+  UnsupportedKeyParams,
+  #[error("could not decode the public key")]
+  KeyDecodingFailure(#[source] Box<dyn std::error::Error + Send + Sync>),
+  #[error("invalid signature")]
+  InvalidSignature,
+  #[error("signature verification failed")]
+  Unspecified(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
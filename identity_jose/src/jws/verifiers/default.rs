@@ -0,0 +1,43 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jwk::Jwk;
+use crate::jws::JwsAlgorithm;
+use crate::jws::JwsSignatureVerifier;
+use crate::jws::SignatureVerificationError;
+
+/// A [`JwsSignatureVerifier`] supporting all algorithms whose feature is enabled.
+///
+/// Algorithms without an enabled implementation are rejected with
+/// [`SignatureVerificationError::UnsupportedAlgorithm`].
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct DefaultJwsSignatureVerifier;
+
+impl DefaultJwsSignatureVerifier {
+  /// Creates a new [`DefaultJwsSignatureVerifier`].
+  pub fn new() -> Self {
+    Self
+  }
+}
+
+impl JwsSignatureVerifier for DefaultJwsSignatureVerifier {
+  #[allow(unused_variables)]
+  fn verify(
+    &self,
+    alg: JwsAlgorithm,
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError> {
+    match alg {
+      #[cfg(feature = "eddsa")]
+      JwsAlgorithm::EdDSA => super::EdDSAJwsSignatureVerifier.verify(alg, signing_input, signature, public_key),
+      #[cfg(feature = "es256")]
+      JwsAlgorithm::ES256 => super::EcDSAJwsSignatureVerifier.verify(alg, signing_input, signature, public_key),
+      #[cfg(feature = "es256k")]
+      JwsAlgorithm::ES256K => super::EcDSAJwsSignatureVerifier.verify(alg, signing_input, signature, public_key),
+      _ => Err(SignatureVerificationError::UnsupportedAlgorithm(alg)),
+    }
+  }
+}
@@ -0,0 +1,108 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jwk::EcCurve;
+use crate::jwk::Jwk;
+use crate::jwk::JwkParamsEc;
+use crate::jwk::JwkType;
+use crate::jws::JwsAlgorithm;
+use crate::jws::JwsSignatureVerifier;
+use crate::jws::SignatureVerificationError;
+use crate::jwu;
+
+/// A [`JwsSignatureVerifier`] for the ECDSA algorithms `ES256` (feature `es256`) and `ES256K` (feature `es256k`).
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct EcDSAJwsSignatureVerifier;
+
+impl EcDSAJwsSignatureVerifier {
+  /// Creates a new [`EcDSAJwsSignatureVerifier`].
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Verifies an `ES256` signature of `signing_input` with the P-256 `public_key`.
+  #[cfg(feature = "es256")]
+  pub fn verify_es256(
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError> {
+    use p256::ecdsa::signature::Verifier;
+    use p256::ecdsa::Signature;
+    use p256::ecdsa::VerifyingKey;
+
+    let sec1_bytes: Vec<u8> = expand_sec1_public_key(public_key, EcCurve::P256)?;
+    let verifying_key: VerifyingKey = VerifyingKey::from_sec1_bytes(&sec1_bytes)
+      .map_err(|err| SignatureVerificationError::KeyDecodingFailure(err.into()))?;
+    let signature: Signature =
+      Signature::try_from(signature).map_err(|_| SignatureVerificationError::InvalidSignature)?;
+
+    verifying_key
+      .verify(signing_input, &signature)
+      .map_err(|_| SignatureVerificationError::InvalidSignature)
+  }
+
+  /// Verifies an `ES256K` signature of `signing_input` with the secp256k1 `public_key`.
+  #[cfg(feature = "es256k")]
+  pub fn verify_es256k(
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError> {
+    use k256::ecdsa::signature::Verifier;
+    use k256::ecdsa::Signature;
+    use k256::ecdsa::VerifyingKey;
+
+    let sec1_bytes: Vec<u8> = expand_sec1_public_key(public_key, EcCurve::Secp256K1)?;
+    let verifying_key: VerifyingKey = VerifyingKey::from_sec1_bytes(&sec1_bytes)
+      .map_err(|err| SignatureVerificationError::KeyDecodingFailure(err.into()))?;
+    let signature: Signature =
+      Signature::try_from(signature).map_err(|_| SignatureVerificationError::InvalidSignature)?;
+    // The verification rejects signatures with a high `s` value, which other implementations may produce.
+    let signature: Signature = signature.normalize_s().unwrap_or(signature);
+
+    verifying_key
+      .verify(signing_input, &signature)
+      .map_err(|_| SignatureVerificationError::InvalidSignature)
+  }
+}
+
+impl JwsSignatureVerifier for EcDSAJwsSignatureVerifier {
+  fn verify(
+    &self,
+    alg: JwsAlgorithm,
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError> {
+    match alg {
+      #[cfg(feature = "es256")]
+      JwsAlgorithm::ES256 => Self::verify_es256(signing_input, signature, public_key),
+      #[cfg(feature = "es256k")]
+      JwsAlgorithm::ES256K => Self::verify_es256k(signing_input, signature, public_key),
+      _ => Err(SignatureVerificationError::UnsupportedAlgorithm(alg)),
+    }
+  }
+}
+
+/// Encodes the EC `public_key` as an uncompressed SEC1 point after checking that it is on the `expected` curve.
+fn expand_sec1_public_key(public_key: &Jwk, expected: EcCurve) -> Result<Vec<u8>, SignatureVerificationError> {
+  if public_key.kty() != JwkType::Ec {
+    return Err(SignatureVerificationError::UnsupportedKeyType);
+  }
+  if public_key.try_ec_curve().ok() != Some(expected) {
+    return Err(SignatureVerificationError::UnsupportedKeyParams);
+  }
+
+  let params: &JwkParamsEc = public_key
+    .try_ec_params()
+    .map_err(|_| SignatureVerificationError::UnsupportedKeyType)?;
+  let x: Vec<u8> =
+    jwu::decode_b64(&params.x).map_err(|err| SignatureVerificationError::KeyDecodingFailure(err.into()))?;
+  let y: Vec<u8> =
+    jwu::decode_b64(&params.y).map_err(|err| SignatureVerificationError::KeyDecodingFailure(err.into()))?;
+
+  // Transformation according to section 2.3.3 from http://www.secg.org/sec1-v2.pdf.
+  Ok([0x04].into_iter().chain(x).chain(y).collect())
+}
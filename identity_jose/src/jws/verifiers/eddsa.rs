@@ -0,0 +1,133 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::signatures::ed25519;
+
+use crate::jwk::EdCurve;
+use crate::jwk::Jwk;
+use crate::jwk::JwkParamsOkp;
+use crate::jwk::JwkType;
+use crate::jws::JwsAlgorithm;
+use crate::jws::JwsSignatureVerifier;
+use crate::jws::SignatureVerificationError;
+use crate::jwu;
+
+/// A [`JwsSignatureVerifier`] for the `EdDSA` algorithm.
+///
+/// Only keys on the Ed25519 curve are currently supported.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct EdDSAJwsSignatureVerifier;
+
+impl EdDSAJwsSignatureVerifier {
+  /// Creates a new [`EdDSAJwsSignatureVerifier`].
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Verifies an EdDSA signature of `signing_input` with the Ed25519 `public_key`.
+  pub fn verify_ed25519(
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError> {
+    let params: &JwkParamsOkp = public_key
+      .try_okp_params()
+      .map_err(|_| SignatureVerificationError::UnsupportedKeyType)?;
+    let public_key: [u8; ed25519::PUBLIC_KEY_LENGTH] = jwu::decode_b64(&params.x)
+      .map_err(|err| SignatureVerificationError::KeyDecodingFailure(err.into()))?
+      .try_into()
+      .map_err(|_| SignatureVerificationError::KeyDecodingFailure("invalid ed25519 public key length".into()))?;
+    let public_key: ed25519::PublicKey = ed25519::PublicKey::try_from_bytes(public_key)
+      .map_err(|err| SignatureVerificationError::KeyDecodingFailure(err.into()))?;
+
+    let signature: [u8; ed25519::SIGNATURE_LENGTH] = signature
+      .try_into()
+      .map_err(|_| SignatureVerificationError::InvalidSignature)?;
+
+    if public_key.verify(&ed25519::Signature::from_bytes(signature), signing_input) {
+      Ok(())
+    } else {
+      Err(SignatureVerificationError::InvalidSignature)
+    }
+  }
+}
+
+impl JwsSignatureVerifier for EdDSAJwsSignatureVerifier {
+  fn verify(
+    &self,
+    alg: JwsAlgorithm,
+    signing_input: &[u8],
+    signature: &[u8],
+    public_key: &Jwk,
+  ) -> Result<(), SignatureVerificationError> {
+    if alg != JwsAlgorithm::EdDSA {
+      return Err(SignatureVerificationError::UnsupportedAlgorithm(alg));
+    }
+    if public_key.kty() != JwkType::Okp {
+      return Err(SignatureVerificationError::UnsupportedKeyType);
+    }
+
+    match public_key.try_ed_curve() {
+      Ok(EdCurve::Ed25519) => Self::verify_ed25519(signing_input, signature, public_key),
+      _ => Err(SignatureVerificationError::UnsupportedKeyParams),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crypto::signatures::ed25519::SecretKey;
+
+  use crate::jwk::EcCurve;
+  use crate::jwk::JwkParamsEc;
+
+  use super::*;
+
+  fn keypair() -> (SecretKey, Jwk) {
+    let secret_key: SecretKey = SecretKey::generate().unwrap();
+    let mut params: JwkParamsOkp = JwkParamsOkp::new();
+    params.crv = EdCurve::Ed25519.name().to_owned();
+    params.x = jwu::encode_b64(secret_key.public_key().to_bytes());
+    (secret_key, Jwk::from_params(params))
+  }
+
+  #[test]
+  fn verify_ed25519() {
+    let (secret_key, jwk) = keypair();
+    let signature: [u8; ed25519::SIGNATURE_LENGTH] = secret_key.sign(b"signing input").to_bytes();
+
+    let verifier: EdDSAJwsSignatureVerifier = EdDSAJwsSignatureVerifier::new();
+    verifier
+      .verify(JwsAlgorithm::EdDSA, b"signing input", &signature, &jwk)
+      .unwrap();
+    assert!(matches!(
+      verifier.verify(JwsAlgorithm::EdDSA, b"other input", &signature, &jwk),
+      Err(SignatureVerificationError::InvalidSignature)
+    ));
+    assert!(matches!(
+      verifier.verify(JwsAlgorithm::ES256, b"signing input", &signature, &jwk),
+      Err(SignatureVerificationError::UnsupportedAlgorithm(JwsAlgorithm::ES256))
+    ));
+  }
+
+  #[test]
+  fn reject_unsupported_keys() {
+    let verifier: EdDSAJwsSignatureVerifier = EdDSAJwsSignatureVerifier::new();
+
+    let mut params: JwkParamsOkp = JwkParamsOkp::new();
+    params.crv = "X25519".to_owned();
+    params.x = jwu::encode_b64([0; 32]);
+    assert!(matches!(
+      verifier.verify(JwsAlgorithm::EdDSA, &[], &[], &Jwk::from_params(params)),
+      Err(SignatureVerificationError::UnsupportedKeyParams)
+    ));
+
+    let mut params: JwkParamsEc = JwkParamsEc::new();
+    params.crv = EcCurve::P256.name().to_owned();
+    assert!(matches!(
+      verifier.verify(JwsAlgorithm::EdDSA, &[], &[], &Jwk::from_params(params)),
+      Err(SignatureVerificationError::UnsupportedKeyType)
+    ));
+  }
+}
@@ -0,0 +1,23 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementations of [`JwsSignatureVerifier`](crate::jws::JwsSignatureVerifier) for common algorithms.
+//!
+//! Each implementation is only available if the corresponding feature is enabled:
+//! - `eddsa`: [`EdDSAJwsSignatureVerifier`] for `EdDSA` with Ed25519 keys,
+//! - `es256`: [`EcDSAJwsSignatureVerifier`] for `ES256` with P-256 keys,
+//! - `es256k`: [`EcDSAJwsSignatureVerifier`] for `ES256K` with secp256k1 keys.
+//!
+//! The [`DefaultJwsSignatureVerifier`] dispatches to all enabled implementations.
+
+mod default;
+#[cfg(any(feature = "es256", feature = "es256k"))]
+mod ecdsa;
+#[cfg(feature = "eddsa")]
+mod eddsa;
+
+pub use self::default::DefaultJwsSignatureVerifier;
+#[cfg(any(feature = "es256", feature = "es256k"))]
+pub use self::ecdsa::EcDSAJwsSignatureVerifier;
+#[cfg(feature = "eddsa")]
+pub use self::eddsa::EdDSAJwsSignatureVerifier;
@@ -6,6 +6,9 @@ use url::Url;
 
 use crate::error::Error;
 use crate::error::Result;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweHeader;
 use crate::jwk::Jwk;
 use crate::jws::JwsAlgorithm;
 use crate::jws::JwsHeader;
@@ -117,19 +120,15 @@ impl<'a> JwtHeaderSet<'a, JwsHeader> {
   impl_accessors!(@protected, ppt, try_ppt, &str);
 }
 
-// #[rustfmt::skip]
-// impl<'a> JwtHeaderSet<'a, JweHeader> {
-//   impl_accessors!(@unwrapped, alg, try_alg, JweAlgorithm);
-//   impl_accessors!(@unwrapped, enc, try_enc, JweEncryption);
-//   impl_accessors!(zip, try_zip, JweCompression);
-//   impl_accessors!(epk, try_epk, &Jwk);
-//   impl_accessors!(@protected, apu, try_apu, &str);
-//   impl_accessors!(@protected, apv, try_apv, &str);
-//   impl_accessors!(@protected, iv, try_iv, &str);
-//   impl_accessors!(@protected, tag, try_tag, &str);
-//   impl_accessors!(@protected, p2s, try_p2s, &str);
-//   impl_accessors!(@protected, p2c, try_p2c, u64);
-// }
+#[rustfmt::skip]
+impl<'a> JwtHeaderSet<'a, JweHeader> {
+  impl_accessors!(alg, try_alg, JweAlgorithm);
+  impl_accessors!(enc, try_enc, JweEncryption);
+  impl_accessors!(epk, try_epk, &Jwk);
+  impl_accessors!(apu, try_apu, &str);
+  impl_accessors!(apv, try_apv, &str);
+  impl_accessors!(skid, try_skid, &str);
+}
 
 impl<'a, T: 'a> Default for JwtHeaderSet<'a, T> {
   fn default() -> Self {
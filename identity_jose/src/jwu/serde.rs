@@ -6,6 +6,7 @@ use core::str;
 use crate::error::Error;
 use crate::error::Result;
 use crate::jose::JoseHeader;
+use crate::jwe::JweHeader;
 use crate::jws::JwsHeader;
 
 // The default value of the "b64" header parameter
@@ -66,6 +67,30 @@ pub fn validate_jws_headers(
   Ok(())
 }
 
+pub fn validate_jwe_headers(
+  protected: Option<&JweHeader>,
+  unprotected: Option<&JweHeader>,
+  recipient: Option<&JweHeader>,
+  permitted: Option<&[String]>,
+) -> Result<()> {
+  // The protected, shared unprotected and per-recipient headers MUST be disjoint
+  let headers: [Option<&JweHeader>; 3] = [protected, unprotected, recipient];
+  for (index, lhs) in headers.iter().enumerate() {
+    for rhs in &headers[index + 1..] {
+      if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+        if !lhs.is_disjoint(rhs) {
+          return Err(Error::InvalidContent("JWE headers are not disjoint"));
+        }
+      }
+    }
+  }
+
+  validate_crit(protected, unprotected, permitted)?;
+  validate_crit(protected, recipient, permitted)?;
+
+  Ok(())
+}
+
 pub fn validate_crit<T>(protected: Option<&T>, unprotected: Option<&T>, permitted: Option<&[String]>) -> Result<()>
 where
   T: JoseHeader,
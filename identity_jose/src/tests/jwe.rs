@@ -0,0 +1,506 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crypto::ciphers::aes_gcm::Aes256Gcm;
+use crypto::ciphers::aes_kw::Aes256Kw;
+use crypto::ciphers::chacha::XChaCha20Poly1305;
+use crypto::ciphers::traits::Aead;
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+use crypto::keys::x25519::PublicKey;
+use crypto::keys::x25519::SecretKey;
+use crypto::keys::x25519::PUBLIC_KEY_LENGTH;
+use crypto::utils::rand;
+
+use crate::error::Error;
+use crate::jwe::Decoder;
+use crate::jwe::Encoder;
+use crate::jwe::EncoderContent;
+use crate::jwe::EncoderKey;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweFormat;
+use crate::jwe::JweHeader;
+use crate::jwe::Recipient;
+use crate::jwe::Token;
+use crate::jwk::EcxCurve;
+use crate::jwk::Jwk;
+use crate::jwk::JwkParamsOkp;
+use crate::jwt::JwtHeaderSet;
+use crate::jwu;
+
+const CEK_LENGTH: usize = 32;
+
+type Keys = Arc<HashMap<String, SecretKey>>;
+
+fn x25519_jwk(public_key: &PublicKey) -> Jwk {
+  let mut params: JwkParamsOkp = JwkParamsOkp::new();
+  params.crv = EcxCurve::X25519.name().to_owned();
+  params.x = jwu::encode_b64(public_key.as_slice());
+  Jwk::from_params(params)
+}
+
+fn expand_x25519_jwk(jwk: &Jwk) -> PublicKey {
+  let params: &JwkParamsOkp = jwk.try_okp_params().unwrap();
+  assert_eq!(params.try_ecx_curve().unwrap(), EcxCurve::X25519);
+  let bytes: [u8; PUBLIC_KEY_LENGTH] = jwu::decode_b64(&params.x).unwrap().try_into().unwrap();
+  PublicKey::from_bytes(bytes)
+}
+
+/// Concat KDF using SHA-256, limited to a single round.
+///
+/// See: https://tools.ietf.org/html/rfc7518#section-4.6.2
+fn concat_kdf(algorithm_id: &str, z: &[u8], apu: &[u8], apv: &[u8]) -> Vec<u8> {
+  let mut input: Vec<u8> = Vec::new();
+  input.extend(1u32.to_be_bytes());
+  input.extend(z);
+  for value in [algorithm_id.as_bytes(), apu, apv] {
+    input.extend((value.len() as u32).to_be_bytes());
+    input.extend(value);
+  }
+  input.extend(((CEK_LENGTH * 8) as u32).to_be_bytes());
+
+  let mut digest: [u8; SHA256_LEN] = [0; SHA256_LEN];
+  SHA256(&input, &mut digest);
+  digest.to_vec()
+}
+
+/// Derives the key agreement output of the given algorithm from the shared secret `z`.
+fn derive_key(header: &JwtHeaderSet<'_, JweHeader>, z: &[u8]) -> Vec<u8> {
+  let alg: JweAlgorithm = header.try_alg().unwrap();
+  // Direct key agreement uses the content encryption algorithm as the AlgorithmID.
+  let algorithm_id: &str = if alg.is_direct_key_agreement() {
+    header.try_enc().unwrap().name()
+  } else {
+    alg.name()
+  };
+  let apu: Vec<u8> = header
+    .apu()
+    .map(jwu::decode_b64)
+    .transpose()
+    .unwrap()
+    .unwrap_or_default();
+  let apv: Vec<u8> = header
+    .apv()
+    .map(jwu::decode_b64)
+    .transpose()
+    .unwrap()
+    .unwrap_or_default();
+
+  concat_kdf(algorithm_id, z, &apu, &apv)
+}
+
+/// Performs the key management for a recipient whose public key is looked up by `kid`.
+///
+/// The `sender` key is used for `ECDH-1PU`.
+async fn key_management(
+  public_keys: Arc<HashMap<String, PublicKey>>,
+  sender: Option<Arc<SecretKey>>,
+  protected: Option<JweHeader>,
+  unprotected: Option<JweHeader>,
+  header: Option<JweHeader>,
+  cek: Option<Vec<u8>>,
+) -> Result<EncoderKey, String> {
+  let header_set: JwtHeaderSet<'_, JweHeader> = JwtHeaderSet::new()
+    .protected(protected.as_ref())
+    .unprotected(unprotected.as_ref())
+    .header(header.as_ref());
+  let kid: &str = header_set.try_kid().map_err(|err| err.to_string())?;
+  let public_key: &PublicKey = public_keys.get(kid).ok_or("unknown recipient")?;
+
+  let ephemeral: SecretKey = SecretKey::generate().map_err(|err| err.to_string())?;
+  let mut z: Vec<u8> = ephemeral.diffie_hellman(public_key).as_bytes().to_vec();
+
+  let alg: JweAlgorithm = header_set.try_alg().map_err(|err| err.to_string())?;
+  if alg == JweAlgorithm::ECDH_1PU {
+    let sender: Arc<SecretKey> = sender.ok_or("missing sender key")?;
+    z.extend(sender.diffie_hellman(public_key).as_bytes());
+  }
+
+  let derived: Vec<u8> = derive_key(&header_set, &z);
+  let epk: Option<Jwk> = Some(x25519_jwk(&ephemeral.public_key()));
+
+  if alg.is_direct_key_agreement() {
+    return Ok(EncoderKey {
+      cek: derived,
+      encrypted_key: None,
+      epk,
+    });
+  }
+
+  let cek: Vec<u8> = match cek {
+    Some(cek) => cek,
+    None => {
+      let mut cek: Vec<u8> = vec![0; CEK_LENGTH];
+      rand::fill(&mut cek).map_err(|err| err.to_string())?;
+      cek
+    }
+  };
+
+  let mut encrypted_key: Vec<u8> = vec![0; CEK_LENGTH + Aes256Kw::BLOCK];
+  Aes256Kw::new(&derived)
+    .wrap_key(&cek, &mut encrypted_key)
+    .map_err(|err| err.to_string())?;
+
+  Ok(EncoderKey {
+    cek,
+    encrypted_key: Some(encrypted_key),
+    epk,
+  })
+}
+
+async fn encrypt(enc: JweEncryption, cek: Vec<u8>, aad: Vec<u8>, plaintext: Vec<u8>) -> Result<EncoderContent, String> {
+  let mut iv: Vec<u8> = vec![0; enc.iv_len()];
+  rand::fill(&mut iv).map_err(|err| err.to_string())?;
+
+  let mut ciphertext: Vec<u8> = vec![0; plaintext.len()];
+  let mut tag: Vec<u8> = vec![0; enc.tag_len()];
+
+  match enc {
+    JweEncryption::A256GCM => Aes256Gcm::try_encrypt(&cek, &iv, &aad, &plaintext, &mut ciphertext, &mut tag),
+    JweEncryption::XC20P => XChaCha20Poly1305::try_encrypt(&cek, &iv, &aad, &plaintext, &mut ciphertext, &mut tag),
+  }
+  .map_err(|err| err.to_string())?;
+
+  Ok(EncoderContent { iv, ciphertext, tag })
+}
+
+fn decrypt(
+  enc: JweEncryption,
+  cek: &[u8],
+  iv: &[u8],
+  aad: &[u8],
+  ciphertext: &[u8],
+  tag: &[u8],
+) -> Result<Vec<u8>, String> {
+  let mut plaintext: Vec<u8> = vec![0; ciphertext.len()];
+
+  match enc {
+    JweEncryption::A256GCM => Aes256Gcm::try_decrypt(cek, iv, aad, &mut plaintext, ciphertext, tag),
+    JweEncryption::XC20P => XChaCha20Poly1305::try_decrypt(cek, iv, aad, &mut plaintext, ciphertext, tag),
+  }
+  .map_err(|err| err.to_string())?;
+
+  Ok(plaintext)
+}
+
+async fn encode(
+  encoder: &Encoder<'_>,
+  plaintext: &[u8],
+  recipients: &Keys,
+  sender: Option<Arc<SecretKey>>,
+) -> crate::error::Result<String> {
+  let public_keys: Arc<HashMap<String, PublicKey>> = Arc::new(
+    recipients
+      .iter()
+      .map(|(kid, secret_key)| (kid.clone(), secret_key.public_key()))
+      .collect(),
+  );
+
+  let key_fn = move |protected: Option<JweHeader>,
+                     unprotected: Option<JweHeader>,
+                     header: Option<JweHeader>,
+                     cek: Option<Vec<u8>>| {
+    key_management(public_keys.clone(), sender.clone(), protected, unprotected, header, cek)
+  };
+
+  encoder.encode(&key_fn, &encrypt, plaintext).await
+}
+
+/// Decodes the JWE using the secret key of the recipient `kid`.
+///
+/// The `sender` public key is used for `ECDH-1PU`.
+fn decode(
+  decoder: &Decoder,
+  data: &[u8],
+  kid: &str,
+  secret_key: &SecretKey,
+  sender: Option<&PublicKey>,
+) -> crate::error::Result<Token> {
+  let key_fn = |protected: Option<&JweHeader>,
+                unprotected: Option<&JweHeader>,
+                header: Option<&JweHeader>,
+                encrypted_key: Option<&[u8]>| {
+    let header_set: JwtHeaderSet<'_, JweHeader> = JwtHeaderSet::new()
+      .protected(protected)
+      .unprotected(unprotected)
+      .header(header);
+
+    if header_set.try_kid().map_err(|err| err.to_string())? != kid {
+      return Err("unknown recipient".to_owned());
+    }
+
+    let epk: PublicKey = expand_x25519_jwk(header_set.try_epk().map_err(|err| err.to_string())?);
+    let mut z: Vec<u8> = secret_key.diffie_hellman(&epk).as_bytes().to_vec();
+
+    let alg: JweAlgorithm = header_set.try_alg().map_err(|err| err.to_string())?;
+    if alg == JweAlgorithm::ECDH_1PU {
+      z.extend(
+        secret_key
+          .diffie_hellman(sender.ok_or("missing sender key")?)
+          .as_bytes(),
+      );
+    }
+
+    let derived: Vec<u8> = derive_key(&header_set, &z);
+
+    match encrypted_key {
+      None => Ok(derived),
+      Some(encrypted_key) => {
+        let mut cek: Vec<u8> = vec![0; encrypted_key.len() - Aes256Kw::BLOCK];
+        Aes256Kw::new(&derived)
+          .unwrap_key(encrypted_key, &mut cek)
+          .map_err(|err| err.to_string())?;
+        Ok(cek)
+      }
+    }
+  };
+
+  decoder.decode(&key_fn, &decrypt, data)
+}
+
+fn generate_keys(kids: &[&str]) -> Keys {
+  Arc::new(
+    kids
+      .iter()
+      .map(|kid| (kid.to_string(), SecretKey::generate().unwrap()))
+      .collect(),
+  )
+}
+
+#[tokio::test]
+async fn test_compact_ecdh_es_roundtrip() {
+  let keys: Keys = generate_keys(&["did:example:bob#key-1"]);
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_alg(JweAlgorithm::ECDH_ES);
+  protected.set_enc(JweEncryption::A256GCM);
+  protected.set_kid("did:example:bob#key-1");
+  protected.set_apu(jwu::encode_b64("Alice"));
+  protected.set_apv(jwu::encode_b64("Bob"));
+
+  let encoder: Encoder<'_> = Encoder::new().protected(&protected).recipient(Recipient::new());
+  let token: String = encode(&encoder, b"Live long and prosper.", &keys, None).await.unwrap();
+
+  // The compact serialization has an empty encrypted key for direct key agreement.
+  let segments: Vec<&str> = token.split('.').collect();
+  assert_eq!(segments.len(), 5);
+  assert!(segments[1].is_empty());
+
+  let decoder: Decoder = Decoder::new();
+  let secret_key: &SecretKey = &keys["did:example:bob#key-1"];
+  let decoded: Token = decode(&decoder, token.as_bytes(), "did:example:bob#key-1", secret_key, None).unwrap();
+
+  assert_eq!(decoded.plaintext, b"Live long and prosper.");
+  let decoded_protected: JweHeader = decoded.protected.unwrap();
+  assert_eq!(decoded_protected.alg(), Some(JweAlgorithm::ECDH_ES));
+  assert!(decoded_protected.epk().is_some());
+  assert!(decoded.unprotected.is_none());
+  assert!(decoded.header.is_none());
+}
+
+#[tokio::test]
+async fn test_general_multiple_recipients_roundtrip() {
+  let kids: [&str; 2] = ["did:example:bob#key-1", "did:example:carol#key-1"];
+  let keys: Keys = generate_keys(&kids);
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_enc(JweEncryption::XC20P);
+  protected.set_cty("application/json");
+
+  let mut unprotected: JweHeader = JweHeader::new();
+  unprotected.set_alg(JweAlgorithm::ECDH_ES_A256KW);
+
+  let headers: Vec<JweHeader> = kids
+    .iter()
+    .map(|kid| {
+      let mut header: JweHeader = JweHeader::new();
+      header.set_kid(*kid);
+      header
+    })
+    .collect();
+
+  let encoder: Encoder<'_> = headers.iter().fold(
+    Encoder::new()
+      .format(JweFormat::General)
+      .protected(&protected)
+      .unprotected(&unprotected)
+      .aad(b"additional data"),
+    |encoder, header| encoder.recipient(Recipient::new().header(header)),
+  );
+  let token: String = encode(&encoder, br#"{"num":42}"#, &keys, None).await.unwrap();
+
+  let json: serde_json::Value = serde_json::from_str(&token).unwrap();
+  assert_eq!(json["recipients"].as_array().unwrap().len(), 2);
+  assert!(json["recipients"][0]["header"]["epk"].is_object());
+  assert!(json["recipients"][0]["encrypted_key"].is_string());
+
+  let decoder: Decoder = Decoder::new().format(JweFormat::General);
+  for kid in kids {
+    let decoded: Token = decode(&decoder, token.as_bytes(), kid, &keys[kid], None).unwrap();
+    assert_eq!(decoded.plaintext, br#"{"num":42}"#);
+    assert_eq!(decoded.aad.as_deref(), Some(b"additional data".as_slice()));
+    assert_eq!(decoded.header.unwrap().kid(), Some(kid));
+  }
+
+  // A party that is not a recipient cannot decrypt the content.
+  let eve: SecretKey = SecretKey::generate().unwrap();
+  assert!(matches!(
+    decode(&decoder, token.as_bytes(), "did:example:eve#key-1", &eve, None).unwrap_err(),
+    Error::DecryptionError(_)
+  ));
+}
+
+#[tokio::test]
+async fn test_flatten_ecdh_1pu_roundtrip() {
+  let keys: Keys = generate_keys(&["did:example:bob#key-1"]);
+  let sender: Arc<SecretKey> = Arc::new(SecretKey::generate().unwrap());
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_alg(JweAlgorithm::ECDH_1PU);
+  protected.set_enc(JweEncryption::A256GCM);
+  protected.set_skid("did:example:alice#key-1");
+
+  let mut header: JweHeader = JweHeader::new();
+  header.set_kid("did:example:bob#key-1");
+
+  let encoder: Encoder<'_> = Encoder::new()
+    .format(JweFormat::Flatten)
+    .protected(&protected)
+    .recipient(Recipient::new().header(&header));
+  let token: String = encode(&encoder, b"authenticated", &keys, Some(sender.clone()))
+    .await
+    .unwrap();
+
+  let decoder: Decoder = Decoder::new().format(JweFormat::Flatten);
+  let secret_key: &SecretKey = &keys["did:example:bob#key-1"];
+  let decoded: Token = decode(
+    &decoder,
+    token.as_bytes(),
+    "did:example:bob#key-1",
+    secret_key,
+    Some(&sender.public_key()),
+  )
+  .unwrap();
+  assert_eq!(decoded.plaintext, b"authenticated");
+  assert_eq!(decoded.protected.unwrap().skid(), Some("did:example:alice#key-1"));
+
+  // The content is bound to the sender's static key.
+  let other: PublicKey = SecretKey::generate().unwrap().public_key();
+  assert!(decode(
+    &decoder,
+    token.as_bytes(),
+    "did:example:bob#key-1",
+    secret_key,
+    Some(&other)
+  )
+  .is_err());
+}
+
+#[tokio::test]
+async fn test_tampered_ciphertext() {
+  let keys: Keys = generate_keys(&["did:example:bob#key-1"]);
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_alg(JweAlgorithm::ECDH_ES_A256KW);
+  protected.set_enc(JweEncryption::A256GCM);
+  protected.set_kid("did:example:bob#key-1");
+
+  let encoder: Encoder<'_> = Encoder::new().protected(&protected).recipient(Recipient::new());
+  let token: String = encode(&encoder, b"hello", &keys, None).await.unwrap();
+
+  let mut segments: Vec<String> = token.split('.').map(ToOwned::to_owned).collect();
+  let mut ciphertext: Vec<u8> = jwu::decode_b64(&segments[3]).unwrap();
+  ciphertext[0] ^= 1;
+  segments[3] = jwu::encode_b64(ciphertext);
+  let tampered: String = segments.join(".");
+
+  let decoder: Decoder = Decoder::new();
+  let secret_key: &SecretKey = &keys["did:example:bob#key-1"];
+  assert!(decode(&decoder, token.as_bytes(), "did:example:bob#key-1", secret_key, None).is_ok());
+  assert!(matches!(
+    decode(&decoder, tampered.as_bytes(), "did:example:bob#key-1", secret_key, None).unwrap_err(),
+    Error::DecryptionError(_)
+  ));
+}
+
+#[tokio::test]
+async fn test_invalid_configurations() {
+  let keys: Keys = generate_keys(&["did:example:bob#key-1", "did:example:carol#key-1"]);
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_alg(JweAlgorithm::ECDH_ES);
+  protected.set_enc(JweEncryption::A256GCM);
+
+  let mut bob: JweHeader = JweHeader::new();
+  bob.set_kid("did:example:bob#key-1");
+  let mut carol: JweHeader = JweHeader::new();
+  carol.set_kid("did:example:carol#key-1");
+
+  // The compact serialization supports neither per-recipient headers nor multiple recipients.
+  let encoder: Encoder<'_> = Encoder::new()
+    .protected(&protected)
+    .recipient(Recipient::new().header(&bob));
+  assert!(matches!(
+    encode(&encoder, b"hello", &keys, None).await.unwrap_err(),
+    Error::EncryptionError(_)
+  ));
+
+  // Direct key agreement cannot be used with multiple recipients.
+  let encoder: Encoder<'_> = Encoder::new()
+    .format(JweFormat::General)
+    .protected(&protected)
+    .recipient(Recipient::new().header(&bob))
+    .recipient(Recipient::new().header(&carol));
+  assert!(matches!(
+    encode(&encoder, b"hello", &keys, None).await.unwrap_err(),
+    Error::EncryptionError(_)
+  ));
+
+  // The headers must be disjoint.
+  let mut duplicate: JweHeader = JweHeader::new();
+  duplicate.set_alg(JweAlgorithm::ECDH_ES_A256KW);
+  duplicate.set_kid("did:example:bob#key-1");
+  let encoder: Encoder<'_> = Encoder::new()
+    .format(JweFormat::Flatten)
+    .protected(&protected)
+    .recipient(Recipient::new().header(&duplicate));
+  assert!(matches!(
+    encode(&encoder, b"hello", &keys, None).await.unwrap_err(),
+    Error::InvalidContent(_)
+  ));
+}
+
+#[tokio::test]
+async fn test_decoder_algorithm_filter() {
+  let keys: Keys = generate_keys(&["did:example:bob#key-1"]);
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_alg(JweAlgorithm::ECDH_ES);
+  protected.set_enc(JweEncryption::XC20P);
+  protected.set_kid("did:example:bob#key-1");
+
+  let encoder: Encoder<'_> = Encoder::new().protected(&protected).recipient(Recipient::new());
+  let token: String = encode(&encoder, b"hello", &keys, None).await.unwrap();
+  let secret_key: &SecretKey = &keys["did:example:bob#key-1"];
+
+  let decoder: Decoder = Decoder::new().algorithm(JweAlgorithm::ECDH_ES_A256KW);
+  assert!(matches!(
+    decode(&decoder, token.as_bytes(), "did:example:bob#key-1", secret_key, None).unwrap_err(),
+    Error::InvalidParam("alg")
+  ));
+
+  let decoder: Decoder = Decoder::new().encryption(JweEncryption::A256GCM);
+  assert!(matches!(
+    decode(&decoder, token.as_bytes(), "did:example:bob#key-1", secret_key, None).unwrap_err(),
+    Error::InvalidParam("enc")
+  ));
+
+  let decoder: Decoder = Decoder::new()
+    .algorithm(JweAlgorithm::ECDH_ES)
+    .encryption(JweEncryption::XC20P);
+  assert!(decode(&decoder, token.as_bytes(), "did:example:bob#key-1", secret_key, None).is_ok());
+}
@@ -97,6 +97,12 @@ pub enum ErrorCause {
   /// [`Resolver`](crate::resolution::Resolver).
   #[error("did resolution failed: the DID method \"{method}\" is not supported by the resolver")]
   UnsupportedMethodError { method: String },
+  /// Caused by attempting to resolve a version of a DID document whose method does not have a corresponding versioned
+  /// handler attached to the [`Resolver`](crate::resolution::Resolver).
+  #[error(
+    "did resolution failed: versioned resolution of the DID method \"{method}\" is not supported by the resolver"
+  )]
+  UnsupportedVersionedMethodError { method: String },
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
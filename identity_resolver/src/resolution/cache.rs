@@ -0,0 +1,267 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Configuration of the cache of a [`Resolver`](crate::Resolver).
+///
+/// Resolved documents are cached by DID for the time-to-live (TTL) configured for their DID method, falling back to
+/// the default TTL. A TTL of zero disables caching for a method. Once the maximum number of entries is reached,
+/// expired entries are evicted first, then the entry that was cached the longest time ago.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResolverCacheConfig {
+  /// The TTL of documents of DID methods without a specific TTL.
+  pub default_ttl: Duration,
+  /// The TTLs of documents of specific DID methods.
+  pub method_ttls: HashMap<String, Duration>,
+  /// The maximum number of cached documents.
+  pub max_entries: usize,
+}
+
+impl ResolverCacheConfig {
+  /// The default maximum number of cached documents.
+  pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+  /// Creates a new [`ResolverCacheConfig`] caching documents of all DID methods for `default_ttl`.
+  pub fn new(default_ttl: Duration) -> Self {
+    Self {
+      default_ttl,
+      method_ttls: HashMap::new(),
+      max_entries: Self::DEFAULT_MAX_ENTRIES,
+    }
+  }
+
+  /// Sets the TTL of documents of the DID method `method`, overriding the default TTL.
+  #[must_use]
+  pub fn method_ttl(mut self, method: impl Into<String>, ttl: Duration) -> Self {
+    self.method_ttls.insert(method.into(), ttl);
+    self
+  }
+
+  /// Sets the maximum number of cached documents.
+  #[must_use]
+  pub fn max_entries(mut self, max_entries: usize) -> Self {
+    self.max_entries = max_entries;
+    self
+  }
+
+  /// Returns the TTL of documents of the DID method `method`.
+  pub fn ttl(&self, method: &str) -> Duration {
+    self.method_ttls.get(method).copied().unwrap_or(self.default_ttl)
+  }
+}
+
+/// Indicates whether a resolved document was served from the cache of a [`Resolver`](crate::Resolver).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheStatus {
+  /// The document was served from the cache.
+  Hit,
+  /// The document was resolved by the method handler and cached.
+  Miss,
+  /// The document was resolved by the method handler without being cached, because the resolver has no cache or
+  /// caching is disabled for the DID method.
+  Bypass,
+}
+
+/// A document resolved by a [`Resolver`](crate::Resolver) together with its [`CacheStatus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CachedResolution<DOC> {
+  /// The resolved document.
+  pub document: DOC,
+  /// Whether the document was served from the cache.
+  pub cache_status: CacheStatus,
+}
+
+struct CacheEntry<DOC> {
+  document: DOC,
+  /// Position of the entry in the insertion order.
+  sequence: u64,
+  expires_at: Instant,
+}
+
+struct CacheEntries<DOC> {
+  entries: HashMap<String, CacheEntry<DOC>>,
+  next_sequence: u64,
+}
+
+/// Cache of resolved documents.
+///
+/// The documents are cloned with the function captured on construction, so the resolver itself does not need to
+/// require [`Clone`] documents.
+pub(super) struct ResolverCache<DOC> {
+  config: ResolverCacheConfig,
+  entries: Mutex<CacheEntries<DOC>>,
+  clone_document: fn(&DOC) -> DOC,
+}
+
+impl<DOC> ResolverCache<DOC> {
+  pub(super) fn new(config: ResolverCacheConfig) -> Self
+  where
+    DOC: Clone,
+  {
+    Self {
+      config,
+      entries: Mutex::new(CacheEntries {
+        entries: HashMap::new(),
+        next_sequence: 0,
+      }),
+      clone_document: DOC::clone,
+    }
+  }
+
+  pub(super) fn config(&self) -> &ResolverCacheConfig {
+    &self.config
+  }
+
+  /// Returns a clone of the unexpired document cached for `did`.
+  pub(super) fn get(&self, did: &str) -> Option<DOC> {
+    let mut guard = self.entries();
+    let entries: &mut HashMap<String, CacheEntry<DOC>> = &mut guard.entries;
+    match entries.get(did) {
+      Some(entry) if entry.expires_at > Instant::now() => Some((self.clone_document)(&entry.document)),
+      Some(_) => {
+        entries.remove(did);
+        None
+      }
+      None => None,
+    }
+  }
+
+  /// Caches a clone of `document` for `did` and returns whether it was cached.
+  pub(super) fn insert(&self, did: &str, method: &str, document: &DOC) -> bool {
+    let ttl: Duration = self.config.ttl(method);
+    if ttl.is_zero() || self.config.max_entries == 0 {
+      return false;
+    }
+    let now: Instant = Instant::now();
+    let expires_at: Instant = match now.checked_add(ttl) {
+      Some(expires_at) => expires_at,
+      // Saturate TTLs that exceed the range of `Instant`.
+      None => now + Duration::from_secs(u32::MAX.into()),
+    };
+
+    let mut guard = self.entries();
+    let sequence: u64 = guard.next_sequence;
+    guard.next_sequence += 1;
+    let entries: &mut HashMap<String, CacheEntry<DOC>> = &mut guard.entries;
+    if !entries.contains_key(did) && entries.len() >= self.config.max_entries {
+      entries.retain(|_, entry| entry.expires_at > now);
+    }
+    if !entries.contains_key(did) && entries.len() >= self.config.max_entries {
+      let oldest: Option<String> = entries
+        .iter()
+        .min_by_key(|(_, entry)| entry.sequence)
+        .map(|(did, _)| did.clone());
+      if let Some(oldest) = oldest {
+        entries.remove(&oldest);
+      }
+    }
+
+    entries.insert(
+      did.to_owned(),
+      CacheEntry {
+        document: (self.clone_document)(document),
+        sequence,
+        expires_at,
+      },
+    );
+    true
+  }
+
+  /// Removes the document cached for `did` and returns whether it was cached.
+  pub(super) fn invalidate(&self, did: &str) -> bool {
+    self.entries().entries.remove(did).is_some()
+  }
+
+  /// Removes all documents of DIDs of the given method.
+  pub(super) fn invalidate_method(&self, method: &str) {
+    let prefix: String = format!("did:{method}:");
+    self.entries().entries.retain(|did, _| !did.starts_with(&prefix));
+  }
+
+  /// Removes all cached documents.
+  pub(super) fn clear(&self) {
+    self.entries().entries.clear();
+  }
+
+  /// Returns the number of cached documents, including expired ones that have not been evicted yet.
+  pub(super) fn len(&self) -> usize {
+    self.entries().entries.len()
+  }
+
+  fn entries(&self) -> MutexGuard<'_, CacheEntries<DOC>> {
+    // The entries are left consistent by all operations, so a poisoned lock can be recovered.
+    self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+}
+
+impl<DOC> std::fmt::Debug for ResolverCache<DOC> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ResolverCache")
+      .field("config", &self.config)
+      .field("entries", &self.len())
+      .finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_max_entries_evicts_oldest() {
+    let cache: ResolverCache<u32> =
+      ResolverCache::new(ResolverCacheConfig::new(Duration::from_secs(60)).max_entries(2));
+    assert!(cache.insert("did:foo:1", "foo", &1));
+    assert!(cache.insert("did:foo:2", "foo", &2));
+    // Replacing an entry does not evict others.
+    assert!(cache.insert("did:foo:2", "foo", &22));
+    assert_eq!(cache.len(), 2);
+
+    assert!(cache.insert("did:foo:3", "foo", &3));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get("did:foo:1"), None);
+    assert_eq!(cache.get("did:foo:2"), Some(22));
+    assert_eq!(cache.get("did:foo:3"), Some(3));
+  }
+
+  #[test]
+  fn test_method_ttl() {
+    let config: ResolverCacheConfig = ResolverCacheConfig::new(Duration::from_secs(60))
+      .method_ttl("bar", Duration::ZERO)
+      .method_ttl("baz", Duration::from_millis(1));
+    assert_eq!(config.ttl("foo"), Duration::from_secs(60));
+    let cache: ResolverCache<u32> = ResolverCache::new(config);
+
+    assert!(!cache.insert("did:bar:1", "bar", &1));
+    assert_eq!(cache.get("did:bar:1"), None);
+
+    assert!(cache.insert("did:baz:1", "baz", &1));
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(cache.get("did:baz:1"), None);
+    assert_eq!(cache.len(), 0);
+  }
+
+  #[test]
+  fn test_invalidation() {
+    let cache: ResolverCache<u32> = ResolverCache::new(ResolverCacheConfig::new(Duration::from_secs(60)));
+    cache.insert("did:foo:1", "foo", &1);
+    cache.insert("did:foo:2", "foo", &2);
+    cache.insert("did:bar:1", "bar", &3);
+
+    assert!(cache.invalidate("did:foo:1"));
+    assert!(!cache.invalidate("did:foo:1"));
+    cache.invalidate_method("foo");
+    assert_eq!(cache.get("did:foo:2"), None);
+    assert_eq!(cache.get("did:bar:1"), Some(3));
+
+    cache.clear();
+    assert_eq!(cache.len(), 0);
+  }
+}
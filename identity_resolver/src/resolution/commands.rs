@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::future::Future;
-use identity_did::DID;
 
 use crate::Error;
 use crate::ErrorCause;
@@ -63,7 +62,7 @@ impl<DOC: 'static> SendSyncCommand<DOC> {
   /// converted to the DID type required by the handler and then the handler is called.  
   pub(super) fn new<D, F, Fut, DOCUMENT, E, DIDERR>(handler: F) -> Self
   where
-    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    D: Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
     DOCUMENT: 'static + Into<DOC>,
     F: Fn(D) -> Fut + 'static + Clone + Send + Sync,
     Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
@@ -111,7 +110,7 @@ impl<DOC: 'static> SingleThreadedCommand<DOC> {
   /// Equivalent to [`SendSyncCommand::new`](SendSyncCommand::new()), but with less `Send` + `Sync` bounds.
   pub(super) fn new<D, F, Fut, DOCUMENT, E, DIDERR>(handler: F) -> Self
   where
-    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    D: for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
     DOCUMENT: 'static + Into<DOC>,
     F: Fn(D) -> Fut + 'static + Clone,
     Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
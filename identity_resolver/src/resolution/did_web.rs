@@ -0,0 +1,151 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_did::DIDWeb;
+use identity_document::document::CoreDocument;
+
+use super::Resolver;
+use super::SingleThreadedCommand;
+
+/// The maximum size of a did:web DID Document, the same limit that applies when fetching a domain linkage
+/// configuration.
+pub const MAX_DID_WEB_DOCUMENT_SIZE: usize = identity_credential::fetch::MAX_FETCH_RESPONSE_SIZE;
+
+/// Fetches the DID Documents of did:web DIDs.
+///
+/// Implementations only need to perform the HTTP request; the size limit of the response is enforced and the DID
+/// Document is validated by the handler attached with
+/// [`Resolver::attach_did_web_handler`](crate::Resolver::attach_did_web_handler()).
+#[async_trait::async_trait]
+pub trait DIDWebFetcher: Send + Sync {
+  /// Performs a GET request for `url` and returns the response body.
+  ///
+  /// Implementations should stop reading the response once it exceeds `max_size` bytes.
+  async fn fetch(&self, url: &Url, max_size: usize) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Errors that may occur when resolving a did:web DID.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum DIDWebResolutionError {
+  /// Caused by a failure of the [`DIDWebFetcher`].
+  #[error("failed to fetch the did:web document")]
+  FetchError(#[source] Box<dyn std::error::Error + Send + Sync>),
+  /// Caused by a DID Document exceeding [`MAX_DID_WEB_DOCUMENT_SIZE`].
+  #[error("did:web document can not exceed 1 MiB")]
+  DocumentTooLarge,
+  /// Caused by a response that is not a valid DID Document.
+  #[error("invalid did:web document")]
+  InvalidDocument(#[source] identity_core::Error),
+  /// Caused by a DID Document whose `id` does not match the resolved DID.
+  #[error("did:web document id `{0}` does not match the resolved did")]
+  DocumentIdMismatch(String),
+}
+
+/// Fetches and validates the DID Document of `did`.
+async fn resolve_did_web(fetcher: &dyn DIDWebFetcher, did: DIDWeb) -> Result<CoreDocument, DIDWebResolutionError> {
+  // The document url of a did:web always uses the `https` protocol.
+  let url: Url = did.document_url();
+  let json: Vec<u8> = fetcher
+    .fetch(&url, MAX_DID_WEB_DOCUMENT_SIZE)
+    .await
+    .map_err(DIDWebResolutionError::FetchError)?;
+  if json.len() > MAX_DID_WEB_DOCUMENT_SIZE {
+    return Err(DIDWebResolutionError::DocumentTooLarge);
+  }
+
+  let document: CoreDocument = CoreDocument::from_json_slice(&json).map_err(DIDWebResolutionError::InvalidDocument)?;
+  if document.id() != did.as_ref() {
+    return Err(DIDWebResolutionError::DocumentIdMismatch(document.id().to_string()));
+  }
+  Ok(document)
+}
+
+impl<DOC> Resolver<DOC>
+where
+  DOC: From<CoreDocument> + AsRef<CoreDocument> + 'static,
+{
+  /// Convenience method for attaching a new handler responsible for resolving did:web DIDs, which fetches DID
+  /// Documents with the given `fetcher`.
+  ///
+  /// Documents are only fetched over `https` and may not exceed [`MAX_DID_WEB_DOCUMENT_SIZE`]. The `id` of a fetched
+  /// document must match the resolved DID.
+  ///
+  /// See also [`attach_handler`](Self::attach_handler).
+  pub fn attach_did_web_handler<F>(&mut self, fetcher: F)
+  where
+    F: DIDWebFetcher + 'static,
+  {
+    let arc_fetcher: Arc<F> = Arc::new(fetcher);
+
+    let handler = move |did: DIDWeb| {
+      let future_fetcher = arc_fetcher.clone();
+      async move { resolve_did_web(future_fetcher.as_ref(), did).await }
+    };
+
+    self.attach_handler(DIDWeb::METHOD.to_owned(), handler);
+  }
+}
+
+impl<DOC> Resolver<DOC, SingleThreadedCommand<DOC>>
+where
+  DOC: From<CoreDocument> + AsRef<CoreDocument> + 'static,
+{
+  /// Convenience method for attaching a new handler responsible for resolving did:web DIDs, which fetches DID
+  /// Documents with the given `fetcher`.
+  ///
+  /// Documents are only fetched over `https` and may not exceed [`MAX_DID_WEB_DOCUMENT_SIZE`]. The `id` of a fetched
+  /// document must match the resolved DID.
+  ///
+  /// See also [`attach_handler`](Self::attach_handler).
+  pub fn attach_did_web_handler<F>(&mut self, fetcher: F)
+  where
+    F: DIDWebFetcher + 'static,
+  {
+    let arc_fetcher: Arc<F> = Arc::new(fetcher);
+
+    let handler = move |did: DIDWeb| {
+      let future_fetcher = arc_fetcher.clone();
+      async move { resolve_did_web(future_fetcher.as_ref(), did).await }
+    };
+
+    self.attach_handler(DIDWeb::METHOD.to_owned(), handler);
+  }
+}
+
+#[cfg(feature = "did-web-fetch")]
+mod reqwest_fetcher {
+  use identity_core::common::Url;
+
+  use super::DIDWebFetcher;
+
+  /// A [`DIDWebFetcher`] performing requests with [`reqwest`](https://docs.rs/reqwest).
+  ///
+  /// Requests are made with [`fetch_bounded`](identity_credential::fetch::fetch_bounded), which also backs
+  /// `DomainLinkageConfiguration::fetch_configuration`: only `https` requests are permitted and redirects are not
+  /// followed.
+  #[derive(Debug, Clone, Default)]
+  #[non_exhaustive]
+  pub struct ReqwestDIDWebFetcher;
+
+  impl ReqwestDIDWebFetcher {
+    /// Creates a new [`ReqwestDIDWebFetcher`].
+    pub fn new() -> Self {
+      Self
+    }
+  }
+
+  #[async_trait::async_trait]
+  impl DIDWebFetcher for ReqwestDIDWebFetcher {
+    async fn fetch(&self, url: &Url, max_size: usize) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+      identity_credential::fetch::fetch_bounded(url, max_size).await
+    }
+  }
+}
+
+#[cfg(feature = "did-web-fetch")]
+pub use reqwest_fetcher::ReqwestDIDWebFetcher;
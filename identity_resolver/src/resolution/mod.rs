@@ -1,12 +1,31 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
+mod cache;
 mod commands;
+mod did_web;
 mod resolver;
+mod result;
 #[cfg(test)]
 mod tests;
+mod version;
 
 use self::commands::SingleThreadedCommand;
+pub use cache::CacheStatus;
+pub use cache::CachedResolution;
+pub use cache::ResolverCacheConfig;
+pub use did_web::DIDWebFetcher;
+pub use did_web::DIDWebResolutionError;
+#[cfg(feature = "did-web-fetch")]
+pub use did_web::ReqwestDIDWebFetcher;
+pub use did_web::MAX_DID_WEB_DOCUMENT_SIZE;
 use identity_document::document::CoreDocument;
 pub use resolver::Resolver;
+pub use result::DIDDocumentMetadata;
+pub use result::DIDResolutionErrorCode;
+pub use result::DIDResolutionMetadata;
+pub use result::DIDResolutionResult;
+pub use result::ResolvedDocument;
+pub use result::DID_JSON_CONTENT_TYPE;
+pub use version::DIDVersionQuery;
 /// Alias for a [`Resolver`] that is not [`Send`] + [`Sync`].
 pub type SingleThreadedResolver<DOC = CoreDocument> = Resolver<DOC, SingleThreadedCommand<DOC>>;
@@ -10,6 +10,7 @@ use identity_credential::validator::FailFast;
 use identity_credential::validator::PresentationValidationOptions;
 use identity_credential::validator::PresentationValidator;
 use identity_did::CoreDID;
+use identity_did::DIDUrl;
 use identity_did::DID;
 
 use identity_document::document::CoreDocument;
@@ -21,9 +22,20 @@ use crate::Error;
 use crate::ErrorCause;
 use crate::Result;
 
+use super::cache::CacheStatus;
+use super::cache::CachedResolution;
+use super::cache::ResolverCache;
+use super::cache::ResolverCacheConfig;
 use super::commands::Command;
 use super::commands::SendSyncCommand;
 use super::commands::SingleThreadedCommand;
+use super::result::DIDDocumentMetadata;
+use super::result::DIDResolutionMetadata;
+use super::result::DIDResolutionResult;
+use super::result::ResolvedDocument;
+use super::result::DID_JSON_CONTENT_TYPE;
+use super::version::DIDVersionQuery;
+use super::version::VersionedDID;
 
 /// Convenience type for resolving DID documents from different DID methods.   
 ///  
@@ -33,11 +45,22 @@ use super::commands::SingleThreadedCommand;
 /// # Configuration
 /// The resolver will only be able to resolve DID documents for methods it has been configured for. This is done by
 /// attaching method specific handlers with [`Self::attach_handler`](Self::attach_handler()).
+///
+/// # Versioned resolution
+/// Historical versions of DID documents can be resolved from DID URLs with a `versionId` or `versionTime` query
+/// parameter, for methods with a versioned handler attached with
+/// [`Self::attach_versioned_handler`](Self::attach_versioned_handler()). See
+/// [`Self::resolve_did_url`](Self::resolve_did_url()).
+///
+/// # Caching
+/// Resolved documents can optionally be cached, see [`Self::with_cache`](Self::with_cache()).
 pub struct Resolver<DOC = CoreDocument, CMD = SendSyncCommand<DOC>>
 where
   CMD: for<'r> Command<'r, Result<DOC>>,
 {
   command_map: HashMap<String, CMD>,
+  versioned_command_map: HashMap<String, CMD>,
+  cache: Option<ResolverCache<DOC>>,
   _required: PhantomData<DOC>,
 }
 
@@ -60,10 +83,78 @@ where
   pub fn new() -> Self {
     Self {
       command_map: HashMap::new(),
+      versioned_command_map: HashMap::new(),
+      cache: None,
       _required: PhantomData::<DOC>,
     }
   }
 
+  /// Enables caching of resolved documents with the given configuration, discarding any previously cached
+  /// documents.
+  ///
+  /// Documents are cached by DID, so a cached document is returned until it expires even if the DID was updated in
+  /// the meantime. Use [`Self::invalidate`](Self::invalidate()) to evict documents known to be outdated.
+  ///
+  /// # Example
+  /// ```
+  /// # use std::time::Duration;
+  /// # use identity_resolver::Resolver;
+  /// # use identity_resolver::ResolverCacheConfig;
+  /// # use identity_document::document::CoreDocument;
+  ///
+  /// let resolver = Resolver::<CoreDocument>::new().with_cache(
+  ///   ResolverCacheConfig::new(Duration::from_secs(60))
+  ///     .method_ttl("iota", Duration::from_secs(10))
+  ///     .max_entries(100),
+  /// );
+  /// ```
+  #[must_use]
+  pub fn with_cache(mut self, config: ResolverCacheConfig) -> Self
+  where
+    DOC: Clone,
+  {
+    self.set_cache(Some(config));
+    self
+  }
+
+  /// Enables caching of resolved documents with the given configuration, or disables it if `None`.
+  ///
+  /// Previously cached documents are discarded.
+  pub fn set_cache(&mut self, config: Option<ResolverCacheConfig>)
+  where
+    DOC: Clone,
+  {
+    self.cache = config.map(ResolverCache::new);
+  }
+
+  /// Returns the configuration of the cache, if caching is enabled.
+  pub fn cache_config(&self) -> Option<&ResolverCacheConfig> {
+    self.cache.as_ref().map(ResolverCache::config)
+  }
+
+  /// Removes the cached document of the given DID and returns whether it was cached.
+  pub fn invalidate<D: DID>(&self, did: &D) -> bool {
+    self
+      .cache
+      .as_ref()
+      .map(|cache| cache.invalidate(did.as_str()))
+      .unwrap_or(false)
+  }
+
+  /// Removes the cached documents of all DIDs of the given method.
+  pub fn invalidate_method(&self, method: &str) {
+    if let Some(cache) = self.cache.as_ref() {
+      cache.invalidate_method(method);
+    }
+  }
+
+  /// Removes all cached documents.
+  pub fn clear_cache(&self) {
+    if let Some(cache) = self.cache.as_ref() {
+      cache.clear();
+    }
+  }
+
   /// Fetches the DID Document of the given DID.
   ///
   /// # Errors
@@ -95,6 +186,19 @@ where
   /// }
   /// ```
   pub async fn resolve<D: DID>(&self, did: &D) -> Result<DOC> {
+    self.resolve_cached(did).await.map(|resolution| resolution.document)
+  }
+
+  /// Fetches the DID Document of the given DID and indicates whether it was served from the cache.
+  ///
+  /// Without a cache, see [`Self::with_cache`](Self::with_cache()), this behaves like
+  /// [`Self::resolve`](Self::resolve()) and the [`CacheStatus`] is always [`CacheStatus::Bypass`]. Failed resolutions
+  /// are never cached.
+  ///
+  /// # Errors
+  /// Errors if the resolver has not been configured to handle the method corresponding to the given DID or the
+  /// resolution process itself fails.
+  pub async fn resolve_cached<D: DID>(&self, did: &D) -> Result<CachedResolution<DOC>> {
     let method = did.method();
     let delegate = self
       .command_map
@@ -104,7 +208,99 @@ where
       })
       .map_err(Error::new)?;
 
-    delegate.apply(did.as_str()).await
+    if let Some(document) = self.cache.as_ref().and_then(|cache| cache.get(did.as_str())) {
+      return Ok(CachedResolution {
+        document,
+        cache_status: CacheStatus::Hit,
+      });
+    }
+
+    let document: DOC = delegate.apply(did.as_str()).await?;
+    let cache_status: CacheStatus = match self.cache {
+      Some(ref cache) if cache.insert(did.as_str(), method, &document) => CacheStatus::Miss,
+      _ => CacheStatus::Bypass,
+    };
+
+    Ok(CachedResolution { document, cache_status })
+  }
+
+  /// Fetches the DID Document of the DID of the given DID URL, in the version selected by its `versionId` or
+  /// `versionTime` query parameter.
+  ///
+  /// If the URL selects no version, the latest DID Document is resolved like with [`Self::resolve`](Self::resolve()).
+  /// Versioned documents are never cached.
+  ///
+  /// # Errors
+  /// Errors if the query parameters are invalid, the resolver has not been configured to handle versioned resolution
+  /// for the method of the DID, or the resolution process itself fails.
+  pub async fn resolve_did_url(&self, did_url: &DIDUrl) -> Result<DOC> {
+    let version: Option<DIDVersionQuery> = DIDVersionQuery::from_did_url(did_url)
+      .map_err(|source| ErrorCause::DIDParsingError { source })
+      .map_err(Error::new)?;
+    if version.is_none() {
+      return self.resolve(did_url.did()).await;
+    }
+
+    let method: &str = did_url.did().method();
+    let delegate = self
+      .versioned_command_map
+      .get(method)
+      .ok_or_else(|| ErrorCause::UnsupportedVersionedMethodError {
+        method: method.to_owned(),
+      })
+      .map_err(Error::new)?;
+
+    let input: String = did_url.to_string();
+    delegate.apply(&input).await
+  }
+
+  /// Resolves the given DID into a [`DIDResolutionResult`], containing the DID document together with metadata about
+  /// the document and the resolution process.
+  ///
+  /// Unlike [`Self::resolve`](Self::resolve()) this never errors: failures are reported by the
+  /// [`DIDResolutionErrorCode`](crate::DIDResolutionErrorCode) in the resolution metadata, in which case no document
+  /// is returned. This makes the result suitable to be served as is, e.g. by a DID resolution HTTP endpoint.
+  ///
+  /// ## Example
+  /// ```
+  /// # use identity_resolver::DIDResolutionErrorCode;
+  /// # use identity_resolver::DIDResolutionResult;
+  /// # use identity_resolver::Resolver;
+  ///
+  /// # async fn example() {
+  /// let resolver: Resolver = Resolver::new();
+  /// let result: DIDResolutionResult = resolver.resolve_with_metadata("did:foo:123").await;
+  /// assert_eq!(result.error(), Some(DIDResolutionErrorCode::MethodNotSupported));
+  /// # }
+  /// ```
+  pub async fn resolve_with_metadata(&self, did: &str) -> DIDResolutionResult<DOC>
+  where
+    DOC: ResolvedDocument,
+  {
+    let resolution: Result<DOC> = match CoreDID::parse(did) {
+      Ok(did) => self.resolve(&did).await,
+      Err(error) => Err(Error::new(ErrorCause::DIDParsingError { source: error.into() })),
+    };
+
+    match resolution {
+      Ok(document) => DIDResolutionResult {
+        did_resolution_metadata: DIDResolutionMetadata {
+          content_type: Some(DID_JSON_CONTENT_TYPE.to_owned()),
+          ..Default::default()
+        },
+        did_document_metadata: document.did_document_metadata(),
+        did_document: Some(document),
+      },
+      Err(error) => DIDResolutionResult {
+        did_resolution_metadata: DIDResolutionMetadata {
+          content_type: None,
+          error: Some(error.error_cause().into()),
+          error_message: Some(error.to_string()),
+        },
+        did_document: None,
+        did_document_metadata: DIDDocumentMetadata::default(),
+      },
+    }
   }
 
   /// Fetches all DID Documents of [`Credential`] issuers contained in a [`Presentation`].
@@ -305,6 +501,27 @@ impl<DOC: 'static> Resolver<DOC, SendSyncCommand<DOC>> {
     let command = SendSyncCommand::new(handler);
     self.command_map.insert(method, command);
   }
+
+  /// Attach a new handler responsible for resolving historical versions of DID documents of the given DID method.
+  ///
+  /// The `handler` is expected to be a closure taking an owned DID and the [`DIDVersionQuery`] parsed from the query
+  /// of the DID URL passed to [`Self::resolve_did_url`](Self::resolve_did_url()), and asynchronously returning the
+  /// selected version of the DID Document. See [`Self::attach_handler`](Self::attach_handler()) for the requirements
+  /// on `handler`.
+  ///
+  /// NOTE: If there already exists a versioned handler for this method then it will be replaced with the new handler.
+  pub fn attach_versioned_handler<D, F, Fut, DOCUMENT, E, DIDERR>(&mut self, method: String, handler: F)
+  where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, DIDVersionQuery) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    let command = SendSyncCommand::new(move |versioned: VersionedDID<D>| handler(versioned.did, versioned.version));
+    self.versioned_command_map.insert(method, command);
+  }
 }
 
 impl<DOC: 'static> Resolver<DOC, SingleThreadedCommand<DOC>> {
@@ -364,14 +581,115 @@ impl<DOC: 'static> Resolver<DOC, SingleThreadedCommand<DOC>> {
     let command = SingleThreadedCommand::new(handler);
     self.command_map.insert(method, command);
   }
+
+  /// Attach a new handler responsible for resolving historical versions of DID documents of the given DID method.
+  ///
+  /// See [`Resolver::attach_versioned_handler`](Resolver::attach_versioned_handler()) and
+  /// [`Self::attach_handler`](Self::attach_handler()).
+  pub fn attach_versioned_handler<D, F, Fut, DOCUMENT, E, DIDERR>(&mut self, method: String, handler: F)
+  where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, DIDVersionQuery) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    let command =
+      SingleThreadedCommand::new(move |versioned: VersionedDID<D>| handler(versioned.did, versioned.version));
+    self.versioned_command_map.insert(method, command);
+  }
+}
+
+mod did_key_handler {
+  use super::Resolver;
+  use super::SingleThreadedCommand;
+  use identity_did::DIDKey;
+  use identity_document::document::CoreDocument;
+
+  impl<DOC> Resolver<DOC>
+  where
+    DOC: From<CoreDocument> + AsRef<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a new handler responsible for resolving did:key DIDs.
+    ///
+    /// Resolution is performed offline by expanding the public key encoded in the DID, see
+    /// [`CoreDocument::expand_did_key`].
+    ///
+    /// See also [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_key_handler(&mut self) {
+      self.attach_handler(DIDKey::METHOD.to_owned(), resolve_did_key);
+    }
+  }
+
+  impl<DOC> Resolver<DOC, SingleThreadedCommand<DOC>>
+  where
+    DOC: From<CoreDocument> + AsRef<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a new handler responsible for resolving did:key DIDs.
+    ///
+    /// Resolution is performed offline by expanding the public key encoded in the DID, see
+    /// [`CoreDocument::expand_did_key`].
+    ///
+    /// See also [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_key_handler(&mut self) {
+      self.attach_handler(DIDKey::METHOD.to_owned(), resolve_did_key);
+    }
+  }
+
+  async fn resolve_did_key(did: DIDKey) -> identity_document::Result<CoreDocument> {
+    CoreDocument::expand_did_key(did)
+  }
+}
+
+mod did_jwk_handler {
+  use super::Resolver;
+  use super::SingleThreadedCommand;
+  use identity_did::DIDJwk;
+  use identity_document::document::CoreDocument;
+
+  impl<DOC> Resolver<DOC>
+  where
+    DOC: From<CoreDocument> + AsRef<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a new handler responsible for resolving did:jwk DIDs.
+    ///
+    /// Resolution is performed offline by expanding the JWK encoded in the DID, see
+    /// [`CoreDocument::expand_did_jwk`].
+    ///
+    /// See also [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_jwk_handler(&mut self) {
+      self.attach_handler(DIDJwk::METHOD.to_owned(), resolve_did_jwk);
+    }
+  }
+
+  impl<DOC> Resolver<DOC, SingleThreadedCommand<DOC>>
+  where
+    DOC: From<CoreDocument> + AsRef<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a new handler responsible for resolving did:jwk DIDs.
+    ///
+    /// Resolution is performed offline by expanding the JWK encoded in the DID, see
+    /// [`CoreDocument::expand_did_jwk`].
+    ///
+    /// See also [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_jwk_handler(&mut self) {
+      self.attach_handler(DIDJwk::METHOD.to_owned(), resolve_did_jwk);
+    }
+  }
+
+  async fn resolve_did_jwk(did: DIDJwk) -> identity_document::Result<CoreDocument> {
+    CoreDocument::expand_did_jwk(did)
+  }
 }
 
 #[cfg(feature = "iota")]
 mod iota_handler {
+  use super::DIDVersionQuery;
   use super::Resolver;
   use identity_document::document::CoreDocument;
-  use identity_iota_core::IotaClientExt;
   use identity_iota_core::IotaDID;
+  use identity_iota_core::IotaDIDVersion;
   use identity_iota_core::IotaDocument;
   use identity_iota_core::IotaIdentityClientExt;
   use std::sync::Arc;
@@ -382,19 +700,36 @@ mod iota_handler {
   {
     /// Convenience method for attaching a new handler responsible for resolving IOTA DIDs.
     ///
-    /// See also [`attach_handler`](Self::attach_handler).
+    /// A versioned handler is attached as well, resolving a `versionId`, which is either a state index or the id of
+    /// an Alias Output, or a `versionTime` from the Alias Output history.
+    ///
+    /// See also [`attach_handler`](Self::attach_handler) and
+    /// [`attach_versioned_handler`](Self::attach_versioned_handler).
     pub fn attach_iota_handler<CLI>(&mut self, client: CLI)
     where
-      CLI: IotaClientExt + Send + Sync + 'static,
+      CLI: IotaIdentityClientExt + Send + Sync + 'static,
     {
       let arc_client: Arc<CLI> = Arc::new(client);
+      let versioned_client: Arc<CLI> = arc_client.clone();
 
       let handler = move |did: IotaDID| {
         let future_client = arc_client.clone();
         async move { future_client.resolve_did(&did).await }
       };
 
+      let versioned_handler = move |did: IotaDID, version: DIDVersionQuery| {
+        let future_client = versioned_client.clone();
+        async move {
+          let version: IotaDIDVersion = match version {
+            DIDVersionQuery::VersionId(version_id) => IotaDIDVersion::parse_version_id(&version_id)?,
+            DIDVersionQuery::VersionTime(time) => IotaDIDVersion::Time(time),
+          };
+          future_client.resolve_did_version(&did, &version).await
+        }
+      };
+
       self.attach_handler(IotaDID::METHOD.to_owned(), handler);
+      self.attach_versioned_handler(IotaDID::METHOD.to_owned(), versioned_handler);
     }
   }
 }
@@ -417,6 +752,8 @@ where
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     f.debug_struct("Resolver")
       .field("command_map", &self.command_map)
+      .field("versioned_command_map", &self.versioned_command_map)
+      .field("cache", &self.cache)
       .finish()
   }
 }
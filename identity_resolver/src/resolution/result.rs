@@ -0,0 +1,177 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_document::document::CoreDocument;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ErrorCause;
+
+/// The media type of DID documents represented as plain JSON.
+pub const DID_JSON_CONTENT_TYPE: &str = "application/did+json";
+
+/// The result of resolving a DID, following the
+/// [DID Resolution](https://w3c-ccg.github.io/did-resolution/#did-resolution-result) data model.
+///
+/// Returned by [`Resolver::resolve_with_metadata`](crate::Resolver::resolve_with_metadata()).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DIDResolutionResult<DOC = CoreDocument> {
+  /// Metadata about the resolution process.
+  #[serde(rename = "didResolutionMetadata")]
+  pub did_resolution_metadata: DIDResolutionMetadata,
+  /// The resolved DID document, or `None` if resolution failed.
+  #[serde(rename = "didDocument")]
+  pub did_document: Option<DOC>,
+  /// Metadata about the resolved DID document.
+  #[serde(rename = "didDocumentMetadata")]
+  pub did_document_metadata: DIDDocumentMetadata,
+}
+
+impl<DOC> DIDResolutionResult<DOC> {
+  /// Returns the error code of a failed resolution, if any.
+  pub fn error(&self) -> Option<DIDResolutionErrorCode> {
+    self.did_resolution_metadata.error
+  }
+}
+
+/// Metadata about the resolution process of a [`DIDResolutionResult`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DIDResolutionMetadata {
+  /// The media type of the resolved DID document, only set when resolution succeeded.
+  #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
+  pub content_type: Option<String>,
+  /// The error code, only set when resolution failed.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<DIDResolutionErrorCode>,
+  /// A human-readable description of the error, only set when resolution failed.
+  #[serde(rename = "errorMessage", skip_serializing_if = "Option::is_none")]
+  pub error_message: Option<String>,
+}
+
+/// The error codes of failed DID resolutions defined by the
+/// [DID Specification Registries](https://www.w3.org/TR/did-spec-registries/#error).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum DIDResolutionErrorCode {
+  /// The DID is not a valid DID.
+  InvalidDid,
+  /// The DID document was not found.
+  NotFound,
+  /// The DID method is not supported by the resolver.
+  MethodNotSupported,
+  /// An unexpected error occurred during resolution.
+  InternalError,
+}
+
+impl From<&ErrorCause> for DIDResolutionErrorCode {
+  /// Maps the cause of a failed resolution to its error code.
+  ///
+  /// An [`ErrorCause::HandlerError`] is only reported as [`DIDResolutionErrorCode::NotFound`] if the handler error or
+  /// one of its sources indicates that the DID does not exist, i.e. it is a [`std::io::Error`] of kind
+  /// [`NotFound`](std::io::ErrorKind::NotFound) or, with the `iota` feature, a not found error of the IOTA client.
+  /// All other handler errors are reported as [`DIDResolutionErrorCode::InternalError`].
+  fn from(cause: &ErrorCause) -> Self {
+    match cause {
+      ErrorCause::DIDParsingError { .. } => Self::InvalidDid,
+      ErrorCause::HandlerError { source } if is_not_found(source.as_ref()) => Self::NotFound,
+      ErrorCause::UnsupportedMethodError { .. } | ErrorCause::UnsupportedVersionedMethodError { .. } => {
+        Self::MethodNotSupported
+      }
+      _ => Self::InternalError,
+    }
+  }
+}
+
+/// Returns `true` if `error` or one of its sources indicates that the resolved DID does not exist.
+fn is_not_found(error: &(dyn std::error::Error + 'static)) -> bool {
+  let mut current: Option<&(dyn std::error::Error + 'static)> = Some(error);
+  while let Some(error) = current {
+    if let Some(error) = error.downcast_ref::<std::io::Error>() {
+      if error.kind() == std::io::ErrorKind::NotFound {
+        return true;
+      }
+    }
+    #[cfg(feature = "iota")]
+    if let Some(error) = error.downcast_ref::<identity_iota_core::Error>() {
+      if error.is_not_found() {
+        return true;
+      }
+    }
+    current = error.source();
+  }
+  false
+}
+
+/// Metadata about a resolved DID document, see
+/// [DID Document Metadata](https://www.w3.org/TR/did-core/#did-document-metadata).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DIDDocumentMetadata {
+  /// The time the DID was created.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub created: Option<Timestamp>,
+  /// The time of the last update of the DID document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub updated: Option<Timestamp>,
+  /// Whether the DID has been deactivated.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub deactivated: Option<bool>,
+  /// Identifies the version of the resolved DID document.
+  #[serde(rename = "versionId", skip_serializing_if = "Option::is_none")]
+  pub version_id: Option<String>,
+  /// Identifies the next version of the resolved DID document, if it has been updated since.
+  #[serde(rename = "nextVersionId", skip_serializing_if = "Option::is_none")]
+  pub next_version_id: Option<String>,
+  /// Method-specific metadata.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+/// A DID document type that can be returned in a [`DIDResolutionResult`].
+pub trait ResolvedDocument {
+  /// Returns the [`DIDDocumentMetadata`] of the document.
+  fn did_document_metadata(&self) -> DIDDocumentMetadata;
+}
+
+impl ResolvedDocument for CoreDocument {
+  /// A [`CoreDocument`] carries no metadata, so this returns empty metadata.
+  fn did_document_metadata(&self) -> DIDDocumentMetadata {
+    DIDDocumentMetadata::default()
+  }
+}
+
+#[cfg(feature = "iota")]
+mod iota_document {
+  use identity_core::common::Object;
+  use identity_iota_core::IotaDocument;
+  use identity_iota_core::IotaDocumentMetadata;
+
+  use super::DIDDocumentMetadata;
+  use super::ResolvedDocument;
+
+  impl ResolvedDocument for IotaDocument {
+    /// Maps the [`IotaDocumentMetadata`], keeping the governor and state controller addresses as method-specific
+    /// metadata.
+    fn did_document_metadata(&self) -> DIDDocumentMetadata {
+      let metadata: &IotaDocumentMetadata = &self.metadata;
+      let mut properties: Object = metadata.properties.clone();
+      if let Some(ref address) = metadata.governor_address {
+        properties.insert("governorAddress".to_owned(), address.clone().into());
+      }
+      if let Some(ref address) = metadata.state_controller_address {
+        properties.insert("stateControllerAddress".to_owned(), address.clone().into());
+      }
+
+      DIDDocumentMetadata {
+        created: metadata.created,
+        updated: metadata.updated,
+        deactivated: metadata.deactivated,
+        version_id: metadata.version_id.clone(),
+        next_version_id: metadata.next_version_id.clone(),
+        properties,
+      }
+    }
+  }
+}
@@ -0,0 +1,119 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use identity_did::CoreDID;
+use identity_document::document::CoreDocument;
+use identity_document::document::DocumentBuilder;
+
+use crate::CacheStatus;
+use crate::Resolver;
+use crate::ResolverCacheConfig;
+use crate::SingleThreadedResolver;
+
+fn core_document(did: CoreDID) -> CoreDocument {
+  DocumentBuilder::default().id(did).build().unwrap()
+}
+
+fn did(did: &str) -> CoreDID {
+  CoreDID::parse(did).unwrap()
+}
+
+#[tokio::test]
+async fn send_sync_resolver_caches_documents() {
+  let calls: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+  let handler_calls: Arc<AtomicUsize> = calls.clone();
+  let mut resolver: Resolver<CoreDocument> =
+    Resolver::new().with_cache(ResolverCacheConfig::new(Duration::from_secs(60)).method_ttl("bar", Duration::ZERO));
+  let handler = move |did: CoreDID| {
+    handler_calls.fetch_add(1, Ordering::SeqCst);
+    async move { Ok::<_, std::io::Error>(core_document(did)) }
+  };
+  resolver.attach_handler("foo".to_owned(), handler.clone());
+  resolver.attach_handler("bar".to_owned(), handler);
+
+  let foo: CoreDID = did("did:foo:1234");
+  let first = resolver.resolve_cached(&foo).await.unwrap();
+  assert_eq!(first.cache_status, CacheStatus::Miss);
+  let second = resolver.resolve_cached(&foo).await.unwrap();
+  assert_eq!(second.cache_status, CacheStatus::Hit);
+  assert_eq!(first.document, second.document);
+  assert_eq!(resolver.resolve(&foo).await.unwrap().id(), &foo);
+  assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+  // Caching is disabled for the "bar" method.
+  let bar: CoreDID = did("did:bar:1234");
+  for _ in 0..2 {
+    let resolution = resolver.resolve_cached(&bar).await.unwrap();
+    assert_eq!(resolution.cache_status, CacheStatus::Bypass);
+  }
+  assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+  assert!(resolver.invalidate(&foo));
+  assert!(!resolver.invalidate(&foo));
+  let resolution = resolver.resolve_cached(&foo).await.unwrap();
+  assert_eq!(resolution.cache_status, CacheStatus::Miss);
+  assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+  resolver.invalidate_method("foo");
+  let resolution = resolver.resolve_cached(&foo).await.unwrap();
+  assert_eq!(resolution.cache_status, CacheStatus::Miss);
+
+  resolver.clear_cache();
+  let resolution = resolver.resolve_cached(&foo).await.unwrap();
+  assert_eq!(resolution.cache_status, CacheStatus::Miss);
+  assert_eq!(calls.load(Ordering::SeqCst), 6);
+}
+
+#[tokio::test]
+async fn single_threaded_resolver_caches_documents() {
+  let calls: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+  let handler_calls: Rc<Cell<usize>> = calls.clone();
+  let mut resolver: SingleThreadedResolver<CoreDocument> =
+    SingleThreadedResolver::new().with_cache(ResolverCacheConfig::new(Duration::from_millis(1)));
+  resolver.attach_handler("foo".to_owned(), move |did: CoreDID| {
+    handler_calls.set(handler_calls.get() + 1);
+    async move { Ok::<_, std::io::Error>(core_document(did)) }
+  });
+
+  let foo: CoreDID = did("did:foo:1234");
+  let resolution = resolver.resolve_cached(&foo).await.unwrap();
+  assert_eq!(resolution.cache_status, CacheStatus::Miss);
+
+  // The cached document expires.
+  tokio::time::sleep(Duration::from_millis(5)).await;
+  let resolution = resolver.resolve_cached(&foo).await.unwrap();
+  assert_eq!(resolution.cache_status, CacheStatus::Miss);
+  assert_eq!(calls.get(), 2);
+}
+
+#[tokio::test]
+async fn resolver_without_cache_bypasses() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("foo".to_owned(), |did: CoreDID| async move {
+    Ok::<_, std::io::Error>(core_document(did))
+  });
+  assert!(resolver.cache_config().is_none());
+
+  let foo: CoreDID = did("did:foo:1234");
+  for _ in 0..2 {
+    let resolution = resolver.resolve_cached(&foo).await.unwrap();
+    assert_eq!(resolution.cache_status, CacheStatus::Bypass);
+  }
+  assert!(!resolver.invalidate(&foo));
+
+  // Failed resolutions are not cached.
+  let mut resolver: Resolver<CoreDocument> =
+    Resolver::new().with_cache(ResolverCacheConfig::new(Duration::from_secs(60)));
+  resolver.attach_handler("foo".to_owned(), |_: CoreDID| async move {
+    Err::<CoreDocument, _>(std::io::Error::new(std::io::ErrorKind::Other, "unavailable"))
+  });
+  assert!(resolver.resolve_cached(&foo).await.is_err());
+  assert!(!resolver.invalidate(&foo));
+}
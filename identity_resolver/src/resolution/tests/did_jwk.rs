@@ -0,0 +1,40 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::CoreDID;
+use identity_did::DIDJwk;
+use identity_document::document::CoreDocument;
+
+use crate::ErrorCause;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+const P256: &str = "did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9";
+
+#[tokio::test]
+async fn resolves_did_jwk() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_jwk_handler();
+
+  let did: DIDJwk = DIDJwk::parse(P256).unwrap();
+  let document: CoreDocument = resolver.resolve(&did).await.unwrap();
+  assert_eq!(document, CoreDocument::expand_did_jwk(did).unwrap());
+
+  let mut single_threaded: SingleThreadedResolver<CoreDocument> = SingleThreadedResolver::new();
+  single_threaded.attach_did_jwk_handler();
+  assert_eq!(
+    single_threaded.resolve(&CoreDID::parse(P256).unwrap()).await.unwrap(),
+    document
+  );
+}
+
+#[tokio::test]
+async fn rejects_invalid_did_jwk() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_jwk_handler();
+
+  // The method-specific id does not encode a JWK.
+  let did: CoreDID = CoreDID::parse("did:jwk:eyJmb28iOiJiYXIifQ").unwrap();
+  let error: ErrorCause = resolver.resolve(&did).await.unwrap_err().into_error_cause();
+  assert!(matches!(error, ErrorCause::DIDParsingError { .. }));
+}
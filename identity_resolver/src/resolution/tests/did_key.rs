@@ -0,0 +1,43 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::CoreDID;
+use identity_did::DIDKey;
+use identity_document::document::CoreDocument;
+
+use crate::ErrorCause;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+const ED25519: &str = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+
+#[tokio::test]
+async fn resolves_did_key() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_key_handler();
+
+  let did: DIDKey = DIDKey::parse(ED25519).unwrap();
+  let document: CoreDocument = resolver.resolve(&did).await.unwrap();
+  assert_eq!(document, CoreDocument::expand_did_key(did).unwrap());
+
+  let mut single_threaded: SingleThreadedResolver<CoreDocument> = SingleThreadedResolver::new();
+  single_threaded.attach_did_key_handler();
+  assert_eq!(
+    single_threaded
+      .resolve(&CoreDID::parse(ED25519).unwrap())
+      .await
+      .unwrap(),
+    document
+  );
+}
+
+#[tokio::test]
+async fn rejects_invalid_did_key() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_key_handler();
+
+  // Unsupported multibase encoding.
+  let did: CoreDID = CoreDID::parse("did:key:f6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp").unwrap();
+  let error: ErrorCause = resolver.resolve(&did).await.unwrap_err().into_error_cause();
+  assert!(matches!(error, ErrorCause::DIDParsingError { .. }));
+}
@@ -0,0 +1,135 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use identity_core::common::Url;
+use identity_core::convert::ToJson;
+use identity_did::CoreDID;
+use identity_did::DIDWeb;
+use identity_document::document::CoreDocument;
+use identity_document::document::DocumentBuilder;
+
+use crate::DIDWebFetcher;
+use crate::DIDWebResolutionError;
+use crate::ErrorCause;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+use crate::MAX_DID_WEB_DOCUMENT_SIZE;
+
+/// A stand-in for a web server, serving fixed responses by URL.
+#[derive(Default)]
+struct MockFetcher {
+  responses: HashMap<String, Vec<u8>>,
+}
+
+impl MockFetcher {
+  fn serve(mut self, url: &str, body: impl Into<Vec<u8>>) -> Self {
+    self.responses.insert(url.to_owned(), body.into());
+    self
+  }
+}
+
+#[async_trait::async_trait]
+impl DIDWebFetcher for MockFetcher {
+  async fn fetch(&self, url: &Url, _max_size: usize) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    self
+      .responses
+      .get(url.as_str())
+      .cloned()
+      .ok_or_else(|| "not found".into())
+  }
+}
+
+fn document_json(did: &str) -> Vec<u8> {
+  let document: CoreDocument = DocumentBuilder::default()
+    .id(CoreDID::parse(did).unwrap())
+    .build()
+    .unwrap();
+  document.to_json_vec().unwrap()
+}
+
+fn resolver(fetcher: MockFetcher) -> Resolver<CoreDocument> {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_web_handler(fetcher);
+  resolver
+}
+
+fn handler_error(error: crate::Error) -> DIDWebResolutionError {
+  match error.into_error_cause() {
+    ErrorCause::HandlerError { source } => *source.downcast::<DIDWebResolutionError>().unwrap(),
+    other => panic!("unexpected error: {other}"),
+  }
+}
+
+fn fetcher() -> MockFetcher {
+  MockFetcher::default()
+    .serve(
+      "https://example.com/.well-known/did.json",
+      document_json("did:web:example.com"),
+    )
+    .serve(
+      "https://example.com:3000/user/alice/did.json",
+      document_json("did:web:example.com%3A3000:user:alice"),
+    )
+}
+
+#[tokio::test]
+async fn resolves_did_web() {
+  let resolver: Resolver<CoreDocument> = resolver(fetcher());
+  let mut single_threaded: SingleThreadedResolver<CoreDocument> = SingleThreadedResolver::new();
+  single_threaded.attach_did_web_handler(fetcher());
+
+  for did in ["did:web:example.com", "did:web:example.com%3A3000:user:alice"] {
+    let did: DIDWeb = DIDWeb::parse(did).unwrap();
+    let document: CoreDocument = resolver.resolve(&did).await.unwrap();
+    assert_eq!(document.id(), did.as_ref());
+    assert_eq!(single_threaded.resolve(&did).await.unwrap(), document);
+  }
+}
+
+#[tokio::test]
+async fn rejects_invalid_documents() {
+  let fetcher: MockFetcher = MockFetcher::default()
+    .serve(
+      "https://example.com/.well-known/did.json",
+      document_json("did:web:example.org"),
+    )
+    .serve("https://example.com/invalid/did.json", "{}")
+    .serve(
+      "https://example.com/large/did.json",
+      vec![b' '; MAX_DID_WEB_DOCUMENT_SIZE + 1],
+    );
+  let resolver: Resolver<CoreDocument> = resolver(fetcher);
+
+  let error = resolver
+    .resolve(&DIDWeb::parse("did:web:example.com").unwrap())
+    .await
+    .unwrap_err();
+  assert!(matches!(
+    handler_error(error),
+    DIDWebResolutionError::DocumentIdMismatch(_)
+  ));
+
+  let error = resolver
+    .resolve(&DIDWeb::parse("did:web:example.com:invalid").unwrap())
+    .await
+    .unwrap_err();
+  assert!(matches!(
+    handler_error(error),
+    DIDWebResolutionError::InvalidDocument(_)
+  ));
+
+  let error = resolver
+    .resolve(&DIDWeb::parse("did:web:example.com:large").unwrap())
+    .await
+    .unwrap_err();
+  assert!(matches!(handler_error(error), DIDWebResolutionError::DocumentTooLarge));
+
+  let error = resolver
+    .resolve(&DIDWeb::parse("did:web:example.com:missing").unwrap())
+    .await
+    .unwrap_err();
+  assert!(matches!(handler_error(error), DIDWebResolutionError::FetchError(_)));
+}
@@ -0,0 +1,63 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Url;
+use identity_did::DIDUrl;
+use identity_did::DID;
+use identity_iota_core::block::address::Address;
+use identity_iota_core::block::address::Ed25519Address;
+use identity_iota_core::block::output::AliasOutput;
+use identity_iota_core::IotaDID;
+use identity_iota_core::IotaDocument;
+use identity_iota_core::IotaIdentityClientExt;
+use identity_iota_core::MemoryLedger;
+
+use crate::DIDResolutionErrorCode;
+use crate::DIDResolutionResult;
+use crate::Resolver;
+
+/// Publishes a DID on `ledger` and updates it once, returning the DID.
+async fn publish_did(ledger: &MemoryLedger) -> IotaDID {
+  let address: Address = Address::Ed25519(Ed25519Address::new([1; 32]));
+
+  let document: IotaDocument = IotaDocument::new(&ledger.network_name().unwrap());
+  let alias_output: AliasOutput = ledger.new_did_output(address, document, None).await.unwrap();
+  let mut document: IotaDocument = ledger.publish_did_output(&address, alias_output).unwrap();
+
+  document
+    .also_known_as_mut()
+    .append(Url::parse("did:example:abc").unwrap());
+  let alias_output: AliasOutput = ledger.update_did_output(document.clone()).await.unwrap();
+  ledger.publish_did_output(&address, alias_output).unwrap();
+
+  document.id().clone()
+}
+
+#[tokio::test]
+async fn resolves_from_memory_ledger() {
+  let ledger: MemoryLedger = MemoryLedger::new();
+  let did: IotaDID = publish_did(&ledger).await;
+  let mut resolver: Resolver<IotaDocument> = Resolver::new();
+  resolver.attach_iota_handler(ledger);
+
+  let document: IotaDocument = resolver.resolve(&did).await.unwrap();
+  assert_eq!(document.id(), &did);
+  assert_eq!(document.also_known_as().len(), 1);
+
+  // Resolve the first version.
+  let did_url: DIDUrl = DIDUrl::parse(format!("{did}?versionId=0")).unwrap();
+  let first_version: IotaDocument = resolver.resolve_did_url(&did_url).await.unwrap();
+  assert!(first_version.also_known_as().is_empty());
+  assert!(first_version.metadata.next_version_id.is_some());
+
+  let result: DIDResolutionResult<IotaDocument> = resolver.resolve_with_metadata(did.as_str()).await;
+  assert_eq!(result.error(), None);
+  assert_eq!(
+    result.did_document_metadata.properties["governorAddress"],
+    document.metadata.governor_address.unwrap()
+  );
+
+  let unknown: IotaDID = IotaDID::new(&[2; 32], &did.network_str().to_owned().try_into().unwrap());
+  let result: DIDResolutionResult<IotaDocument> = resolver.resolve_with_metadata(unknown.as_str()).await;
+  assert_eq!(result.error(), Some(DIDResolutionErrorCode::NotFound));
+}
@@ -2,9 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::resolver::*;
+mod caching;
+mod did_jwk;
+mod did_key;
+mod did_web;
 mod resolution_errors;
+mod resolution_result;
 mod send_sync;
+mod versioned_resolution;
 
+#[cfg(feature = "iota")]
+mod memory_ledger;
 #[cfg(feature = "iota")]
 mod successful_presentation_validation;
 #[cfg(feature = "iota")]
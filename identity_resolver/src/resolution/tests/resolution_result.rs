@@ -0,0 +1,130 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::convert::ToJson;
+use identity_did::CoreDID;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+
+use crate::DIDDocumentMetadata;
+use crate::DIDResolutionErrorCode;
+use crate::DIDResolutionResult;
+use crate::Resolver;
+use crate::DID_JSON_CONTENT_TYPE;
+
+const DID: &str = "did:example:123";
+
+async fn handler(did: CoreDID) -> std::result::Result<CoreDocument, std::io::Error> {
+  match did.method_id() {
+    "123" => Ok(CoreDocument::builder(Default::default()).id(did).build().unwrap()),
+    "789" => Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out")),
+    _ => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "not found")),
+  }
+}
+
+#[tokio::test]
+async fn resolves_with_metadata() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("example".to_owned(), handler);
+
+  let result: DIDResolutionResult = resolver.resolve_with_metadata(DID).await;
+  assert_eq!(result.error(), None);
+  assert_eq!(
+    result.did_resolution_metadata.content_type.as_deref(),
+    Some(DID_JSON_CONTENT_TYPE)
+  );
+  assert_eq!(
+    result.did_document,
+    Some(resolver.resolve(&CoreDID::parse(DID).unwrap()).await.unwrap())
+  );
+  assert_eq!(result.did_document_metadata, DIDDocumentMetadata::default());
+
+  let json = result.to_json_value().unwrap();
+  assert_eq!(json["didResolutionMetadata"]["contentType"], DID_JSON_CONTENT_TYPE);
+  assert_eq!(json["didDocument"]["id"], DID);
+  assert!(json["didDocumentMetadata"].as_object().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn reports_error_codes() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("example".to_owned(), handler);
+
+  for (did, code) in [
+    ("did:example", DIDResolutionErrorCode::InvalidDid),
+    ("did:example:456", DIDResolutionErrorCode::NotFound),
+    ("did:example:789", DIDResolutionErrorCode::InternalError),
+    ("did:foo:123", DIDResolutionErrorCode::MethodNotSupported),
+  ] {
+    let result: DIDResolutionResult = resolver.resolve_with_metadata(did).await;
+    assert_eq!(result.error(), Some(code));
+    assert!(result.did_resolution_metadata.content_type.is_none());
+    assert!(result.did_resolution_metadata.error_message.is_some());
+    assert!(result.did_document.is_none());
+  }
+
+  let result: DIDResolutionResult = resolver.resolve_with_metadata("did:foo:123").await;
+  let json = result.to_json_value().unwrap();
+  assert_eq!(json["didResolutionMetadata"]["error"], "methodNotSupported");
+  assert!(json["didDocument"].is_null());
+}
+
+#[tokio::test]
+async fn reports_not_found_only_for_not_found_sources() {
+  #[derive(Debug, thiserror::Error)]
+  #[error("handler failed")]
+  struct HandlerError(#[source] std::io::Error);
+
+  async fn wrapping_handler(did: CoreDID) -> std::result::Result<CoreDocument, HandlerError> {
+    handler(did).await.map_err(HandlerError)
+  }
+
+  async fn failing_handler(_: CoreDID) -> std::result::Result<CoreDocument, std::fmt::Error> {
+    Err(std::fmt::Error)
+  }
+
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("example".to_owned(), wrapping_handler);
+  resolver.attach_handler("other".to_owned(), failing_handler);
+
+  for (did, code) in [
+    ("did:example:456", DIDResolutionErrorCode::NotFound),
+    ("did:example:789", DIDResolutionErrorCode::InternalError),
+    ("did:other:123", DIDResolutionErrorCode::InternalError),
+  ] {
+    let result: DIDResolutionResult = resolver.resolve_with_metadata(did).await;
+    assert_eq!(result.error(), Some(code));
+  }
+
+  let result: DIDResolutionResult = resolver.resolve_with_metadata("did:other:123").await;
+  let json = result.to_json_value().unwrap();
+  assert_eq!(json["didResolutionMetadata"]["error"], "internalError");
+}
+
+#[cfg(feature = "iota")]
+#[tokio::test]
+async fn reports_iota_document_metadata() {
+  use identity_iota_core::IotaDID;
+  use identity_iota_core::IotaDocument;
+  use identity_iota_core::NetworkName;
+
+  async fn iota_handler(did: IotaDID) -> std::result::Result<IotaDocument, std::io::Error> {
+    let mut document: IotaDocument = IotaDocument::new_with_id(did);
+    document.metadata.deactivated = Some(true);
+    document.metadata.version_id = Some("1".to_owned());
+    document.metadata.governor_address = Some("rms1governor".to_owned());
+    Ok(document)
+  }
+
+  let mut resolver: Resolver<IotaDocument> = Resolver::new();
+  resolver.attach_handler(IotaDID::METHOD.to_owned(), iota_handler);
+
+  let did: IotaDID = IotaDID::placeholder(&NetworkName::try_from("smr").unwrap());
+  let result: DIDResolutionResult<IotaDocument> = resolver.resolve_with_metadata(did.as_str()).await;
+  let document: IotaDocument = result.did_document.unwrap();
+  let metadata: DIDDocumentMetadata = result.did_document_metadata;
+  assert_eq!(metadata.deactivated, Some(true));
+  assert_eq!(metadata.version_id.as_deref(), Some("1"));
+  assert_eq!(metadata.created, document.metadata.created);
+  assert_eq!(metadata.properties["governorAddress"], "rms1governor");
+}
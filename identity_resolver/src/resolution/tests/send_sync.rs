@@ -20,6 +20,12 @@ fn default_resolver_is_send_sync<DOC: AsRef<CoreDocument> + Send + Sync + 'stati
   is_send_sync(resolver);
 }
 
+#[allow(dead_code)]
+fn cached_resolver_is_send_sync<DOC: AsRef<CoreDocument> + Clone + Send + Sync + 'static>() {
+  let resolver = Resolver::<DOC>::new().with_cache(crate::ResolverCacheConfig::new(std::time::Duration::from_secs(60)));
+  is_send_sync(resolver);
+}
+
 #[allow(dead_code)]
 fn resolver_methods_give_send_futures<DOC, D, T, U, V>(
   did: D,
@@ -35,6 +41,8 @@ fn resolver_methods_give_send_futures<DOC, D, T, U, V>(
   let resolver = Resolver::<DOC>::new();
   is_send(resolver.resolve(&did));
 
+  is_send(resolver.resolve_cached(&did));
+
   is_send(resolver.resolve_credential_issuer(&credential));
 
   is_send(resolver.resolve_presentation_holder(&presentation));
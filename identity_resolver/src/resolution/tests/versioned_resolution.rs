@@ -0,0 +1,89 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_did::CoreDID;
+use identity_did::DIDUrl;
+use identity_document::document::CoreDocument;
+
+use crate::DIDVersionQuery;
+use crate::ErrorCause;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+const DID: &str = "did:example:123";
+
+fn document(did: CoreDID, version: Option<DIDVersionQuery>) -> CoreDocument {
+  let mut properties: Object = Object::new();
+  if let Some(version) = version {
+    properties.insert("version".to_owned(), format!("{version:?}").into());
+  }
+  CoreDocument::builder(properties).id(did).build().unwrap()
+}
+
+async fn latest_handler(did: CoreDID) -> std::result::Result<CoreDocument, std::io::Error> {
+  Ok(document(did, None))
+}
+
+async fn versioned_handler(
+  did: CoreDID,
+  version: DIDVersionQuery,
+) -> std::result::Result<CoreDocument, std::io::Error> {
+  Ok(document(did, Some(version)))
+}
+
+#[tokio::test]
+async fn resolves_versions() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("example".to_owned(), latest_handler);
+  resolver.attach_versioned_handler("example".to_owned(), versioned_handler);
+  let did: CoreDID = CoreDID::parse(DID).unwrap();
+
+  let did_url: DIDUrl = DIDUrl::parse(format!("{DID}?versionId=2")).unwrap();
+  assert_eq!(
+    resolver.resolve_did_url(&did_url).await.unwrap(),
+    document(did.clone(), Some(DIDVersionQuery::VersionId("2".to_owned())))
+  );
+
+  let did_url: DIDUrl = DIDUrl::parse(format!("{DID}?versionTime=2023-01-02T03:04:05Z")).unwrap();
+  let time: Timestamp = Timestamp::parse("2023-01-02T03:04:05Z").unwrap();
+  assert_eq!(
+    resolver.resolve_did_url(&did_url).await.unwrap(),
+    document(did.clone(), Some(DIDVersionQuery::VersionTime(time)))
+  );
+
+  // Without a version parameter the latest document is resolved.
+  let did_url: DIDUrl = DIDUrl::parse(format!("{DID}#key-1")).unwrap();
+  assert_eq!(
+    resolver.resolve_did_url(&did_url).await.unwrap(),
+    document(did.clone(), None)
+  );
+
+  let mut single_threaded: SingleThreadedResolver<CoreDocument> = SingleThreadedResolver::new();
+  single_threaded.attach_versioned_handler("example".to_owned(), versioned_handler);
+  let did_url: DIDUrl = DIDUrl::parse(format!("{DID}?versionId=2")).unwrap();
+  assert_eq!(
+    single_threaded.resolve_did_url(&did_url).await.unwrap(),
+    document(did, Some(DIDVersionQuery::VersionId("2".to_owned())))
+  );
+}
+
+#[tokio::test]
+async fn versioned_resolution_errors() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("example".to_owned(), latest_handler);
+
+  let did_url: DIDUrl = DIDUrl::parse(format!("{DID}?versionId=2")).unwrap();
+  assert!(matches!(
+    resolver.resolve_did_url(&did_url).await.unwrap_err().into_error_cause(),
+    ErrorCause::UnsupportedVersionedMethodError { method } if method == "example"
+  ));
+
+  resolver.attach_versioned_handler("example".to_owned(), versioned_handler);
+  let did_url: DIDUrl = DIDUrl::parse(format!("{DID}?versionId=2&versionTime=2023-01-02T03:04:05Z")).unwrap();
+  assert!(matches!(
+    resolver.resolve_did_url(&did_url).await.unwrap_err().into_error_cause(),
+    ErrorCause::DIDParsingError { .. }
+  ));
+}
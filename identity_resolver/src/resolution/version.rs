@@ -0,0 +1,101 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_did::DIDUrl;
+use identity_did::DID;
+
+/// The `versionId` DID URL query parameter.
+const VERSION_ID: &str = "versionId";
+/// The `versionTime` DID URL query parameter.
+const VERSION_TIME: &str = "versionTime";
+
+/// A version of a DID document selected by the `versionId` or `versionTime`
+/// [DID parameter](https://www.w3.org/TR/did-core/#did-parameters) of a DID URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DIDVersionQuery {
+  /// The version with the given method-specific identifier.
+  VersionId(String),
+  /// The latest version that was valid at the given time.
+  VersionTime(Timestamp),
+}
+
+impl DIDVersionQuery {
+  /// Extracts the version selected by the query of `did_url`, returning `None` if no version is selected.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if both or multiple version parameters are given, or `versionTime` is not an RFC 3339 timestamp.
+  pub fn from_did_url(did_url: &DIDUrl) -> Result<Option<Self>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let mut version: Option<Self> = None;
+    for (key, value) in did_url.query_pairs() {
+      let parameter: Self = match key.as_ref() {
+        VERSION_ID => Self::VersionId(value.into_owned()),
+        VERSION_TIME => Self::VersionTime(Timestamp::parse(&value)?),
+        _ => continue,
+      };
+      if version.replace(parameter).is_some() {
+        return Err(format!("at most one of `{VERSION_ID}` or `{VERSION_TIME}` may be given").into());
+      }
+    }
+    Ok(version)
+  }
+}
+
+/// A DID together with the version of its document to resolve.
+///
+/// This is the input of versioned handlers, parsed from a DID URL.
+pub(super) struct VersionedDID<D> {
+  pub(super) did: D,
+  pub(super) version: DIDVersionQuery,
+}
+
+impl<D, E> TryFrom<&str> for VersionedDID<D>
+where
+  D: for<'r> TryFrom<&'r str, Error = E>,
+  E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+  type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+  fn try_from(input: &str) -> Result<Self, Self::Error> {
+    let did_url: DIDUrl = DIDUrl::parse(input)?;
+    let version: DIDVersionQuery =
+      DIDVersionQuery::from_did_url(&did_url)?.ok_or("the DID URL does not select a version")?;
+    let did: D = D::try_from(did_url.did().as_str()).map_err(Into::into)?;
+    Ok(Self { did, version })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_from_did_url() {
+    let did_url: DIDUrl = DIDUrl::parse("did:example:123?versionId=4").unwrap();
+    assert_eq!(
+      DIDVersionQuery::from_did_url(&did_url).unwrap(),
+      Some(DIDVersionQuery::VersionId("4".to_owned()))
+    );
+
+    let did_url: DIDUrl = DIDUrl::parse("did:example:123?service=foo&versionTime=2023-01-02T03:04:05Z").unwrap();
+    assert_eq!(
+      DIDVersionQuery::from_did_url(&did_url).unwrap(),
+      Some(DIDVersionQuery::VersionTime(
+        Timestamp::parse("2023-01-02T03:04:05Z").unwrap()
+      ))
+    );
+
+    let did_url: DIDUrl = DIDUrl::parse("did:example:123?service=foo").unwrap();
+    assert_eq!(DIDVersionQuery::from_did_url(&did_url).unwrap(), None);
+
+    for invalid in [
+      "did:example:123?versionId=1&versionTime=2023-01-02T03:04:05Z",
+      "did:example:123?versionId=1&versionId=2",
+      "did:example:123?versionTime=yesterday",
+    ] {
+      assert!(DIDVersionQuery::from_did_url(&DIDUrl::parse(invalid).unwrap()).is_err());
+    }
+  }
+}
@@ -0,0 +1,138 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use tokio::sync::RwLockReadGuard;
+use tokio::sync::RwLockWriteGuard;
+
+use crate::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::KeyIdStorageError;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_id_storage::KeyIdStorageResult;
+use crate::key_id_storage::MethodDigest;
+use crate::key_storage::KeyId;
+use crate::utils::Shared;
+
+/// The map from method digests to key ids.
+type KeyIdStore = HashMap<MethodDigest, KeyId>;
+
+/// An insecure, in-memory [`KeyIdStorage`] implementation that serves as an example and may be used in tests.
+#[derive(Debug)]
+pub struct KeyIdMemStore {
+  key_id_store: Shared<KeyIdStore>,
+}
+
+impl KeyIdMemStore {
+  /// Creates a new, empty `KeyIdMemStore` instance.
+  pub fn new() -> Self {
+    Self {
+      key_id_store: Shared::new(HashMap::new()),
+    }
+  }
+}
+
+impl Default for KeyIdMemStore {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// Refer to the `KeyIdStorage` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl KeyIdStorage for KeyIdMemStore {
+  async fn insert_key_id(&self, method_digest: MethodDigest, key_id: KeyId) -> KeyIdStorageResult<()> {
+    let mut key_id_store: RwLockWriteGuard<'_, KeyIdStore> = self.key_id_store.write().await;
+
+    if key_id_store.contains_key(&method_digest) {
+      return Err(KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdAlreadyExists));
+    }
+
+    key_id_store.insert(method_digest, key_id);
+    Ok(())
+  }
+
+  async fn get_key_id(&self, method_digest: &MethodDigest) -> KeyIdStorageResult<KeyId> {
+    let key_id_store: RwLockReadGuard<'_, KeyIdStore> = self.key_id_store.read().await;
+    key_id_store
+      .get(method_digest)
+      .cloned()
+      .ok_or_else(|| KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound))
+  }
+
+  async fn delete_key_id(&self, method_digest: &MethodDigest) -> KeyIdStorageResult<()> {
+    let mut key_id_store: RwLockWriteGuard<'_, KeyIdStore> = self.key_id_store.write().await;
+    key_id_store
+      .remove(method_digest)
+      .map(|_| ())
+      .ok_or_else(|| KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_verification::VerificationMethod;
+
+  use super::*;
+
+  fn method_digest() -> MethodDigest {
+    let method: VerificationMethod = VerificationMethod::from_json(
+      r#"{
+        "id": "did:example:1234#key-1",
+        "controller": "did:example:1234",
+        "type": "Ed25519VerificationKey2018",
+        "publicKeyMultibase": "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+      }"#,
+    )
+    .unwrap();
+    MethodDigest::new(&method).unwrap()
+  }
+
+  #[tokio::test]
+  async fn insert_get_delete() {
+    let store: KeyIdMemStore = KeyIdMemStore::new();
+    let method_digest: MethodDigest = method_digest();
+    let key_id: KeyId = KeyId::new("key");
+
+    store
+      .insert_key_id(method_digest.clone(), key_id.clone())
+      .await
+      .unwrap();
+    assert_eq!(store.get_key_id(&method_digest).await.unwrap(), key_id);
+
+    store.delete_key_id(&method_digest).await.unwrap();
+    assert!(matches!(
+      store.get_key_id(&method_digest).await.unwrap_err().kind(),
+      KeyIdStorageErrorKind::KeyIdNotFound
+    ));
+  }
+
+  #[tokio::test]
+  async fn insert_existing() {
+    let store: KeyIdMemStore = KeyIdMemStore::new();
+    let method_digest: MethodDigest = method_digest();
+
+    store
+      .insert_key_id(method_digest.clone(), KeyId::new("key"))
+      .await
+      .unwrap();
+    let err: KeyIdStorageError = store
+      .insert_key_id(method_digest.clone(), KeyId::new("other-key"))
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdAlreadyExists));
+
+    // The original entry is left untouched.
+    assert_eq!(store.get_key_id(&method_digest).await.unwrap(), KeyId::new("key"));
+  }
+
+  #[tokio::test]
+  async fn delete_missing() {
+    let store: KeyIdMemStore = KeyIdMemStore::new();
+    let err: KeyIdStorageError = store.delete_key_id(&method_digest()).await.unwrap_err();
+    assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdNotFound));
+  }
+}
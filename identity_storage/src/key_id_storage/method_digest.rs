@@ -0,0 +1,181 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::hash::Hasher;
+
+use identity_jose::jwk::JwkThumbprint;
+use identity_verification::MethodData;
+use identity_verification::VerificationMethod;
+use seahash::SeaHasher;
+
+use crate::key_id_storage::KeyIdStorageError;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_id_storage::KeyIdStorageResult;
+
+/// Unique identifier of a [`VerificationMethod`].
+///
+/// NOTE:
+/// This class does not have a JSON representation,
+/// use the methods `pack` and `unpack` instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodDigest {
+  /// Version of hashing.
+  version: u8,
+  /// Hash value.
+  value: u64,
+}
+
+impl MethodDigest {
+  /// The current version of the hashing scheme.
+  const VERSION: u8 = 0;
+  /// The length of a packed [`MethodDigest`] in bytes.
+  const PACKED_LEN: usize = 1 + std::mem::size_of::<u64>();
+
+  /// Creates a new [`MethodDigest`] of the given [`VerificationMethod`].
+  ///
+  /// The digest is computed from the fragment of the method's id and its public key material.
+  /// The DID part of the id is deliberately excluded, as it may change after the method was created,
+  /// e.g. when a document with a placeholder DID is published.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the id of the method has no fragment or if its key material cannot be decoded.
+  pub fn new(verification_method: &VerificationMethod) -> KeyIdStorageResult<Self> {
+    let mut hasher: SeaHasher = SeaHasher::default();
+    let fragment: &str = verification_method.id().fragment().ok_or_else(|| {
+      KeyIdStorageError::new(KeyIdStorageErrorKind::Unspecified).with_custom_message("method id has no fragment")
+    })?;
+    hasher.write(fragment.as_bytes());
+
+    match verification_method.data() {
+      // Use the thumbprint so that optional JWK members such as `alg` or `kid` do not affect the digest.
+      MethodData::PublicKeyJwk(jwk) => {
+        let thumbprint: JwkThumbprint = jwk.thumbprint_raw().map_err(|err| {
+          KeyIdStorageError::new(KeyIdStorageErrorKind::Unspecified)
+            .with_custom_message("unable to compute the jwk thumbprint")
+            .with_source(err)
+        })?;
+        hasher.write(&thumbprint);
+      }
+      data => {
+        let key: Vec<u8> = data.try_decode().map_err(|err| {
+          KeyIdStorageError::new(KeyIdStorageErrorKind::Unspecified)
+            .with_custom_message("unable to decode the method data")
+            .with_source(err)
+        })?;
+        hasher.write(&key);
+      }
+    }
+
+    Ok(Self {
+      version: Self::VERSION,
+      value: hasher.finish(),
+    })
+  }
+
+  /// Packs [`MethodDigest`] into bytes.
+  pub fn pack(&self) -> Vec<u8> {
+    let mut pack: Vec<u8> = Vec::with_capacity(Self::PACKED_LEN);
+    pack.push(self.version);
+    pack.extend_from_slice(&self.value.to_le_bytes());
+    pack
+  }
+
+  /// Unpacks bytes into [`MethodDigest`].
+  pub fn unpack(bytes: Vec<u8>) -> KeyIdStorageResult<Self> {
+    if bytes.len() != Self::PACKED_LEN {
+      return Err(
+        KeyIdStorageError::new(KeyIdStorageErrorKind::SerializationError).with_custom_message(format!(
+          "expected {} bytes, found {}",
+          Self::PACKED_LEN,
+          bytes.len()
+        )),
+      );
+    }
+    let version: u8 = bytes[0];
+    if version != Self::VERSION {
+      return Err(
+        KeyIdStorageError::new(KeyIdStorageErrorKind::SerializationError)
+          .with_custom_message(format!("unsupported method digest version {version}")),
+      );
+    }
+    let value_le_bytes: [u8; 8] = bytes[1..]
+      .try_into()
+      .expect("length was checked to match the packed length");
+
+    Ok(Self {
+      version,
+      value: u64::from_le_bytes(value_le_bytes),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_verification::VerificationMethod;
+
+  use super::*;
+
+  const JWK_METHOD: &str = r#"{
+    "id": "did:example:1234#key-1",
+    "controller": "did:example:1234",
+    "type": "JsonWebKey2020",
+    "publicKeyJwk": {
+      "kty": "OKP",
+      "crv": "Ed25519",
+      "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"
+    }
+  }"#;
+
+  #[test]
+  fn hash_is_stable() {
+    let method: VerificationMethod = VerificationMethod::from_json(JWK_METHOD).unwrap();
+    let method_digest: MethodDigest = MethodDigest::new(&method).unwrap();
+    // Changing this value breaks the lookup of keys stored with earlier versions.
+    assert_eq!(method_digest.value, 3038850978029294011);
+  }
+
+  #[test]
+  fn hash_ignores_did() {
+    let method: VerificationMethod = VerificationMethod::from_json(JWK_METHOD).unwrap();
+    let other_did: VerificationMethod =
+      VerificationMethod::from_json(&JWK_METHOD.replace("did:example:1234", "did:example:5678")).unwrap();
+    assert_eq!(
+      MethodDigest::new(&method).unwrap(),
+      MethodDigest::new(&other_did).unwrap()
+    );
+
+    let other_fragment: VerificationMethod =
+      VerificationMethod::from_json(&JWK_METHOD.replace("#key-1", "#key-2")).unwrap();
+    assert_ne!(
+      MethodDigest::new(&method).unwrap(),
+      MethodDigest::new(&other_fragment).unwrap()
+    );
+  }
+
+  #[test]
+  fn hash_ignores_optional_jwk_members() {
+    let method: VerificationMethod = VerificationMethod::from_json(JWK_METHOD).unwrap();
+    let with_alg: VerificationMethod =
+      VerificationMethod::from_json(&JWK_METHOD.replace(r#""kty": "OKP","#, r#""kty": "OKP", "alg": "EdDSA","#))
+        .unwrap();
+    assert_eq!(
+      MethodDigest::new(&method).unwrap(),
+      MethodDigest::new(&with_alg).unwrap()
+    );
+  }
+
+  #[test]
+  fn pack_unpack() {
+    let method: VerificationMethod = VerificationMethod::from_json(JWK_METHOD).unwrap();
+    let method_digest: MethodDigest = MethodDigest::new(&method).unwrap();
+    let packed: Vec<u8> = method_digest.pack();
+    assert_eq!(MethodDigest::unpack(packed.clone()).unwrap(), method_digest);
+
+    assert!(matches!(
+      MethodDigest::unpack(packed[1..].to_vec()).unwrap_err().kind(),
+      KeyIdStorageErrorKind::SerializationError
+    ));
+  }
+}
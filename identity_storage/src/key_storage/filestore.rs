@@ -0,0 +1,535 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::path::Path;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use crypto::ciphers::chacha::XChaCha20Poly1305;
+use crypto::ciphers::traits::Aead;
+use crypto::keys::pbkdf::PBKDF2_HMAC_SHA512;
+use identity_jose::jwk::Jwk;
+use identity_jose::jws::JwsAlgorithm;
+use identity_jose::jwu;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::RwLockReadGuard;
+use tokio::sync::RwLockWriteGuard;
+use zeroize::Zeroizing;
+
+use super::key_gen::JwkGenOutput;
+use super::util::check_insertable_jwk;
+use super::util::generate_private_jwk;
+use super::util::random_key_id;
+use super::util::sign_with_jwk;
+use super::KeyId;
+use super::KeyStorageError;
+use super::KeyStorageErrorKind;
+use super::KeyStorageResult;
+use super::KeyType;
+use crate::key_storage::JwkStorage;
+use crate::utils::Shared;
+
+/// The map from key ids to JWKs.
+type JwkKeyStore = HashMap<KeyId, Jwk>;
+
+/// The length of the key derived from the password.
+const ENCRYPTION_KEY_LENGTH: usize = 32;
+/// The length of the random salt used for the key derivation.
+const SALT_LENGTH: usize = 16;
+/// The version of the file format.
+const FILE_VERSION: u8 = 1;
+/// The associated data authenticated along with the encrypted keys.
+const ASSOCIATED_DATA: &[u8] = b"identity_storage::JwkFileStore";
+
+/// A persistent [`JwkStorage`] implementation that keeps its keys in a single file.
+///
+/// The keys are encrypted at rest with XChaCha20-Poly1305 under a key derived from a password with
+/// PBKDF2-HMAC-SHA512 and a random salt. Every modification rewrites the file atomically by writing to a temporary file
+/// next to it, which is then renamed, so a crash never leaves a partially written file behind.
+///
+/// The keys are held in memory after the file was opened. The file must not be opened by more than one instance at
+/// the same time, as concurrent modifications of different instances overwrite each other.
+///
+/// The supported key types and algorithms are the same as for the [`JwkMemStore`](crate::key_storage::JwkMemStore).
+pub struct JwkFileStore {
+  path: PathBuf,
+  salt: [u8; SALT_LENGTH],
+  iterations: u32,
+  encryption_key: Zeroizing<[u8; ENCRYPTION_KEY_LENGTH]>,
+  jwk_store: Shared<JwkKeyStore>,
+}
+
+impl JwkFileStore {
+  /// The number of PBKDF2 iterations used for new files by [`JwkFileStore::open`].
+  pub const DEFAULT_ITERATIONS: u32 = 210_000;
+  /// The minimum number of PBKDF2 iterations accepted by [`JwkFileStore::open_with_iterations`].
+  pub const MIN_ITERATIONS: u32 = 1;
+  /// The maximum number of PBKDF2 iterations accepted by [`JwkFileStore::open_with_iterations`], which bounds the time
+  /// spent deriving the key of a file that was tampered with.
+  pub const MAX_ITERATIONS: u32 = 10_000_000;
+
+  /// Opens the key file at `path` and decrypts it with `password`.
+  ///
+  /// If no file exists at `path`, a new, empty file is created which is encrypted with `password`.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`KeyStorageErrorKind::Unauthenticated`] if the file cannot be decrypted with `password`.
+  pub async fn open(path: impl AsRef<Path>, password: &str) -> KeyStorageResult<Self> {
+    Self::open_with_iterations(path, password, Self::DEFAULT_ITERATIONS).await
+  }
+
+  /// Same as [`JwkFileStore::open`], but uses the given number of PBKDF2 `iterations` if a new file is created.
+  ///
+  /// The number of iterations of an existing file is read from the file itself.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `iterations` or the number of iterations of an existing file is not within
+  /// [`JwkFileStore::MIN_ITERATIONS`] and [`JwkFileStore::MAX_ITERATIONS`].
+  pub async fn open_with_iterations(path: impl AsRef<Path>, password: &str, iterations: u32) -> KeyStorageResult<Self> {
+    if !Self::valid_iterations(iterations) {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+          "the number of iterations must be between {} and {}",
+          Self::MIN_ITERATIONS,
+          Self::MAX_ITERATIONS
+        )),
+      );
+    }
+    let path: PathBuf = path.as_ref().to_owned();
+
+    let contents: Option<Vec<u8>> = match tokio::fs::read(&path).await {
+      Ok(contents) => Some(contents),
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+      Err(err) => {
+        return Err(
+          KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure)
+            .with_custom_message(format!("unable to read `{}`", path.display()))
+            .with_source(err),
+        )
+      }
+    };
+
+    match contents {
+      Some(contents) => {
+        let file: KeyFile = serde_json::from_slice(&contents).map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::SerializationError)
+            .with_custom_message("invalid key file")
+            .with_source(err)
+        })?;
+        if file.version != FILE_VERSION {
+          return Err(
+            KeyStorageError::new(KeyStorageErrorKind::SerializationError)
+              .with_custom_message(format!("unsupported key file version {}", file.version)),
+          );
+        }
+        // The file is untrusted, so the work of the key derivation must be bounded.
+        if !Self::valid_iterations(file.iterations) {
+          return Err(invalid_field("iterations"));
+        }
+
+        let salt: [u8; SALT_LENGTH] = decode_b64_field(&file.salt, "salt")?
+          .try_into()
+          .map_err(|_| invalid_field("salt"))?;
+        let encryption_key = derive_encryption_key(password, &salt, file.iterations)?;
+        let jwk_store: JwkKeyStore = decrypt(&encryption_key, &file)?;
+
+        Ok(Self {
+          path,
+          salt,
+          iterations: file.iterations,
+          encryption_key,
+          jwk_store: Shared::new(jwk_store),
+        })
+      }
+      None => {
+        let mut salt: [u8; SALT_LENGTH] = [0; SALT_LENGTH];
+        crypto::utils::rand::fill(&mut salt)
+          .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+        let encryption_key = derive_encryption_key(password, &salt, iterations)?;
+
+        let store: Self = Self {
+          path,
+          salt,
+          iterations,
+          encryption_key,
+          jwk_store: Shared::new(HashMap::new()),
+        };
+        // Persist the empty store, so the password is fixed from now on.
+        store.persist(&HashMap::new()).await?;
+
+        Ok(store)
+      }
+    }
+  }
+
+  fn valid_iterations(iterations: u32) -> bool {
+    (Self::MIN_ITERATIONS..=Self::MAX_ITERATIONS).contains(&iterations)
+  }
+
+  /// Returns the path of the key file.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Encrypts `jwk_store` and atomically replaces the key file with the result.
+  async fn persist(&self, jwk_store: &JwkKeyStore) -> KeyStorageResult<()> {
+    let contents: Vec<u8> = serde_json::to_vec(&self.encrypt(jwk_store)?)
+      .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::SerializationError).with_source(err))?;
+
+    let mut tmp_path: OsString = self.path.clone().into_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path: PathBuf = PathBuf::from(tmp_path);
+
+    let io_error = |err: std::io::Error| {
+      KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure)
+        .with_custom_message(format!("unable to write `{}`", self.path.display()))
+        .with_source(err)
+    };
+
+    let mut options: tokio::fs::OpenOptions = tokio::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file: tokio::fs::File = options.open(&tmp_path).await.map_err(io_error)?;
+    file.write_all(&contents).await.map_err(io_error)?;
+    file.sync_all().await.map_err(io_error)?;
+    drop(file);
+
+    tokio::fs::rename(&tmp_path, &self.path).await.map_err(io_error)
+  }
+
+  fn encrypt(&self, jwk_store: &JwkKeyStore) -> KeyStorageResult<KeyFile> {
+    let plaintext: Zeroizing<Vec<u8>> = Zeroizing::new(
+      serde_json::to_vec(jwk_store)
+        .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::SerializationError).with_source(err))?,
+    );
+
+    let nonce = XChaCha20Poly1305::random_nonce()
+      .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+    let mut ciphertext: Vec<u8> = vec![0; plaintext.len()];
+    let mut tag: Vec<u8> = vec![0; XChaCha20Poly1305::TAG_LENGTH];
+    XChaCha20Poly1305::try_encrypt(
+      self.encryption_key.as_ref(),
+      &nonce,
+      ASSOCIATED_DATA,
+      &plaintext,
+      &mut ciphertext,
+      &mut tag,
+    )
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to encrypt the keys")
+        .with_source(err)
+    })?;
+
+    Ok(KeyFile {
+      version: FILE_VERSION,
+      iterations: self.iterations,
+      salt: jwu::encode_b64(self.salt),
+      nonce: jwu::encode_b64(nonce),
+      tag: jwu::encode_b64(tag),
+      ciphertext: jwu::encode_b64(ciphertext),
+    })
+  }
+
+  /// Applies `update` to a copy of the stored keys, persists the result and only then makes it visible in memory.
+  async fn update<T>(&self, update: impl FnOnce(&mut JwkKeyStore) -> KeyStorageResult<T>) -> KeyStorageResult<T> {
+    let mut jwk_store: RwLockWriteGuard<'_, JwkKeyStore> = self.jwk_store.write().await;
+    let mut updated: JwkKeyStore = jwk_store.clone();
+    let output: T = update(&mut updated)?;
+    self.persist(&updated).await?;
+    *jwk_store = updated;
+    Ok(output)
+  }
+}
+
+impl Debug for JwkFileStore {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("JwkFileStore")
+      .field("path", &self.path)
+      .finish_non_exhaustive()
+  }
+}
+
+// Refer to the `JwkStorage` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorage for JwkFileStore {
+  async fn generate(&self, key_type: KeyType, alg: JwsAlgorithm) -> KeyStorageResult<JwkGenOutput> {
+    let jwk: Jwk = generate_private_jwk(&key_type, alg)?;
+    let kid: KeyId = random_key_id();
+    let public_jwk: Jwk = jwk.to_public();
+
+    self
+      .update(|jwk_store| {
+        jwk_store.insert(kid.clone(), jwk);
+        Ok(())
+      })
+      .await?;
+
+    Ok(JwkGenOutput::new(kid, public_jwk))
+  }
+
+  async fn insert(&self, jwk: Jwk) -> KeyStorageResult<KeyId> {
+    check_insertable_jwk(&jwk)?;
+
+    let key_id: KeyId = random_key_id();
+
+    self
+      .update(|jwk_store| {
+        jwk_store.insert(key_id.clone(), jwk);
+        Ok(())
+      })
+      .await?;
+
+    Ok(key_id)
+  }
+
+  async fn sign(&self, key_id: &KeyId, data: Vec<u8>) -> KeyStorageResult<Vec<u8>> {
+    let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
+
+    let jwk: &Jwk = jwk_store
+      .get(key_id)
+      .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
+
+    sign_with_jwk(jwk, &data)
+  }
+
+  async fn public(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
+    let jwk: &Jwk = jwk_store
+      .get(key_id)
+      .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
+    Ok(jwk.to_public())
+  }
+
+  async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
+    self
+      .update(|jwk_store| {
+        jwk_store
+          .remove(key_id)
+          .map(|_| ())
+          .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))
+      })
+      .await
+  }
+
+  async fn exists(&self, key_id: &KeyId) -> KeyStorageResult<bool> {
+    let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
+    Ok(jwk_store.contains_key(key_id))
+  }
+}
+
+/// The serialized representation of the key file.
+#[derive(Debug, Serialize, Deserialize)]
+struct KeyFile {
+  version: u8,
+  iterations: u32,
+  salt: String,
+  nonce: String,
+  tag: String,
+  ciphertext: String,
+}
+
+fn derive_encryption_key(
+  password: &str,
+  salt: &[u8],
+  iterations: u32,
+) -> KeyStorageResult<Zeroizing<[u8; ENCRYPTION_KEY_LENGTH]>> {
+  let mut encryption_key: Zeroizing<[u8; ENCRYPTION_KEY_LENGTH]> = Zeroizing::new([0; ENCRYPTION_KEY_LENGTH]);
+  PBKDF2_HMAC_SHA512(password.as_bytes(), salt, iterations as usize, encryption_key.as_mut()).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("unable to derive the encryption key")
+      .with_source(err)
+  })?;
+  Ok(encryption_key)
+}
+
+fn decrypt(encryption_key: &[u8; ENCRYPTION_KEY_LENGTH], file: &KeyFile) -> KeyStorageResult<JwkKeyStore> {
+  let nonce: Vec<u8> = decode_b64_field(&file.nonce, "nonce")?;
+  let tag: Vec<u8> = decode_b64_field(&file.tag, "tag")?;
+  let ciphertext: Vec<u8> = decode_b64_field(&file.ciphertext, "ciphertext")?;
+
+  let mut plaintext: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; ciphertext.len()]);
+  XChaCha20Poly1305::try_decrypt(
+    encryption_key,
+    &nonce,
+    ASSOCIATED_DATA,
+    &mut plaintext,
+    &ciphertext,
+    &tag,
+  )
+  .map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unauthenticated)
+      .with_custom_message("unable to decrypt the key file, the password may be wrong or the file corrupted")
+      .with_source(err)
+  })?;
+
+  serde_json::from_slice(&plaintext).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::SerializationError)
+      .with_custom_message("invalid key file contents")
+      .with_source(err)
+  })
+}
+
+fn decode_b64_field(value: &str, field: &'static str) -> KeyStorageResult<Vec<u8>> {
+  jwu::decode_b64(value).map_err(|err| invalid_field(field).with_source(err))
+}
+
+fn invalid_field(field: &'static str) -> KeyStorageError {
+  KeyStorageError::new(KeyStorageErrorKind::SerializationError)
+    .with_custom_message(format!("invalid `{field}` in key file"))
+}
+
+#[cfg(test)]
+mod tests {
+  use crypto::signatures::ed25519::SecretKey;
+  use crypto::signatures::ed25519::Signature;
+
+  use super::*;
+  use crate::key_storage::test_suite;
+  use crate::key_storage::util::ed25519::encode_jwk;
+  use crate::key_storage::ED25519_KEY_TYPE;
+
+  const PASSWORD: &str = "password";
+  // Keep the tests fast, the number of iterations does not affect their outcome.
+  const ITERATIONS: u32 = 10;
+
+  /// A path in the temporary directory that is removed when dropped.
+  struct TempPath(PathBuf);
+
+  impl TempPath {
+    fn new() -> Self {
+      Self(std::env::temp_dir().join(format!("jwk-file-store-{}.json", random_key_id())))
+    }
+  }
+
+  impl Drop for TempPath {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_file(&self.0);
+    }
+  }
+
+  async fn open(path: &TempPath, password: &str) -> KeyStorageResult<JwkFileStore> {
+    JwkFileStore::open_with_iterations(&path.0, password, ITERATIONS).await
+  }
+
+  #[tokio::test]
+  async fn generate_and_sign() {
+    let path: TempPath = TempPath::new();
+    test_suite::generate_and_sign(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn insert() {
+    let path: TempPath = TempPath::new();
+    test_suite::insert(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn exists() {
+    let path: TempPath = TempPath::new();
+    test_suite::exists(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn incompatible_key_type() {
+    let path: TempPath = TempPath::new();
+    test_suite::incompatible_key_type(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn incompatible_key_alg() {
+    let path: TempPath = TempPath::new();
+    test_suite::incompatible_key_alg(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[cfg(feature = "es256k")]
+  #[tokio::test]
+  async fn generate_and_sign_es256k() {
+    let path: TempPath = TempPath::new();
+    test_suite::generate_and_sign_es256k(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[cfg(feature = "es256")]
+  #[tokio::test]
+  async fn generate_and_sign_es256() {
+    let path: TempPath = TempPath::new();
+    test_suite::generate_and_sign_es256(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[cfg(all(feature = "es256", feature = "es256k"))]
+  #[tokio::test]
+  async fn incompatible_ec_key_alg() {
+    let path: TempPath = TempPath::new();
+    test_suite::incompatible_ec_key_alg(open(&path, PASSWORD).await.unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn persists_keys() {
+    let path: TempPath = TempPath::new();
+    let store: JwkFileStore = open(&path, PASSWORD).await.unwrap();
+
+    let private_key: SecretKey = SecretKey::generate().unwrap();
+    let mut private_jwk: Jwk = encode_jwk(&private_key, &private_key.public_key());
+    private_jwk.set_alg(JwsAlgorithm::EdDSA.name());
+    let key_id: KeyId = store.insert(private_jwk.clone()).await.unwrap();
+    let deleted: KeyId = store
+      .generate(ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+      .await
+      .unwrap()
+      .key_id;
+    store.delete(&deleted).await.unwrap();
+    drop(store);
+
+    // The private key is not stored in plaintext and no temporary file is left behind.
+    let contents: String = std::fs::read_to_string(&path.0).unwrap();
+    assert!(!contents.contains(private_jwk.try_okp_params().unwrap().d.as_deref().unwrap()));
+    assert!(!PathBuf::from(format!("{}.tmp", path.0.display())).exists());
+
+    let store: JwkFileStore = open(&path, PASSWORD).await.unwrap();
+    assert_eq!(store.public(&key_id).await.unwrap(), private_jwk.to_public());
+    assert!(!store.exists(&deleted).await.unwrap());
+
+    let signature = store.sign(&key_id, b"test".to_vec()).await.unwrap();
+    let signature: Signature = Signature::from_bytes(signature.try_into().unwrap());
+    assert!(private_key.public_key().verify(&signature, b"test"));
+  }
+
+  #[tokio::test]
+  async fn wrong_password() {
+    let path: TempPath = TempPath::new();
+    open(&path, PASSWORD).await.unwrap();
+
+    let err: KeyStorageError = open(&path, "wrong password").await.unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::Unauthenticated));
+  }
+
+  #[tokio::test]
+  async fn invalid_iterations() {
+    let path: TempPath = TempPath::new();
+    assert!(JwkFileStore::open_with_iterations(&path.0, PASSWORD, 0).await.is_err());
+    assert!(JwkFileStore::open_with_iterations(&path.0, PASSWORD, u32::MAX)
+      .await
+      .is_err());
+    open(&path, PASSWORD).await.unwrap();
+
+    // The number of iterations read from the file is checked before deriving the key.
+    let mut file: serde_json::Value = serde_json::from_slice(&std::fs::read(&path.0).unwrap()).unwrap();
+    for iterations in [0, u32::MAX] {
+      file["iterations"] = iterations.into();
+      std::fs::write(&path.0, serde_json::to_vec(&file).unwrap()).unwrap();
+      let err: KeyStorageError = open(&path, PASSWORD).await.unwrap_err();
+      assert!(matches!(err.kind(), KeyStorageErrorKind::SerializationError));
+    }
+  }
+}
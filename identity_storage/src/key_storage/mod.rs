@@ -1,6 +1,8 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "filestore")]
+mod filestore;
 mod jwk_storage;
 mod key_gen;
 mod key_id;
@@ -8,7 +10,15 @@ mod key_storage_error;
 mod key_type;
 #[cfg(feature = "memstore")]
 mod memstore;
+#[cfg(feature = "stronghold")]
+mod stronghold;
+#[cfg(all(test, any(feature = "memstore", feature = "filestore", feature = "stronghold")))]
+mod test_suite;
+#[cfg(any(feature = "memstore", feature = "filestore", feature = "stronghold"))]
+pub(crate) mod util;
 
+#[cfg(feature = "filestore")]
+pub use filestore::*;
 pub use jwk_storage::*;
 pub use key_gen::*;
 pub use key_id::*;
@@ -16,3 +26,17 @@ pub use key_storage_error::*;
 pub use key_type::*;
 #[cfg(feature = "memstore")]
 pub use memstore::*;
+#[cfg(feature = "stronghold")]
+pub use stronghold::*;
+#[cfg(any(feature = "memstore", feature = "filestore", feature = "stronghold"))]
+pub use util::ED25519_KEY_TYPE;
+#[cfg(all(
+  feature = "es256",
+  any(feature = "memstore", feature = "filestore", feature = "stronghold")
+))]
+pub use util::P256_KEY_TYPE;
+#[cfg(all(
+  feature = "es256k",
+  any(feature = "memstore", feature = "filestore", feature = "stronghold")
+))]
+pub use util::SECP256K1_KEY_TYPE;
@@ -0,0 +1,564 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crypto::signatures::ed25519::SecretKey;
+use identity_jose::jwk::EdCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jwk::JwkParamsOkp;
+use identity_jose::jws::JwsAlgorithm;
+use identity_jose::jwu;
+use iota_stronghold::procedures::Ed25519Sign;
+use iota_stronghold::procedures::GenerateKey;
+use iota_stronghold::procedures::KeyType as ProceduresKeyType;
+use iota_stronghold::procedures::PublicKey;
+use iota_stronghold::Client;
+use iota_stronghold::ClientError;
+use iota_stronghold::KeyProvider;
+use iota_stronghold::Location;
+use iota_stronghold::SnapshotPath;
+use iota_stronghold::Stronghold;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+
+use super::key_gen::JwkGenOutput;
+use super::util::check_insertable_jwk;
+use super::util::ed25519;
+use super::util::random_key_id;
+use super::KeyId;
+use super::KeyStorageError;
+use super::KeyStorageErrorKind;
+use super::KeyStorageResult;
+use super::KeyType;
+use crate::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::KeyIdStorageError;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_id_storage::KeyIdStorageResult;
+use crate::key_id_storage::MethodDigest;
+use crate::key_storage::JwkStorage;
+
+/// The path of the Stronghold client holding the keys.
+const IDENTITY_CLIENT_PATH: &[u8] = b"iota_identity_client";
+/// The path of the vault holding the keys within the client.
+const IDENTITY_VAULT_PATH: &[u8] = b"iota_identity_vault";
+
+const ED25519_KEY_TYPE_STR: &str = "Ed25519";
+/// The Ed25519 key type supported by the [`StrongholdStorage`].
+pub const STRONGHOLD_ED25519_KEY_TYPE: KeyType = KeyType::from_static_str(ED25519_KEY_TYPE_STR);
+
+/// A [`JwkStorage`] and [`KeyIdStorage`] implementation backed by a Stronghold snapshot file.
+///
+/// Stronghold is a secure storage for sensitive data. Private keys that are stored inside a Stronghold
+/// can never be read, but only be used via cryptographic procedures. The keys and the key ids of the methods are
+/// persisted in a snapshot file, which is encrypted with a key derived from the given password and written after
+/// every modification.
+///
+/// Clones share the same Stronghold, so a single snapshot can serve as both storages of a
+/// [`Storage`](crate::storage::Storage).
+///
+/// Currently, only Ed25519 keys with the `EdDSA` algorithm are supported.
+#[derive(Clone)]
+pub struct StrongholdStorage {
+  stronghold: Stronghold,
+  client: Client,
+  snapshot_path: SnapshotPath,
+  key_provider: Arc<KeyProvider>,
+  // Serializes modifications and the following snapshot writes.
+  commit_lock: Arc<Mutex<()>>,
+}
+
+impl StrongholdStorage {
+  /// Loads the Stronghold snapshot at `path` and decrypts it with `password`.
+  ///
+  /// If no snapshot exists at `path`, it is created on the first modification of the storage.
+  /// The `password` is zeroized after the encryption key was derived from it.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`KeyStorageErrorKind::Unauthenticated`] if the snapshot cannot be decrypted with `password`.
+  pub fn new(path: impl AsRef<Path>, password: String) -> KeyStorageResult<Self> {
+    let stronghold: Stronghold = Stronghold::default();
+    let key_provider: KeyProvider = KeyProvider::with_passphrase_hashed_blake2b(password).map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("failed to load password into key provider")
+        .with_source(err)
+    })?;
+    let snapshot_path: SnapshotPath = SnapshotPath::from_path(path);
+
+    // If the snapshot file exists, we load it.
+    // If it doesn't we write keys into the in-memory `Stronghold` and only persist to disk on first write.
+    if snapshot_path.exists() {
+      stronghold.load_snapshot(&key_provider, &snapshot_path).map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unauthenticated)
+          .with_custom_message(format!(
+            "unable to load the snapshot `{}`, the password may be wrong or the file corrupted",
+            snapshot_path.as_path().display()
+          ))
+          .with_source(err)
+      })?;
+    }
+
+    let client: Client = match stronghold.load_client(IDENTITY_CLIENT_PATH) {
+      Ok(client) => Ok(client),
+      Err(ClientError::ClientDataNotPresent) => stronghold.create_client(IDENTITY_CLIENT_PATH),
+      Err(err) => Err(err),
+    }
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to load the stronghold client")
+        .with_source(err)
+    })?;
+
+    Ok(Self {
+      stronghold,
+      client,
+      snapshot_path,
+      key_provider: Arc::new(key_provider),
+      commit_lock: Arc::new(Mutex::new(())),
+    })
+  }
+
+  /// Returns the path of the snapshot file.
+  pub fn snapshot_path(&self) -> &Path {
+    self.snapshot_path.as_path()
+  }
+
+  /// Encrypts the state of the client with the key provider and persists it to the snapshot file.
+  ///
+  /// The snapshot is written on a blocking thread, so as not to stall the async runtime.
+  async fn persist_snapshot(&self) -> KeyStorageResult<()> {
+    let stronghold: Stronghold = self.stronghold.clone();
+    let snapshot_path: SnapshotPath = self.snapshot_path.clone();
+    let key_provider: Arc<KeyProvider> = Arc::clone(&self.key_provider);
+
+    tokio::task::spawn_blocking(move || {
+      stronghold
+        .commit_with_keyprovider(&snapshot_path, &key_provider)
+        .map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure)
+            .with_custom_message(format!(
+              "unable to write the snapshot `{}`",
+              snapshot_path.as_path().display()
+            ))
+            .with_source(err)
+        })
+    })
+    .await
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("the snapshot write task failed")
+        .with_source(err)
+    })?
+  }
+
+  /// Same as [`StrongholdStorage::persist_snapshot`], but fails with a [`KeyIdStorageError`].
+  async fn persist_key_ids(&self) -> KeyIdStorageResult<()> {
+    self.persist_snapshot().await.map_err(|err| {
+      KeyIdStorageError::new(KeyIdStorageErrorKind::RetryableIOFailure)
+        .with_custom_message("unable to persist the key ids")
+        .with_source(err)
+    })
+  }
+
+  /// Persists a newly added key, removing it from the vault again if the snapshot cannot be written.
+  async fn persist_or_remove_key(&self, key_id: &KeyId) -> KeyStorageResult<()> {
+    let result: KeyStorageResult<()> = self.persist_snapshot().await;
+    if result.is_err() {
+      // The key id is never returned to the caller, so the key would be unusable.
+      let _ = self.client.vault(IDENTITY_VAULT_PATH).delete_secret(key_id.as_str());
+    }
+    result
+  }
+
+  /// Returns an error if no key with the given `key_id` exists.
+  fn ensure_exists(&self, key_id: &KeyId) -> KeyStorageResult<Location> {
+    let location: Location = key_location(key_id);
+    if self.record_exists(&location)? {
+      Ok(location)
+    } else {
+      Err(KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))
+    }
+  }
+
+  fn record_exists(&self, location: &Location) -> KeyStorageResult<bool> {
+    self.client.record_exists(location).map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to check the existence of the key")
+        .with_source(err)
+    })
+  }
+
+  fn public_jwk(&self, location: &Location) -> KeyStorageResult<Jwk> {
+    let public_key: [u8; 32] = self
+      .client
+      .execute_procedure(PublicKey {
+        ty: ProceduresKeyType::Ed25519,
+        private_key: location.clone(),
+      })
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("stronghold public key procedure failed")
+          .with_source(err)
+      })?;
+
+    let mut params: JwkParamsOkp = JwkParamsOkp::new();
+    params.x = jwu::encode_b64(public_key);
+    params.crv = EdCurve::Ed25519.name().to_owned();
+    let mut jwk: Jwk = Jwk::from_params(params);
+    jwk.set_alg(JwsAlgorithm::EdDSA.name());
+
+    Ok(jwk)
+  }
+}
+
+impl Debug for StrongholdStorage {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("StrongholdStorage")
+      .field("snapshot_path", &self.snapshot_path.as_path())
+      .finish_non_exhaustive()
+  }
+}
+
+// Refer to the `JwkStorage` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorage for StrongholdStorage {
+  async fn generate(&self, key_type: KeyType, alg: JwsAlgorithm) -> KeyStorageResult<JwkGenOutput> {
+    check_key_type(&key_type)?;
+    check_alg(alg)?;
+
+    let key_id: KeyId = random_key_id();
+    let location: Location = key_location(&key_id);
+
+    let _commit_guard: MutexGuard<'_, ()> = self.commit_lock.lock().await;
+    self
+      .client
+      .execute_procedure(GenerateKey {
+        ty: ProceduresKeyType::Ed25519,
+        output: location.clone(),
+      })
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("stronghold generate key procedure failed")
+          .with_source(err)
+      })?;
+    self.persist_or_remove_key(&key_id).await?;
+
+    let public_jwk: Jwk = self.public_jwk(&location)?;
+
+    Ok(JwkGenOutput::new(key_id, public_jwk))
+  }
+
+  async fn insert(&self, jwk: Jwk) -> KeyStorageResult<KeyId> {
+    check_insertable_jwk(&jwk)?;
+    // Stronghold only supports Ed25519 keys, which is enforced when expanding the key.
+    let secret_key: SecretKey = ed25519::expand_secret_jwk(&jwk)?;
+
+    let key_id: KeyId = random_key_id();
+    let location: Location = key_location(&key_id);
+
+    let _commit_guard: MutexGuard<'_, ()> = self.commit_lock.lock().await;
+    self
+      .client
+      .vault(IDENTITY_VAULT_PATH)
+      .write_secret(location, secret_key.to_bytes().to_vec())
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("unable to write the key into the vault")
+          .with_source(err)
+      })?;
+    self.persist_or_remove_key(&key_id).await?;
+
+    Ok(key_id)
+  }
+
+  async fn sign(&self, key_id: &KeyId, data: Vec<u8>) -> KeyStorageResult<Vec<u8>> {
+    let location: Location = self.ensure_exists(key_id)?;
+
+    let signature: [u8; 64] = self
+      .client
+      .execute_procedure(Ed25519Sign {
+        msg: data,
+        private_key: location,
+      })
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("stronghold Ed25519 sign procedure failed")
+          .with_source(err)
+      })?;
+
+    Ok(signature.to_vec())
+  }
+
+  async fn public(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    let location: Location = self.ensure_exists(key_id)?;
+    self.public_jwk(&location)
+  }
+
+  async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
+    let _commit_guard: MutexGuard<'_, ()> = self.commit_lock.lock().await;
+    self.ensure_exists(key_id)?;
+
+    self
+      .client
+      .vault(IDENTITY_VAULT_PATH)
+      .delete_secret(key_id.as_str())
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("unable to delete the key from the vault")
+          .with_source(err)
+      })?;
+    self.persist_snapshot().await
+  }
+
+  async fn exists(&self, key_id: &KeyId) -> KeyStorageResult<bool> {
+    self.record_exists(&key_location(key_id))
+  }
+}
+
+// Refer to the `KeyIdStorage` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl KeyIdStorage for StrongholdStorage {
+  async fn insert_key_id(&self, method_digest: MethodDigest, key_id: KeyId) -> KeyIdStorageResult<()> {
+    let key: Vec<u8> = method_digest.pack();
+
+    let _commit_guard: MutexGuard<'_, ()> = self.commit_lock.lock().await;
+    let store = self.client.store();
+    if store.contains_key(&key).map_err(key_id_store_error)? {
+      return Err(KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdAlreadyExists));
+    }
+    store
+      .insert(key.clone(), key_id.as_str().as_bytes().to_vec(), None)
+      .map_err(key_id_store_error)?;
+    if let Err(err) = self.persist_key_ids().await {
+      // Do not keep a key id that is not persisted.
+      let _ = store.delete(&key);
+      return Err(err);
+    }
+    Ok(())
+  }
+
+  async fn get_key_id(&self, method_digest: &MethodDigest) -> KeyIdStorageResult<KeyId> {
+    let key_id: Vec<u8> = self
+      .client
+      .store()
+      .get(&method_digest.pack())
+      .map_err(key_id_store_error)?
+      .ok_or_else(|| KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound))?;
+
+    String::from_utf8(key_id).map(KeyId::new).map_err(|err| {
+      KeyIdStorageError::new(KeyIdStorageErrorKind::SerializationError)
+        .with_custom_message("invalid key id in the stronghold store")
+        .with_source(err)
+    })
+  }
+
+  async fn delete_key_id(&self, method_digest: &MethodDigest) -> KeyIdStorageResult<()> {
+    let _commit_guard: MutexGuard<'_, ()> = self.commit_lock.lock().await;
+    self
+      .client
+      .store()
+      .delete(&method_digest.pack())
+      .map_err(key_id_store_error)?
+      .ok_or_else(|| KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound))?;
+    self.persist_key_ids().await
+  }
+}
+
+/// Returns the location of the key with the given `key_id` in the vault.
+fn key_location(key_id: &KeyId) -> Location {
+  Location::generic(IDENTITY_VAULT_PATH, key_id.as_str())
+}
+
+fn key_id_store_error(err: ClientError) -> KeyIdStorageError {
+  KeyIdStorageError::new(KeyIdStorageErrorKind::Unspecified)
+    .with_custom_message("unable to access the stronghold store")
+    .with_source(err)
+}
+
+fn check_key_type(key_type: &KeyType) -> KeyStorageResult<()> {
+  if key_type.as_str() == ED25519_KEY_TYPE_STR {
+    Ok(())
+  } else {
+    Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("`{key_type}` is not supported")),
+    )
+  }
+}
+
+fn check_alg(alg: JwsAlgorithm) -> KeyStorageResult<()> {
+  match alg {
+    JwsAlgorithm::EdDSA => Ok(()),
+    alg => Err(
+      KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch).with_custom_message(format!(
+        "cannot use key type `{ED25519_KEY_TYPE_STR}` with algorithm `{alg}`"
+      )),
+    ),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use identity_core::convert::FromJson;
+  use identity_verification::VerificationMethod;
+
+  use super::*;
+  use crate::key_storage::test_suite;
+
+  const PASSWORD: &str = "password";
+
+  /// A path in the temporary directory that is removed when dropped.
+  struct TempPath(PathBuf);
+
+  impl TempPath {
+    fn new() -> Self {
+      let mut path: PathBuf = std::env::temp_dir();
+      path.push("test_strongholds");
+      path.push(random_key_id().as_str());
+      path.set_extension("stronghold");
+      Self(path)
+    }
+  }
+
+  impl Drop for TempPath {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_file(&self.0);
+    }
+  }
+
+  fn open(path: &TempPath, password: &str) -> KeyStorageResult<StrongholdStorage> {
+    StrongholdStorage::new(&path.0, password.to_owned())
+  }
+
+  fn method_digest() -> MethodDigest {
+    let method: VerificationMethod = VerificationMethod::from_json(
+      r#"{
+        "id": "did:example:1234#key-1",
+        "controller": "did:example:1234",
+        "type": "Ed25519VerificationKey2018",
+        "publicKeyMultibase": "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+      }"#,
+    )
+    .unwrap();
+    MethodDigest::new(&method).unwrap()
+  }
+
+  #[tokio::test]
+  async fn generate_and_sign() {
+    let path: TempPath = TempPath::new();
+    test_suite::generate_and_sign(open(&path, PASSWORD).unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn insert() {
+    let path: TempPath = TempPath::new();
+    test_suite::insert(open(&path, PASSWORD).unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn exists() {
+    let path: TempPath = TempPath::new();
+    test_suite::exists(open(&path, PASSWORD).unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn incompatible_key_type() {
+    let path: TempPath = TempPath::new();
+    test_suite::incompatible_key_type(open(&path, PASSWORD).unwrap()).await;
+
+    let store: StrongholdStorage = open(&path, PASSWORD).unwrap();
+    let err: KeyStorageError = store
+      .generate(KeyType::new("X25519"), JwsAlgorithm::EdDSA)
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+  }
+
+  #[tokio::test]
+  async fn incompatible_key_alg() {
+    let path: TempPath = TempPath::new();
+    test_suite::incompatible_key_alg(open(&path, PASSWORD).unwrap()).await;
+  }
+
+  #[tokio::test]
+  async fn persists_keys() {
+    let path: TempPath = TempPath::new();
+    let store: StrongholdStorage = open(&path, PASSWORD).unwrap();
+    let JwkGenOutput { key_id, jwk } = store
+      .generate(STRONGHOLD_ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+      .await
+      .unwrap();
+    drop(store);
+
+    let err: KeyStorageError = open(&path, "wrong password").unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::Unauthenticated));
+
+    let store: StrongholdStorage = open(&path, PASSWORD).unwrap();
+    assert!(store.exists(&key_id).await.unwrap());
+    assert_eq!(store.public(&key_id).await.unwrap(), jwk);
+  }
+
+  #[tokio::test]
+  async fn insert_get_delete_key_id() {
+    let path: TempPath = TempPath::new();
+    let store: StrongholdStorage = open(&path, PASSWORD).unwrap();
+    let method_digest: MethodDigest = method_digest();
+    let key_id: KeyId = KeyId::new("key");
+
+    store
+      .insert_key_id(method_digest.clone(), key_id.clone())
+      .await
+      .unwrap();
+    assert_eq!(store.get_key_id(&method_digest).await.unwrap(), key_id);
+
+    // INVALID: Inserting a key id for the same method digest again fails and leaves the original entry untouched.
+    let err: KeyIdStorageError = store
+      .insert_key_id(method_digest.clone(), KeyId::new("other-key"))
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdAlreadyExists));
+    assert_eq!(store.get_key_id(&method_digest).await.unwrap(), key_id);
+
+    store.delete_key_id(&method_digest).await.unwrap();
+    assert!(matches!(
+      store.get_key_id(&method_digest).await.unwrap_err().kind(),
+      KeyIdStorageErrorKind::KeyIdNotFound
+    ));
+
+    // INVALID: Deleting a missing key id fails.
+    let err: KeyIdStorageError = store.delete_key_id(&method_digest).await.unwrap_err();
+    assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdNotFound));
+  }
+
+  #[tokio::test]
+  async fn persists_key_ids() {
+    let path: TempPath = TempPath::new();
+    let store: StrongholdStorage = open(&path, PASSWORD).unwrap();
+    // Clones share the Stronghold, so the key id is visible through the original store.
+    let key_id_store: StrongholdStorage = store.clone();
+    let JwkGenOutput { key_id, .. } = store
+      .generate(STRONGHOLD_ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+      .await
+      .unwrap();
+    key_id_store
+      .insert_key_id(method_digest(), key_id.clone())
+      .await
+      .unwrap();
+    assert_eq!(store.get_key_id(&method_digest()).await.unwrap(), key_id);
+    drop(store);
+    drop(key_id_store);
+
+    let store: StrongholdStorage = open(&path, PASSWORD).unwrap();
+    assert_eq!(store.get_key_id(&method_digest()).await.unwrap(), key_id);
+    assert!(store.exists(&key_id).await.unwrap());
+  }
+}
@@ -0,0 +1,178 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests shared by the [`JwkStorage`] implementations that support the key types of the [`util`](super::util) module.
+
+use crypto::signatures::ed25519::PublicKey;
+use crypto::signatures::ed25519::SecretKey;
+use crypto::signatures::ed25519::Signature;
+use crypto::signatures::ed25519::{self};
+use identity_jose::jwk::EcCurve;
+use identity_jose::jwk::EdCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jwk::JwkParamsEc;
+use identity_jose::jwk::JwkParamsOkp;
+use identity_jose::jws::JwsAlgorithm;
+use identity_jose::jwu;
+
+use super::util::ed25519::encode_jwk;
+use super::JwkGenOutput;
+use super::JwkStorage;
+use super::KeyId;
+use super::KeyStorageErrorKind;
+use super::ED25519_KEY_TYPE;
+
+pub(crate) async fn generate_and_sign(store: impl JwkStorage) {
+  let test_msg: &[u8] = b"test";
+
+  let JwkGenOutput { key_id, jwk } = store.generate(ED25519_KEY_TYPE, JwsAlgorithm::EdDSA).await.unwrap();
+
+  let signature = store.sign(&key_id, test_msg.to_vec()).await.unwrap();
+
+  let public_key: PublicKey = expand_public_jwk(&jwk);
+  let signature: Signature = Signature::from_bytes(signature.try_into().unwrap());
+
+  assert!(public_key.verify(&signature, test_msg));
+  assert!(store.exists(&key_id).await.unwrap());
+  store.delete(&key_id).await.unwrap();
+  assert!(!store.exists(&key_id).await.unwrap());
+}
+
+pub(crate) async fn insert(store: impl JwkStorage) {
+  let private_key: SecretKey = SecretKey::generate().unwrap();
+  let mut jwk: Jwk = encode_jwk(&private_key, &private_key.public_key());
+
+  // INVALID: Inserting a Jwk without an `alg` parameter should fail.
+  let err = store.insert(jwk.clone()).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedSignatureAlgorithm));
+
+  // VALID: Inserting a Jwk with all private key components set should succeed.
+  jwk.set_alg(JwsAlgorithm::EdDSA.name());
+  let key_id: KeyId = store.insert(jwk.clone()).await.unwrap();
+  assert_eq!(store.public(&key_id).await.unwrap(), jwk.to_public());
+
+  // INVALID: Inserting a Jwk with all private key components unset should fail.
+  let err = store.insert(jwk.to_public()).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified))
+}
+
+pub(crate) async fn exists(store: impl JwkStorage) {
+  let key_id: KeyId = KeyId::new("non-existent-id");
+  assert!(!store.exists(&key_id).await.unwrap());
+
+  let err = store.delete(&key_id).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyNotFound));
+}
+
+pub(crate) async fn incompatible_key_type(store: impl JwkStorage) {
+  let mut ec_params = JwkParamsEc::new();
+  ec_params.crv = EcCurve::P384.name().to_owned();
+  ec_params.x = "".to_owned();
+  ec_params.y = "".to_owned();
+  ec_params.d = Some("".to_owned());
+  let jwk_ec = Jwk::from_params(ec_params);
+
+  let err = store.insert(jwk_ec).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+}
+
+pub(crate) async fn incompatible_key_alg(store: impl JwkStorage) {
+  let private_key: SecretKey = SecretKey::generate().unwrap();
+  let mut jwk: Jwk = encode_jwk(&private_key, &private_key.public_key());
+  jwk.set_alg(JwsAlgorithm::ES256.name());
+
+  // INVALID: Inserting an Ed25519 key with the ES256 alg is not compatible.
+  let err = store.insert(jwk).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  // INVALID: Generating an Ed25519 key for the ES256 alg is not compatible.
+  let err = store.generate(ED25519_KEY_TYPE, JwsAlgorithm::ES256).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+}
+
+#[cfg(feature = "es256k")]
+pub(crate) async fn generate_and_sign_es256k(store: impl JwkStorage) {
+  use k256::ecdsa::signature::Verifier;
+
+  use super::SECP256K1_KEY_TYPE;
+
+  let test_msg: &[u8] = b"test";
+
+  let JwkGenOutput { key_id, jwk } = store.generate(SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K).await.unwrap();
+  assert_eq!(jwk.try_ec_curve().unwrap(), EcCurve::Secp256K1);
+  assert!(jwk.is_public());
+
+  let signature = store.sign(&key_id, test_msg.to_vec()).await.unwrap();
+
+  let verifying_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&expand_sec1_public_key(&jwk)).unwrap();
+  let signature = k256::ecdsa::Signature::try_from(signature.as_slice()).unwrap();
+  assert!(verifying_key.verify(test_msg, &signature).is_ok());
+}
+
+#[cfg(feature = "es256")]
+pub(crate) async fn generate_and_sign_es256(store: impl JwkStorage) {
+  use p256::ecdsa::signature::Verifier;
+
+  use super::P256_KEY_TYPE;
+
+  let test_msg: &[u8] = b"test";
+
+  let JwkGenOutput { key_id, jwk } = store.generate(P256_KEY_TYPE, JwsAlgorithm::ES256).await.unwrap();
+  assert_eq!(jwk.try_ec_curve().unwrap(), EcCurve::P256);
+  assert!(jwk.is_public());
+
+  let signature = store.sign(&key_id, test_msg.to_vec()).await.unwrap();
+
+  let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&expand_sec1_public_key(&jwk)).unwrap();
+  let signature = p256::ecdsa::Signature::try_from(signature.as_slice()).unwrap();
+  assert!(verifying_key.verify(test_msg, &signature).is_ok());
+}
+
+#[cfg(all(feature = "es256", feature = "es256k"))]
+pub(crate) async fn incompatible_ec_key_alg(store: impl JwkStorage) {
+  use super::util::secp256k1;
+  use super::P256_KEY_TYPE;
+  use super::SECP256K1_KEY_TYPE;
+
+  // INVALID: The curve of the key type determines the algorithm.
+  let err = store.generate(P256_KEY_TYPE, JwsAlgorithm::ES256K).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+  let err = store
+    .generate(SECP256K1_KEY_TYPE, JwsAlgorithm::ES256)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  // INVALID: Inserting a secp256k1 key with the ES256 alg is not compatible.
+  let mut jwk: Jwk = secp256k1::encode_jwk(&k256::SecretKey::random(&mut rand::thread_rng()));
+  jwk.set_alg(JwsAlgorithm::ES256.name());
+  let err = store.insert(jwk.clone()).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  // VALID: Inserting it with the ES256K alg succeeds.
+  jwk.set_alg(JwsAlgorithm::ES256K.name());
+  store.insert(jwk).await.unwrap();
+}
+
+#[cfg(any(feature = "es256", feature = "es256k"))]
+fn expand_sec1_public_key(jwk: &Jwk) -> Vec<u8> {
+  let params: &JwkParamsEc = jwk.try_ec_params().unwrap();
+  // Transformation according to section 2.3.3 from http://www.secg.org/sec1-v2.pdf.
+  [0x04]
+    .into_iter()
+    .chain(jwu::decode_b64(&params.x).unwrap())
+    .chain(jwu::decode_b64(&params.y).unwrap())
+    .collect()
+}
+
+fn expand_public_jwk(jwk: &Jwk) -> PublicKey {
+  let params: &JwkParamsOkp = jwk.try_okp_params().unwrap();
+
+  if params.try_ed_curve().unwrap() != EdCurve::Ed25519 {
+    panic!("expected an ed25519 jwk");
+  }
+
+  let pk: [u8; ed25519::PUBLIC_KEY_LENGTH] = jwu::decode_b64(params.x.as_str()).unwrap().try_into().unwrap();
+
+  PublicKey::try_from(pk).unwrap()
+}
@@ -0,0 +1,413 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Key generation, validation and signing shared by the [`JwkStorage`](crate::key_storage::JwkStorage)
+//! implementations that hold their keys as private JWKs.
+
+// The `StrongholdStorage` only uses the key id generation and insertion checks.
+#![cfg_attr(not(any(feature = "memstore", feature = "filestore")), allow(dead_code))]
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crypto::signatures::ed25519::SecretKey;
+#[cfg(any(feature = "es256", feature = "es256k"))]
+use identity_jose::jwk::EcCurve;
+use identity_jose::jwk::EdCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jwk::JwkType;
+use identity_jose::jws::JwsAlgorithm;
+use rand::distributions::DistString;
+
+use super::KeyId;
+use super::KeyStorageError;
+use super::KeyStorageErrorKind;
+use super::KeyStorageResult;
+use super::KeyType;
+
+/// Generates a private JWK of the given `key_type` for use with `alg`.
+pub(crate) fn generate_private_jwk(key_type: &KeyType, alg: JwsAlgorithm) -> KeyStorageResult<Jwk> {
+  let key_type: SupportedKeyType = SupportedKeyType::try_from(key_type)?;
+
+  check_key_alg_compatibility(key_type, alg)?;
+
+  let mut jwk: Jwk = match key_type {
+    SupportedKeyType::Ed25519 => {
+      let private_key = SecretKey::generate()
+        .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+      let public_key = private_key.public_key();
+      ed25519::encode_jwk(&private_key, &public_key)
+    }
+    #[cfg(feature = "es256k")]
+    SupportedKeyType::Secp256k1 => {
+      let private_key = k256::SecretKey::random(&mut rand::thread_rng());
+      secp256k1::encode_jwk(&private_key)
+    }
+    #[cfg(feature = "es256")]
+    SupportedKeyType::P256 => {
+      let private_key = p256::SecretKey::random(&mut rand::thread_rng());
+      secp256r1::encode_jwk(&private_key)
+    }
+  };
+
+  jwk.set_alg(alg.name());
+
+  Ok(jwk)
+}
+
+/// Checks that `jwk` is a private key with an `alg` compatible with its key type.
+pub(crate) fn check_insertable_jwk(jwk: &Jwk) -> KeyStorageResult<()> {
+  let key_type = SupportedKeyType::try_from(jwk)?;
+
+  if !jwk.is_private() {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("expected a Jwk with all private key components set"),
+    );
+  }
+
+  match jwk.alg() {
+    Some(alg) => {
+      let alg: JwsAlgorithm = JwsAlgorithm::from_str(alg)
+        .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm).with_source(err))?;
+      check_key_alg_compatibility(key_type, alg)?;
+    }
+    None => {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
+          .with_custom_message("expected a Jwk with an `alg` parameter"),
+      );
+    }
+  }
+
+  Ok(())
+}
+
+/// Signs `data` with the private `jwk` according to its `alg`.
+pub(crate) fn sign_with_jwk(jwk: &Jwk, data: &[u8]) -> KeyStorageResult<Vec<u8>> {
+  let alg: JwsAlgorithm = JwsAlgorithm::from_str(jwk.alg().expect("we should only store Jwks that have an `alg` set"))
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm).with_source(err))?;
+
+  // Note: Because we check for key type and algorithm compatiblity in generate/insert, these errors are impossible.
+  let signature: Vec<u8> = match alg {
+    JwsAlgorithm::EdDSA => {
+      let okp_params = jwk.try_okp_params().map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message(format!("expected a Jwk with Okp params in order to sign with {alg}"))
+          .with_source(err)
+      })?;
+      if okp_params.crv != EdCurve::Ed25519.name() {
+        return Err(
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+            "expected Jwk with Okp {} crv in order to sign with {alg}",
+            EdCurve::Ed25519
+          )),
+        );
+      }
+
+      let secret_key: _ = ed25519::expand_secret_jwk(jwk)?;
+      secret_key.sign(data).to_bytes().to_vec()
+    }
+    #[cfg(feature = "es256k")]
+    JwsAlgorithm::ES256K => {
+      use k256::ecdsa::signature::Signer;
+
+      let signing_key: k256::ecdsa::SigningKey = secp256k1::expand_secret_jwk(jwk)?.into();
+      let signature: k256::ecdsa::Signature = signing_key.sign(data);
+      signature.to_bytes().to_vec()
+    }
+    #[cfg(feature = "es256")]
+    JwsAlgorithm::ES256 => {
+      use p256::ecdsa::signature::Signer;
+
+      let signing_key: p256::ecdsa::SigningKey = secp256r1::expand_secret_jwk(jwk)?.into();
+      let signature: p256::ecdsa::Signature = signing_key.sign(data);
+      signature.to_bytes().to_vec()
+    }
+    other => {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
+          .with_custom_message(format!("{other} is not supported")),
+      );
+    }
+  };
+
+  Ok(signature)
+}
+
+pub(crate) mod ed25519 {
+  use crypto::signatures::ed25519::PublicKey;
+  use crypto::signatures::ed25519::SecretKey;
+  use crypto::signatures::ed25519::{self};
+  use identity_jose::jwk::EdCurve;
+  use identity_jose::jwk::Jwk;
+  use identity_jose::jwk::JwkParamsOkp;
+  use identity_jose::jwu;
+
+  use crate::key_storage::KeyStorageError;
+  use crate::key_storage::KeyStorageErrorKind;
+  use crate::key_storage::KeyStorageResult;
+
+  pub(crate) fn expand_secret_jwk(jwk: &Jwk) -> KeyStorageResult<SecretKey> {
+    let params: &JwkParamsOkp = jwk.try_okp_params().map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message("expected Okp parameters")
+        .with_source(err)
+    })?;
+
+    if params
+      .try_ed_curve()
+      .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_source(err))?
+      != EdCurve::Ed25519
+    {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+          .with_custom_message(format!("expected an {} key", EdCurve::Ed25519.name())),
+      );
+    }
+
+    let sk: [u8; ed25519::SECRET_KEY_LENGTH] = params
+      .d
+      .as_deref()
+      .map(jwu::decode_b64)
+      .ok_or_else(|| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("expected Jwk `d` param to be present")
+      })?
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("unable to decode `d` param")
+          .with_source(err)
+      })?
+      .try_into()
+      .map_err(|_| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message(format!("expected key of length {}", ed25519::SECRET_KEY_LENGTH))
+      })?;
+
+    Ok(SecretKey::from_bytes(sk))
+  }
+
+  pub(crate) fn encode_jwk(private_key: &SecretKey, public_key: &PublicKey) -> Jwk {
+    let x = jwu::encode_b64(public_key.as_ref());
+    let d = jwu::encode_b64(private_key.to_bytes().as_ref());
+    let mut params = JwkParamsOkp::new();
+    params.x = x;
+    params.d = Some(d);
+    params.crv = EdCurve::Ed25519.name().to_owned();
+    Jwk::from_params(params)
+  }
+}
+
+/// Defines a module with the JWK conversions of an elliptic curve from the RustCrypto family,
+/// such as `k256` or `p256`, which share the same `SecretKey` and `EncodedPoint` API.
+macro_rules! ec_jwk_module {
+  ($(#[$attr:meta])* $module:ident, $krate:ident, $curve:expr) => {
+    $(#[$attr])*
+    pub(crate) mod $module {
+      use identity_jose::jwk::EcCurve;
+      use identity_jose::jwk::Jwk;
+      use identity_jose::jwk::JwkParamsEc;
+      use identity_jose::jwu;
+      use $krate::elliptic_curve::sec1::ToEncodedPoint;
+      use $krate::EncodedPoint;
+      use $krate::SecretKey;
+
+      use crate::key_storage::KeyStorageError;
+      use crate::key_storage::KeyStorageErrorKind;
+      use crate::key_storage::KeyStorageResult;
+
+      const CURVE: EcCurve = $curve;
+
+      pub(crate) fn expand_secret_jwk(jwk: &Jwk) -> KeyStorageResult<SecretKey> {
+        let params: &JwkParamsEc = jwk.try_ec_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("expected EC parameters")
+            .with_source(err)
+        })?;
+
+        if params
+          .try_ec_curve()
+          .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_source(err))?
+          != CURVE
+        {
+          return Err(
+            KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+              .with_custom_message(format!("expected a {} key", CURVE.name())),
+          );
+        }
+
+        let sk_bytes: Vec<u8> = params
+          .d
+          .as_deref()
+          .map(jwu::decode_b64)
+          .ok_or_else(|| {
+            KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+              .with_custom_message("expected Jwk `d` param to be present")
+          })?
+          .map_err(|err| {
+            KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+              .with_custom_message("unable to decode `d` param")
+              .with_source(err)
+          })?;
+
+        SecretKey::from_be_bytes(&sk_bytes).map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message(format!("invalid {} secret key", CURVE.name()))
+            .with_source(err)
+        })
+      }
+
+      pub(crate) fn encode_jwk(private_key: &SecretKey) -> Jwk {
+        let public_key: EncodedPoint = private_key.public_key().to_encoded_point(false);
+        let mut params = JwkParamsEc::new();
+        params.x = jwu::encode_b64(public_key.x().expect("an uncompressed point has an x-coordinate"));
+        params.y = jwu::encode_b64(public_key.y().expect("an uncompressed point has a y-coordinate"));
+        params.d = Some(jwu::encode_b64(private_key.to_be_bytes()));
+        params.crv = CURVE.name().to_owned();
+        Jwk::from_params(params)
+      }
+    }
+  };
+}
+
+ec_jwk_module!(
+  #[cfg(feature = "es256k")]
+  secp256k1,
+  k256,
+  EcCurve::Secp256K1
+);
+ec_jwk_module!(
+  #[cfg(feature = "es256")]
+  secp256r1,
+  p256,
+  EcCurve::P256
+);
+
+const ED25519_KEY_TYPE_STR: &str = "Ed25519";
+pub const ED25519_KEY_TYPE: KeyType = KeyType::from_static_str(ED25519_KEY_TYPE_STR);
+#[cfg(feature = "es256k")]
+const SECP256K1_KEY_TYPE_STR: &str = "Secp256k1";
+#[cfg(feature = "es256k")]
+pub const SECP256K1_KEY_TYPE: KeyType = KeyType::from_static_str(SECP256K1_KEY_TYPE_STR);
+#[cfg(feature = "es256")]
+const P256_KEY_TYPE_STR: &str = "P256";
+#[cfg(feature = "es256")]
+pub const P256_KEY_TYPE: KeyType = KeyType::from_static_str(P256_KEY_TYPE_STR);
+
+#[derive(Debug, Copy, Clone)]
+enum SupportedKeyType {
+  Ed25519,
+  #[cfg(feature = "es256k")]
+  Secp256k1,
+  #[cfg(feature = "es256")]
+  P256,
+}
+
+impl SupportedKeyType {
+  pub const fn name(&self) -> &'static str {
+    match self {
+      SupportedKeyType::Ed25519 => ED25519_KEY_TYPE_STR,
+      #[cfg(feature = "es256k")]
+      SupportedKeyType::Secp256k1 => SECP256K1_KEY_TYPE_STR,
+      #[cfg(feature = "es256")]
+      SupportedKeyType::P256 => P256_KEY_TYPE_STR,
+    }
+  }
+}
+
+impl Display for SupportedKeyType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+impl TryFrom<&KeyType> for SupportedKeyType {
+  type Error = KeyStorageError;
+
+  fn try_from(value: &KeyType) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      ED25519_KEY_TYPE_STR => Ok(SupportedKeyType::Ed25519),
+      #[cfg(feature = "es256k")]
+      SECP256K1_KEY_TYPE_STR => Ok(SupportedKeyType::Secp256k1),
+      #[cfg(feature = "es256")]
+      P256_KEY_TYPE_STR => Ok(SupportedKeyType::P256),
+      _ => Err(KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)),
+    }
+  }
+}
+
+impl TryFrom<&Jwk> for SupportedKeyType {
+  type Error = KeyStorageError;
+
+  fn try_from(jwk: &Jwk) -> Result<Self, Self::Error> {
+    match jwk.kty() {
+      JwkType::Okp => {
+        let okp_params = jwk.try_okp_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("expected Okp parameters for a JWK with `kty` Okp")
+            .with_source(err)
+        })?;
+        match okp_params.try_ed_curve().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("only Ed curves are supported for signing")
+            .with_source(err)
+        })? {
+          EdCurve::Ed25519 => Ok(SupportedKeyType::Ed25519),
+          curve => Err(
+            KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+              .with_custom_message(format!("{curve} not supported")),
+          ),
+        }
+      }
+      #[cfg(any(feature = "es256", feature = "es256k"))]
+      JwkType::Ec => {
+        let ec_params = jwk.try_ec_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("expected EC parameters for a JWK with `kty` Ec")
+            .with_source(err)
+        })?;
+        match ec_params.try_ec_curve().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("only secp256k1 and P-256 curves are supported for signing")
+            .with_source(err)
+        })? {
+          #[cfg(feature = "es256k")]
+          EcCurve::Secp256K1 => Ok(SupportedKeyType::Secp256k1),
+          #[cfg(feature = "es256")]
+          EcCurve::P256 => Ok(SupportedKeyType::P256),
+          curve => Err(
+            KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+              .with_custom_message(format!("{curve} not supported")),
+          ),
+        }
+      }
+      other => Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+          .with_custom_message(format!("Jwk `kty` {other} not supported")),
+      ),
+    }
+  }
+}
+
+/// Generate a random alphanumeric string of len 32.
+pub(crate) fn random_key_id() -> KeyId {
+  KeyId::new(rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 32))
+}
+
+/// Check that the key type can be used with the algorithm.
+fn check_key_alg_compatibility(key_type: SupportedKeyType, alg: JwsAlgorithm) -> KeyStorageResult<()> {
+  match (key_type, alg) {
+    (SupportedKeyType::Ed25519, JwsAlgorithm::EdDSA) => Ok(()),
+    #[cfg(feature = "es256k")]
+    (SupportedKeyType::Secp256k1, JwsAlgorithm::ES256K) => Ok(()),
+    #[cfg(feature = "es256")]
+    (SupportedKeyType::P256, JwsAlgorithm::ES256) => Ok(()),
+    (key_type, alg) => {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+          .with_custom_message(format!("`cannot use key type `{key_type}` with algorithm `{alg}`")),
+      )
+    }
+  }
+}
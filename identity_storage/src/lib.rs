@@ -1,4 +1,10 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod key_id_storage;
 pub mod key_storage;
+#[cfg(feature = "revocation-bitmap")]
+pub mod revocation_index_storage;
+pub mod storage;
+#[cfg(any(feature = "memstore", feature = "filestore"))]
+pub(crate) mod utils;
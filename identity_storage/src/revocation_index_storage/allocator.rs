@@ -0,0 +1,187 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_credential::credential::RevocationBitmapStatus;
+use identity_credential::revocation::RevocationBitmap;
+use identity_credential::revocation::StatusPurpose;
+use identity_did::DIDUrl;
+use identity_document::document::CoreDocument;
+use identity_document::service::Service;
+use identity_document::utils::DIDUrlQuery;
+
+use crate::revocation_index_storage::RevocationIndexStorage;
+use crate::revocation_index_storage::RevocationIndexStorageError;
+use crate::revocation_index_storage::RevocationIndexStorageErrorKind;
+use crate::revocation_index_storage::RevocationIndexStorageResult;
+
+/// Hands out fresh indices of `RevocationBitmap2022` services to credentials.
+///
+/// Indices in use are tracked through a [`RevocationIndexStorage`]. Indices are allocated in increasing order,
+/// starting after the highest index reserved so far, and indices already set in the bitmap are skipped.
+///
+/// The allocated [`RevocationBitmapStatus`] can be converted into a
+/// [`Status`](identity_credential::credential::Status) and passed to
+/// [`CredentialBuilder::status`](identity_credential::credential::CredentialBuilder::status).
+#[derive(Debug)]
+pub struct RevocationIndexAllocator<S> {
+  storage: S,
+}
+
+impl<S: RevocationIndexStorage> RevocationIndexAllocator<S> {
+  /// Constructs a new [`RevocationIndexAllocator`] tracking allocated indices in `storage`.
+  pub fn new(storage: S) -> Self {
+    Self { storage }
+  }
+
+  /// Returns a reference to the underlying [`RevocationIndexStorage`].
+  pub fn storage(&self) -> &S {
+    &self.storage
+  }
+
+  /// Reserves a fresh index of the `RevocationBitmap2022` service identified by `service_query` in `document`
+  /// and returns the matching [`RevocationBitmapStatus`].
+  ///
+  /// The purpose of the status is the [`StatusPurpose`] of the service.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the service does not exist or is not a valid `RevocationBitmap2022` service,
+  /// if all indices are in use or if the storage operation fails.
+  pub async fn allocate<'query, DOC, Q>(
+    &self,
+    document: &DOC,
+    service_query: Q,
+  ) -> RevocationIndexStorageResult<RevocationBitmapStatus>
+  where
+    DOC: AsRef<CoreDocument>,
+    Q: Into<DIDUrlQuery<'query>>,
+  {
+    let service: &Service = document
+      .as_ref()
+      .resolve_service(service_query)
+      .ok_or_else(|| RevocationIndexStorageError::new(RevocationIndexStorageErrorKind::InvalidService))?;
+    let bitmap: RevocationBitmap = RevocationBitmap::try_from(service).map_err(|err| {
+      RevocationIndexStorageError::new(RevocationIndexStorageErrorKind::InvalidService).with_source(err)
+    })?;
+    let purpose: StatusPurpose = RevocationBitmap::service_purpose(service).map_err(|err| {
+      RevocationIndexStorageError::new(RevocationIndexStorageErrorKind::InvalidService).with_source(err)
+    })?;
+    let service_id: &DIDUrl = service.id();
+
+    let start: u32 = match self.storage.last_reserved_index(service_id).await? {
+      Some(last) => last
+        .checked_add(1)
+        .ok_or_else(|| RevocationIndexStorageError::new(RevocationIndexStorageErrorKind::IndexSpaceExhausted))?,
+      None => 0,
+    };
+
+    for index in start..=u32::MAX {
+      // Indices set in the bitmap must have been handed out before, e.g. by another allocator.
+      if bitmap.is_revoked(index) {
+        continue;
+      }
+      if self.storage.reserve_index(service_id, index).await? {
+        return Ok(match purpose {
+          StatusPurpose::Revocation => RevocationBitmapStatus::new(service_id.clone(), index),
+          StatusPurpose::Suspension => RevocationBitmapStatus::new_with_purpose(service_id.clone(), index, purpose),
+        });
+      }
+    }
+
+    Err(RevocationIndexStorageError::new(
+      RevocationIndexStorageErrorKind::IndexSpaceExhausted,
+    ))
+  }
+}
+
+#[cfg(all(test, feature = "memstore"))]
+mod tests {
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+  use identity_credential::credential::Credential;
+  use identity_credential::credential::CredentialBuilder;
+  use identity_credential::credential::Subject;
+  use identity_credential::revocation::RevocationDocumentExt;
+  use identity_did::DID;
+
+  use crate::revocation_index_storage::RevocationIndexMemStore;
+
+  use super::*;
+
+  fn document() -> (CoreDocument, DIDUrl, DIDUrl) {
+    let mut document: CoreDocument =
+      CoreDocument::from_json_value(serde_json::json!({ "id": "did:example:1234" })).unwrap();
+    let revocation_id: DIDUrl = document.id().to_url().join("#revocation").unwrap();
+    let suspension_id: DIDUrl = document.id().to_url().join("#suspension").unwrap();
+    for (id, purpose) in [
+      (&revocation_id, StatusPurpose::Revocation),
+      (&suspension_id, StatusPurpose::Suspension),
+    ] {
+      document
+        .insert_service(RevocationBitmap::new().to_service(id.clone(), purpose).unwrap())
+        .unwrap();
+    }
+    (document, revocation_id, suspension_id)
+  }
+
+  #[tokio::test]
+  async fn test_allocate() {
+    let (mut document, revocation_id, suspension_id) = document();
+    let allocator: RevocationIndexAllocator<RevocationIndexMemStore> =
+      RevocationIndexAllocator::new(RevocationIndexMemStore::new());
+
+    // Indices are handed out in order, per service.
+    for expected in 0..3 {
+      let status: RevocationBitmapStatus = allocator.allocate(&document, &revocation_id).await.unwrap();
+      assert_eq!(status.index().unwrap(), expected);
+      assert_eq!(status.purpose().unwrap(), StatusPurpose::Revocation);
+    }
+    let status: RevocationBitmapStatus = allocator.allocate(&document, "#suspension").await.unwrap();
+    assert_eq!(status.index().unwrap(), 0);
+    assert_eq!(status.purpose().unwrap(), StatusPurpose::Suspension);
+    assert!(allocator.storage().is_index_in_use(&suspension_id, 0).await.unwrap());
+
+    // Indices revoked by another issuing service are skipped.
+    document.revoke_credentials(&revocation_id, &[3, 4]).unwrap();
+    let status: RevocationBitmapStatus = allocator.allocate(&document, &revocation_id).await.unwrap();
+    assert_eq!(status.index().unwrap(), 5);
+
+    // The status can be used to build a credential.
+    let credential: Credential = CredentialBuilder::default()
+      .issuer(Url::parse(document.id().as_str()).unwrap())
+      .subject(Subject::with_id(Url::parse("did:example:subject").unwrap()))
+      .issuance_date(Timestamp::now_utc())
+      .status(status.into())
+      .build()
+      .unwrap();
+    assert!(credential.credential_status.is_some());
+  }
+
+  #[tokio::test]
+  async fn test_allocate_invalid_service() {
+    let (document, ..) = document();
+    let allocator: RevocationIndexAllocator<RevocationIndexMemStore> =
+      RevocationIndexAllocator::new(RevocationIndexMemStore::new());
+    assert!(matches!(
+      allocator.allocate(&document, "#missing").await.unwrap_err().kind(),
+      RevocationIndexStorageErrorKind::InvalidService
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_allocate_exhausted() {
+    let (document, revocation_id, _) = document();
+    let allocator: RevocationIndexAllocator<RevocationIndexMemStore> =
+      RevocationIndexAllocator::new(RevocationIndexMemStore::new());
+    assert!(allocator
+      .storage()
+      .reserve_index(&revocation_id, u32::MAX)
+      .await
+      .unwrap());
+    assert!(matches!(
+      allocator.allocate(&document, &revocation_id).await.unwrap_err().kind(),
+      RevocationIndexStorageErrorKind::IndexSpaceExhausted
+    ));
+  }
+}
@@ -0,0 +1,89 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use identity_did::DIDUrl;
+use tokio::sync::RwLockReadGuard;
+use tokio::sync::RwLockWriteGuard;
+
+use crate::revocation_index_storage::RevocationIndexStorage;
+use crate::revocation_index_storage::RevocationIndexStorageResult;
+use crate::utils::Shared;
+
+/// The map from service ids to the indices in use.
+type RevocationIndexStore = HashMap<String, BTreeSet<u32>>;
+
+/// An in-memory [`RevocationIndexStorage`] implementation that serves as an example and may be used in tests.
+#[derive(Debug)]
+pub struct RevocationIndexMemStore {
+  index_store: Shared<RevocationIndexStore>,
+}
+
+impl RevocationIndexMemStore {
+  /// Creates a new, empty `RevocationIndexMemStore` instance.
+  pub fn new() -> Self {
+    Self {
+      index_store: Shared::new(HashMap::new()),
+    }
+  }
+}
+
+impl Default for RevocationIndexMemStore {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// Refer to the `RevocationIndexStorage` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl RevocationIndexStorage for RevocationIndexMemStore {
+  async fn reserve_index(&self, service_id: &DIDUrl, index: u32) -> RevocationIndexStorageResult<bool> {
+    let mut index_store: RwLockWriteGuard<'_, RevocationIndexStore> = self.index_store.write().await;
+    Ok(index_store.entry(service_id.to_string()).or_default().insert(index))
+  }
+
+  async fn is_index_in_use(&self, service_id: &DIDUrl, index: u32) -> RevocationIndexStorageResult<bool> {
+    let index_store: RwLockReadGuard<'_, RevocationIndexStore> = self.index_store.read().await;
+    Ok(
+      index_store
+        .get(&service_id.to_string())
+        .map(|indices| indices.contains(&index))
+        .unwrap_or(false),
+    )
+  }
+
+  async fn last_reserved_index(&self, service_id: &DIDUrl) -> RevocationIndexStorageResult<Option<u32>> {
+    let index_store: RwLockReadGuard<'_, RevocationIndexStore> = self.index_store.read().await;
+    Ok(
+      index_store
+        .get(&service_id.to_string())
+        .and_then(|indices| indices.iter().next_back().copied()),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn test_reserve_index() {
+    let store: RevocationIndexMemStore = RevocationIndexMemStore::new();
+    let service_1: DIDUrl = DIDUrl::parse("did:example:1234#revocation").unwrap();
+    let service_2: DIDUrl = DIDUrl::parse("did:example:1234#suspension").unwrap();
+
+    assert_eq!(store.last_reserved_index(&service_1).await.unwrap(), None);
+    assert!(store.reserve_index(&service_1, 5).await.unwrap());
+    assert!(store.reserve_index(&service_1, 2).await.unwrap());
+    assert!(!store.reserve_index(&service_1, 5).await.unwrap());
+    assert!(store.reserve_index(&service_2, 5).await.unwrap());
+
+    assert!(store.is_index_in_use(&service_1, 2).await.unwrap());
+    assert!(!store.is_index_in_use(&service_1, 3).await.unwrap());
+    assert_eq!(store.last_reserved_index(&service_1).await.unwrap(), Some(5));
+  }
+}
@@ -0,0 +1,22 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A Revocation Index Storage tracks which indices of `RevocationBitmap2022` services
+//! have been handed out to credentials.
+//!
+//! This module provides the [`RevocationIndexStorage`] trait and the [`RevocationIndexAllocator`],
+//! which hands out fresh indices and produces the matching
+//! [`RevocationBitmapStatus`](identity_credential::credential::RevocationBitmapStatus).
+
+mod allocator;
+#[cfg(feature = "memstore")]
+mod memstore;
+#[allow(clippy::module_inception)]
+mod revocation_index_storage;
+mod revocation_index_storage_error;
+
+pub use allocator::*;
+#[cfg(feature = "memstore")]
+pub use memstore::*;
+pub use revocation_index_storage::*;
+pub use revocation_index_storage_error::*;
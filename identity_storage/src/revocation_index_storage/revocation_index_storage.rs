@@ -0,0 +1,42 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use identity_did::DIDUrl;
+
+use crate::revocation_index_storage::RevocationIndexStorageError;
+
+/// Result of revocation index storage operations.
+pub type RevocationIndexStorageResult<T> = Result<T, RevocationIndexStorageError>;
+
+#[cfg(not(feature = "send-sync-storage"))]
+mod storage_sub_trait {
+  pub trait StorageSendSyncMaybe {}
+  impl<S: super::RevocationIndexStorage> StorageSendSyncMaybe for S {}
+}
+
+#[cfg(feature = "send-sync-storage")]
+mod storage_sub_trait {
+  pub trait StorageSendSyncMaybe: Send + Sync {}
+  impl<S: Send + Sync + super::RevocationIndexStorage> StorageSendSyncMaybe for S {}
+}
+
+/// Storage tracking which indices of `RevocationBitmap2022` services have been handed out to credentials.
+///
+/// Indices are tracked per service, identified by the [`DIDUrl`] of the service.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+pub trait RevocationIndexStorage: storage_sub_trait::StorageSendSyncMaybe {
+  /// Marks `index` of the service identified by `service_id` as in use.
+  ///
+  /// Returns `false` without altering the state of the storage if the index is already in use.
+  /// This check and the update must happen atomically, so that the same index is never reserved twice,
+  /// even if the storage is shared by multiple issuing services.
+  async fn reserve_index(&self, service_id: &DIDUrl, index: u32) -> RevocationIndexStorageResult<bool>;
+
+  /// Returns whether `index` of the service identified by `service_id` is in use.
+  async fn is_index_in_use(&self, service_id: &DIDUrl, index: u32) -> RevocationIndexStorageResult<bool>;
+
+  /// Returns the highest index reserved for the service identified by `service_id`, if any.
+  async fn last_reserved_index(&self, service_id: &DIDUrl) -> RevocationIndexStorageResult<Option<u32>>;
+}
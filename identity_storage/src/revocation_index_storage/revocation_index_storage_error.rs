@@ -0,0 +1,207 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::Display;
+
+/// The error type for revocation index storage and allocation operations.
+///
+/// Instances always carry a corresponding [`RevocationIndexStorageErrorKind`] and may be extended with custom error messages and
+/// source.
+#[derive(Debug)]
+pub struct RevocationIndexStorageError {
+  repr: Repr,
+}
+
+impl Display for RevocationIndexStorageError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.repr {
+      Repr::Simple(ref cause) => write!(f, "{}", cause.as_str()),
+      Repr::Extensive(ref extensive) => {
+        write!(f, "{}", extensive.cause.as_str())?;
+        let Some(ref message) = extensive.message else {
+          return Ok(());
+        };
+        write!(f, " message: {}", message.as_ref())
+      }
+    }
+  }
+}
+
+impl Error for RevocationIndexStorageError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    self.extensive().and_then(|err| {
+      err
+        .source
+        .as_ref()
+        .map(|source| source.as_ref() as &(dyn Error + 'static))
+    })
+  }
+}
+
+#[derive(Debug)]
+struct Extensive {
+  cause: RevocationIndexStorageErrorKind,
+  source: Option<Box<dyn Error + Send + Sync + 'static>>,
+  message: Option<Cow<'static, str>>,
+}
+
+#[derive(Debug)]
+enum Repr {
+  Simple(RevocationIndexStorageErrorKind),
+  Extensive(Box<Extensive>),
+}
+
+impl From<RevocationIndexStorageErrorKind> for RevocationIndexStorageError {
+  fn from(cause: RevocationIndexStorageErrorKind) -> Self {
+    Self::new(cause)
+  }
+}
+
+impl From<Box<Extensive>> for RevocationIndexStorageError {
+  fn from(extensive: Box<Extensive>) -> Self {
+    Self {
+      repr: Repr::Extensive(extensive),
+    }
+  }
+}
+
+impl RevocationIndexStorageError {
+  /// Constructs a new [`RevocationIndexStorageError`].
+  pub fn new(cause: RevocationIndexStorageErrorKind) -> Self {
+    Self {
+      repr: Repr::Simple(cause),
+    }
+  }
+
+  /// Returns a reference to corresponding [`RevocationIndexStorageErrorKind`] of this error.
+  pub fn kind(&self) -> &RevocationIndexStorageErrorKind {
+    match self.repr {
+      Repr::Simple(ref cause) => cause,
+      Repr::Extensive(ref extensive) => &extensive.cause,
+    }
+  }
+
+  /// Converts this error into the corresponding [`RevocationIndexStorageErrorKind`] of this error.
+  pub fn into_kind(self) -> RevocationIndexStorageErrorKind {
+    match self.repr {
+      Repr::Simple(cause) => cause,
+      Repr::Extensive(extensive) => extensive.cause,
+    }
+  }
+
+  /// Returns a reference to the custom message of the [`RevocationIndexStorageError`] if it was set.
+  pub fn custom_message(&self) -> Option<&str> {
+    self
+      .extensive()
+      .into_iter()
+      .flat_map(|extensive| extensive.message.as_deref())
+      .next()
+  }
+
+  /// Returns a reference to the attached source of the [`RevocationIndexStorageError`] if it was set.
+  pub fn source_ref(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+    self.extensive().and_then(|extensive| extensive.source.as_deref())
+  }
+
+  /// Converts this error into the source error if it was set.
+  pub fn into_source(self) -> Option<Box<dyn Error + Send + Sync + 'static>> {
+    self.into_extensive().source
+  }
+
+  fn extensive(&self) -> Option<&Extensive> {
+    match self.repr {
+      Repr::Extensive(ref extensive) => Some(extensive.as_ref()),
+      _ => None,
+    }
+  }
+
+  fn into_extensive(self) -> Box<Extensive> {
+    match self.repr {
+      Repr::Extensive(extensive) => extensive,
+      Repr::Simple(cause) => Box::new(Extensive {
+        cause,
+        source: None,
+        message: None,
+      }),
+    }
+  }
+
+  /// Updates the `source` of the [`RevocationIndexStorageError`].
+  pub fn with_source(self, source: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+    self._with_source(source.into())
+  }
+
+  fn _with_source(self, source: Box<dyn Error + Send + Sync + 'static>) -> Self {
+    let mut extensive = self.into_extensive();
+    extensive.as_mut().source = Some(source);
+    Self::from(extensive)
+  }
+
+  /// Updates the custom message of the [`RevocationIndexStorageError`].
+  pub fn with_custom_message(self, message: impl Into<Cow<'static, str>>) -> Self {
+    self._with_custom_message(message.into())
+  }
+
+  fn _with_custom_message(self, message: Cow<'static, str>) -> Self {
+    let mut extensive = self.into_extensive();
+    extensive.as_mut().message = Some(message);
+    Self::from(extensive)
+  }
+}
+
+/// The cause of the failed revocation index storage or allocation operation.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum RevocationIndexStorageErrorKind {
+  /// Indicates that every index of the revocation bitmap is already in use.
+  IndexSpaceExhausted,
+
+  /// Indicates that the document does not contain a valid `RevocationBitmap2022` service for the given query.
+  InvalidService,
+
+  /// Indicates that the storage is unavailable for an unpredictable amount of time.
+  ///
+  /// Occurrences of this variant should hopefully be rare, but could occur if hardware fails, or a hosted revocation
+  /// index store goes offline.
+  Unavailable,
+
+  /// Indicates that an attempt was made to authenticate with the revocation index storage, but the operation did not
+  /// succeed.
+  Unauthenticated,
+
+  /// Indicates an unsuccessful I/O operation that may be retried, such as a temporary connection failure or timeouts.
+  ///
+  /// Returning this error signals to the caller that the operation may be retried with a chance of success.
+  /// It is at the caller's discretion whether to retry or not, and how often.
+  RetryableIOFailure,
+
+  /// Indicates that something went wrong, but it is unclear whether the reason matches any of the other variants.
+  ///
+  /// When using this variant one may want to attach additional context to the corresponding
+  /// [`RevocationIndexStorageError`]. See
+  /// [`RevocationIndexStorageError::with_custom_message`](RevocationIndexStorageError::with_custom_message()) and
+  /// [`RevocationIndexStorageError::with_source`](RevocationIndexStorageError::with_source()).
+  Unspecified,
+}
+
+impl RevocationIndexStorageErrorKind {
+  /// Returns a report friendly representation of the [`RevocationIndexStorageErrorKind`].
+  const fn as_str(&self) -> &str {
+    match self {
+      Self::IndexSpaceExhausted => "no unused revocation index left",
+      Self::InvalidService => "invalid or missing revocation bitmap service",
+      Self::Unavailable => "revocation index storage unavailable",
+      Self::Unauthenticated => "authentication with the revocation index storage failed",
+      Self::RetryableIOFailure => "revocation index storage was unsuccessful because of an I/O failure",
+      Self::Unspecified => "revocation index storage operation failed",
+    }
+  }
+}
+
+impl Display for RevocationIndexStorageErrorKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
@@ -0,0 +1,51 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::key_id_storage::KeyIdStorageError;
+use crate::key_storage::KeyStorageError;
+
+/// Alias for a `Result` with the error type [`JwkStorageDocumentError`].
+pub type StorageResult<T> = Result<T, JwkStorageDocumentError>;
+
+/// Errors that can occur when working with the [`JwkDocumentExt`](crate::storage::JwkDocumentExt) API.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum JwkStorageDocumentError {
+  /// Caused by a failure in the key storage.
+  #[error("storage operation failed: key storage error")]
+  KeyStorageError(#[source] KeyStorageError),
+  /// Caused by a failure in the key id storage.
+  #[error("storage operation failed: key id storage error")]
+  KeyIdStorageError(#[source] KeyIdStorageError),
+  /// Caused by an attempt to add a method with a fragment that already exists.
+  #[error("could not add method: the fragment already exists")]
+  FragmentAlreadyExists,
+  /// Caused by a missing verification method.
+  #[error("method not found")]
+  MethodNotFound,
+  /// Caused by the usage of a verification method whose public key is not represented as a JWK.
+  #[error("verification method is not a public key JWK")]
+  NotPublicKeyJwk,
+  /// Caused by a failure to compute the [`MethodDigest`](crate::key_id_storage::MethodDigest) of a method.
+  #[error("unable to compute the method digest")]
+  MethodDigestConstructionError(#[source] KeyIdStorageError),
+  /// Caused by a failure to construct a verification method.
+  #[error("method generation failed: unable to create a valid verification method")]
+  VerificationMethodConstructionError(#[source] identity_verification::Error),
+  /// Caused by a verification method whose key does not specify a valid JWS algorithm.
+  #[error("invalid JWS algorithm")]
+  InvalidJwsAlgorithm,
+  /// Caused by a failure to encode a credential or presentation as a JWT.
+  #[error("could not produce JWS")]
+  EncodingError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by a failure to undo a previously executed step.
+  #[error("undoing previously executed operation failed: {message}")]
+  UndoOperationFailed {
+    /// Description of the operation that could not be undone.
+    message: String,
+    /// The error that caused the undo operation to be attempted.
+    source: Box<Self>,
+    /// The error that occurred while undoing.
+    undo_error: Option<Box<Self>>,
+  },
+}
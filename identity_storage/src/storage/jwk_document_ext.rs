@@ -0,0 +1,701 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::MethodDigest;
+use crate::key_storage::JwkGenOutput;
+use crate::key_storage::JwkStorage;
+use crate::key_storage::KeyId;
+use crate::key_storage::KeyType;
+
+use super::JwkStorageDocumentError as Error;
+use super::Storage;
+use super::StorageResult;
+
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use identity_credential::credential::Credential;
+use identity_credential::credential::Jwt;
+use identity_credential::presentation::JwtPresentationOptions;
+use identity_credential::presentation::Presentation;
+use identity_did::DIDUrl;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+use identity_jose::jwk::Jwk;
+use identity_jose::jws::Encoder;
+use identity_jose::jws::JwsAlgorithm;
+use identity_jose::jws::JwsHeader;
+use identity_jose::jws::Recipient;
+use identity_jose::jwu;
+use identity_verification::MethodScope;
+use identity_verification::VerificationMethod;
+use serde::Serialize;
+
+/// Extension trait for DID documents whose verification methods are backed by a [`JwkStorage`].
+///
+/// The operations of this trait keep the document and the storage consistent, i.e. a key is only kept in the storage
+/// if the corresponding verification method was successfully added to the document and vice versa. The mapping from
+/// verification methods to their keys is recorded in a [`KeyIdStorage`].
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+pub trait JwkDocumentExt {
+  /// Generate a new key in the key storage and insert it into the document as a verification method of type
+  /// `JsonWebKey2020` in the given `scope`. The [`KeyId`] of the key is recorded in the key id storage.
+  ///
+  /// If no `fragment` is provided, the JWK thumbprint of the generated public key is used as the fragment.
+  ///
+  /// Returns the fragment of the inserted method.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if a method with the given `fragment` already exists, or if any of the steps fails. In the
+  /// latter case all previous modifications of the storage are undone.
+  async fn generate_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    key_type: KeyType,
+    alg: JwsAlgorithm,
+    fragment: Option<&str>,
+    scope: MethodScope,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage;
+
+  /// Remove the method identified by `id` from the document and delete the corresponding key and key id from the
+  /// storage.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the method does not exist or if its key id cannot be found. The method is only removed
+  /// from the document if both the key and the key id were deleted successfully.
+  async fn purge_method<K, I>(&mut self, storage: &Storage<K, I>, id: &DIDUrl) -> StorageResult<()>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage;
+
+  /// Encode `credential` as a JWT and sign it with the key of the method identified by `fragment`.
+  ///
+  /// The claims follow the [VC-JWT encoding](https://www.w3.org/TR/vc-data-model/#json-web-token) and the `kid`
+  /// header parameter is set to the id of the method. The signature is created by the key storage, so the private
+  /// key never leaves it.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the method does not exist, is not a `publicKeyJwk` with a valid `alg` parameter, or if its
+  /// key cannot be found in the storage.
+  async fn create_credential_jwt<K, I, T>(
+    &self,
+    credential: &Credential<T>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+  ) -> StorageResult<Jwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: ToOwned<Owned = T> + Serialize + Sync;
+
+  /// Encode `presentation` as a JWT and sign it with the key of the method identified by `fragment`.
+  ///
+  /// See [`JwkDocumentExt::create_credential_jwt`] for details.
+  async fn create_presentation_jwt<K, I, T, U>(
+    &self,
+    presentation: &Presentation<T, U>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    options: &JwtPresentationOptions,
+  ) -> StorageResult<Jwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: ToOwned<Owned = T> + Serialize + Sync,
+    U: Clone + Serialize + Sync;
+}
+
+/// Attempts to delete the key identified by `key_id` after `source` caused an operation to fail.
+///
+/// Returns the error to surface to the caller.
+async fn try_undo_key_generation<K, I>(storage: &Storage<K, I>, key_id: &KeyId, source: Error) -> Error
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+{
+  match storage.key_storage().delete(key_id).await {
+    Ok(()) => source,
+    Err(err) => Error::UndoOperationFailed {
+      message: format!("unable to delete stray key with id: {key_id}"),
+      source: Box::new(source),
+      undo_error: Some(Box::new(Error::KeyStorageError(err))),
+    },
+  }
+}
+
+/// Attempts to delete both the key id and the key after `source` caused the insertion of a generated method to fail.
+///
+/// Returns the error to surface to the caller.
+async fn try_undo_method_generation<K, I>(
+  storage: &Storage<K, I>,
+  method_digest: &MethodDigest,
+  key_id: &KeyId,
+  source: Error,
+) -> Error
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+{
+  if let Err(err) = storage.key_id_storage().delete_key_id(method_digest).await {
+    return Error::UndoOperationFailed {
+      message: format!("unable to delete stray key id for key with id: {key_id}"),
+      source: Box::new(source),
+      undo_error: Some(Box::new(Error::KeyIdStorageError(err))),
+    };
+  }
+  try_undo_key_generation(storage, key_id, source).await
+}
+
+/// Generates a key and records its key id for the method constructed from it, undoing the storage modifications
+/// on failure.
+///
+/// Returns the method, which has not been inserted into any document yet, together with its digest and key id.
+async fn generate_method_with_storage<K, I, D>(
+  did: D,
+  storage: &Storage<K, I>,
+  key_type: KeyType,
+  alg: JwsAlgorithm,
+  fragment: Option<&str>,
+) -> StorageResult<(VerificationMethod, MethodDigest, KeyId)>
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+  D: DID,
+{
+  let JwkGenOutput { key_id, jwk } = storage
+    .key_storage()
+    .generate(key_type, alg)
+    .await
+    .map_err(Error::KeyStorageError)?;
+
+  let method: VerificationMethod =
+    match VerificationMethod::new_from_jwk(did, jwk, fragment).map_err(Error::VerificationMethodConstructionError) {
+      Ok(method) => method,
+      Err(source) => return Err(try_undo_key_generation(storage, &key_id, source).await),
+    };
+
+  let method_digest: MethodDigest = match MethodDigest::new(&method).map_err(Error::MethodDigestConstructionError) {
+    Ok(method_digest) => method_digest,
+    Err(source) => return Err(try_undo_key_generation(storage, &key_id, source).await),
+  };
+
+  if let Err(source) = storage
+    .key_id_storage()
+    .insert_key_id(method_digest.clone(), key_id.clone())
+    .await
+    .map_err(Error::KeyIdStorageError)
+  {
+    return Err(try_undo_key_generation(storage, &key_id, source).await);
+  }
+
+  Ok((method, method_digest, key_id))
+}
+
+/// Deletes the key and key id of `method` from the storage.
+///
+/// The key id is deleted first, so a failure to delete the key leaves an orphaned key rather than a key id
+/// referencing a key that no longer exists.
+async fn purge_method_from_storage<K, I>(storage: &Storage<K, I>, method: &VerificationMethod) -> StorageResult<()>
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+{
+  let method_digest: MethodDigest = MethodDigest::new(method).map_err(Error::MethodDigestConstructionError)?;
+  let key_id: KeyId = storage
+    .key_id_storage()
+    .get_key_id(&method_digest)
+    .await
+    .map_err(Error::KeyIdStorageError)?;
+
+  storage
+    .key_id_storage()
+    .delete_key_id(&method_digest)
+    .await
+    .map_err(Error::KeyIdStorageError)?;
+  storage
+    .key_storage()
+    .delete(&key_id)
+    .await
+    .map_err(Error::KeyStorageError)
+}
+
+/// Signs `payload` with the key of `method` and returns the resulting JWS in compact serialization.
+async fn sign_jws<K, I>(storage: &Storage<K, I>, method: &VerificationMethod, payload: &[u8]) -> StorageResult<Jwt>
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+{
+  let jwk: &Jwk = method.data().public_key_jwk().ok_or(Error::NotPublicKeyJwk)?;
+  let alg: JwsAlgorithm = jwk
+    .alg()
+    .and_then(|alg| JwsAlgorithm::from_str(alg).ok())
+    .ok_or(Error::InvalidJwsAlgorithm)?;
+
+  let method_digest: MethodDigest = MethodDigest::new(method).map_err(Error::MethodDigestConstructionError)?;
+  let key_id: KeyId = storage
+    .key_id_storage()
+    .get_key_id(&method_digest)
+    .await
+    .map_err(Error::KeyIdStorageError)?;
+
+  let mut header: JwsHeader = JwsHeader::new();
+  header.set_alg(alg);
+  header.set_typ("JWT");
+  header.set_kid(method.id().to_string());
+
+  let key_id: &KeyId = &key_id;
+  let sign_fn = move |_protected, _unprotected, message: Vec<u8>| async move {
+    storage.key_storage().sign(key_id, message).await.map(jwu::encode_b64)
+  };
+
+  Encoder::new()
+    .recipient(Recipient::new().protected(&header))
+    .encode(&sign_fn, payload)
+    .await
+    .map(Jwt::new)
+    .map_err(|err| Error::EncodingError(err.into()))
+}
+
+/// Implements [`JwkDocumentExt`] for a document type exposing the same method-related API as [`CoreDocument`].
+macro_rules! impl_jwk_document_ext {
+  ($document:ty) => {
+    #[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+    #[cfg_attr(feature = "send-sync-storage", async_trait)]
+    impl JwkDocumentExt for $document {
+      async fn generate_method<K, I>(
+        &mut self,
+        storage: &Storage<K, I>,
+        key_type: KeyType,
+        alg: JwsAlgorithm,
+        fragment: Option<&str>,
+        scope: MethodScope,
+      ) -> StorageResult<String>
+      where
+        K: JwkStorage,
+        I: KeyIdStorage,
+      {
+        // Check for an existing method before generating a key, so we do not have to undo anything.
+        if let Some(fragment) = fragment {
+          if self.resolve_method(fragment, None).is_some() {
+            return Err(Error::FragmentAlreadyExists);
+          }
+        }
+
+        let (method, method_digest, key_id) =
+          generate_method_with_storage(self.id().clone(), storage, key_type, alg, fragment).await?;
+        let method_fragment: String = method
+          .id()
+          .fragment()
+          .expect("verification methods always have a fragment")
+          .to_owned();
+
+        // The fragment may have been derived from the key, in which case it can only be checked now.
+        if let Err(source) = self
+          .insert_method(method, scope)
+          .map_err(|_| Error::FragmentAlreadyExists)
+        {
+          return Err(try_undo_method_generation(storage, &method_digest, &key_id, source).await);
+        }
+
+        Ok(method_fragment)
+      }
+
+      async fn purge_method<K, I>(&mut self, storage: &Storage<K, I>, id: &DIDUrl) -> StorageResult<()>
+      where
+        K: JwkStorage,
+        I: KeyIdStorage,
+      {
+        let method: &VerificationMethod = self.resolve_method(id, None).ok_or(Error::MethodNotFound)?;
+        purge_method_from_storage(storage, method).await?;
+        self.remove_method(id);
+
+        Ok(())
+      }
+
+      async fn create_credential_jwt<K, I, T>(
+        &self,
+        credential: &Credential<T>,
+        storage: &Storage<K, I>,
+        fragment: &str,
+      ) -> StorageResult<Jwt>
+      where
+        K: JwkStorage,
+        I: KeyIdStorage,
+        T: ToOwned<Owned = T> + Serialize + Sync,
+      {
+        let method: &VerificationMethod = self.resolve_method(fragment, None).ok_or(Error::MethodNotFound)?;
+        let payload: String = credential
+          .serialize_jwt()
+          .map_err(|err| Error::EncodingError(err.into()))?;
+        sign_jws(storage, method, payload.as_bytes()).await
+      }
+
+      async fn create_presentation_jwt<K, I, T, U>(
+        &self,
+        presentation: &Presentation<T, U>,
+        storage: &Storage<K, I>,
+        fragment: &str,
+        options: &JwtPresentationOptions,
+      ) -> StorageResult<Jwt>
+      where
+        K: JwkStorage,
+        I: KeyIdStorage,
+        T: ToOwned<Owned = T> + Serialize + Sync,
+        U: Clone + Serialize + Sync,
+      {
+        let method: &VerificationMethod = self.resolve_method(fragment, None).ok_or(Error::MethodNotFound)?;
+        let payload: String = presentation
+          .serialize_jwt(options)
+          .map_err(|err| Error::EncodingError(err.into()))?;
+        sign_jws(storage, method, payload.as_bytes()).await
+      }
+    }
+  };
+}
+
+impl_jwk_document_ext!(CoreDocument);
+
+#[cfg(feature = "iota-document")]
+mod iota_document {
+  use super::*;
+  use identity_iota_core::IotaDocument;
+
+  impl_jwk_document_ext!(IotaDocument);
+}
+
+#[cfg(all(test, feature = "memstore"))]
+mod tests {
+  use crypto::signatures::ed25519::PublicKey;
+  use crypto::signatures::ed25519::Signature;
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_credential::credential::Subject;
+  use identity_did::CoreDID;
+  use identity_jose::jws::Decoder;
+  use identity_jose::jws::Token;
+  use identity_verification::MethodRelationship;
+
+  use super::*;
+  use crate::key_id_storage::KeyIdMemStore;
+  use crate::key_id_storage::KeyIdStorageErrorKind;
+  use crate::key_storage::JwkMemStore;
+  use crate::key_storage::KeyStorageErrorKind;
+  use crate::key_storage::ED25519_KEY_TYPE;
+
+  type MemStorage = Storage<JwkMemStore, KeyIdMemStore>;
+
+  fn storage() -> MemStorage {
+    Storage::new(JwkMemStore::new(), KeyIdMemStore::new())
+  }
+
+  fn core_document() -> CoreDocument {
+    CoreDocument::builder(Object::new())
+      .id("did:example:1234".parse::<CoreDID>().unwrap())
+      .build()
+      .unwrap()
+  }
+
+  async fn key_id(storage: &MemStorage, method: &VerificationMethod) -> KeyId {
+    storage
+      .key_id_storage()
+      .get_key_id(&MethodDigest::new(method).unwrap())
+      .await
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn generate_and_purge_method() {
+    let storage: MemStorage = storage();
+    let mut document: CoreDocument = core_document();
+
+    let fragment: String = document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        Some("key-1"),
+        MethodScope::VerificationRelationship(MethodRelationship::AssertionMethod),
+      )
+      .await
+      .unwrap();
+    assert_eq!(fragment, "key-1");
+
+    let method: VerificationMethod = document
+      .resolve_method("key-1", Some(MethodScope::assertion_method()))
+      .unwrap()
+      .clone();
+    let key_id: KeyId = key_id(&storage, &method).await;
+    assert_eq!(
+      method.data().try_public_key_jwk().unwrap().params(),
+      storage.key_storage().public(&key_id).await.unwrap().params()
+    );
+
+    document.purge_method(&storage, method.id()).await.unwrap();
+
+    assert!(document.resolve_method("key-1", None).is_none());
+    assert!(!storage.key_storage().exists(&key_id).await.unwrap());
+    let err = storage
+      .key_id_storage()
+      .get_key_id(&MethodDigest::new(&method).unwrap())
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdNotFound));
+  }
+
+  #[tokio::test]
+  async fn generate_method_thumbprint_fragment() {
+    let storage: MemStorage = storage();
+    let mut document: CoreDocument = core_document();
+
+    let fragment: String = document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        None,
+        MethodScope::VerificationMethod,
+      )
+      .await
+      .unwrap();
+
+    let method: &VerificationMethod = document.resolve_method(fragment.as_str(), None).unwrap();
+    let thumbprint: String = method.data().try_public_key_jwk().unwrap().thumbprint_b64().unwrap();
+    assert_eq!(fragment, thumbprint);
+  }
+
+  #[tokio::test]
+  async fn generate_method_existing_fragment() {
+    let storage: MemStorage = storage();
+    let mut document: CoreDocument = core_document();
+
+    document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        Some("key-1"),
+        MethodScope::VerificationMethod,
+      )
+      .await
+      .unwrap();
+
+    let err: Error = document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        Some("#key-1"),
+        MethodScope::VerificationMethod,
+      )
+      .await
+      .unwrap_err();
+    assert!(matches!(err, Error::FragmentAlreadyExists));
+    assert_eq!(document.methods(None).len(), 1);
+  }
+
+  #[tokio::test]
+  async fn generate_method_key_storage_failure() {
+    let storage: MemStorage = storage();
+    let mut document: CoreDocument = core_document();
+
+    let err: Error = document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::ES256,
+        None,
+        MethodScope::VerificationMethod,
+      )
+      .await
+      .unwrap_err();
+    assert!(
+      matches!(err, Error::KeyStorageError(ref err) if matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch))
+    );
+    assert!(document.methods(None).is_empty());
+  }
+
+  #[tokio::test]
+  async fn purge_method_unknown_key_id() {
+    let storage: MemStorage = storage();
+    let mut document: CoreDocument = core_document();
+
+    document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        Some("key-1"),
+        MethodScope::VerificationMethod,
+      )
+      .await
+      .unwrap();
+    let method: VerificationMethod = document.resolve_method("key-1", None).unwrap().clone();
+    let key_id: KeyId = key_id(&storage, &method).await;
+
+    // Purging with a storage that does not know the method must leave everything untouched.
+    let err: Error = document.purge_method(&self::storage(), method.id()).await.unwrap_err();
+    assert!(matches!(err, Error::KeyIdStorageError(_)));
+    assert!(document.resolve_method("key-1", None).is_some());
+    assert!(storage.key_storage().exists(&key_id).await.unwrap());
+  }
+
+  fn credential(issuer: &CoreDocument) -> Credential {
+    Credential::builder(Object::new())
+      .issuer(Url::parse(issuer.id().as_str()).unwrap())
+      .subject(Subject::with_id(Url::parse("did:example:subject").unwrap()))
+      .issuance_date(Timestamp::parse("2023-01-01T00:00:00Z").unwrap())
+      .build()
+      .unwrap()
+  }
+
+  /// Verifies the EdDSA signature of `jwt` with the key of `method` and returns the decoded token.
+  fn decode_jwt<'jwt>(jwt: &'jwt Jwt, method: &VerificationMethod) -> Token<'jwt> {
+    let x: Vec<u8> = jwu::decode_b64(&method.data().try_public_key_jwk().unwrap().try_okp_params().unwrap().x).unwrap();
+    let public_key: PublicKey = PublicKey::try_from_bytes(x.try_into().unwrap()).unwrap();
+    Decoder::new()
+      .decode(
+        &|_protected, _unprotected, message, signature| -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+          let signature: Signature = Signature::from_bytes(signature.try_into()?);
+          if public_key.verify(&signature, message) {
+            Ok(())
+          } else {
+            Err("invalid signature".into())
+          }
+        },
+        jwt.as_str().as_bytes(),
+      )
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn create_credential_jwt() {
+    let storage: MemStorage = storage();
+    let mut document: CoreDocument = core_document();
+    let fragment: String = document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        None,
+        MethodScope::assertion_method(),
+      )
+      .await
+      .unwrap();
+    let method: VerificationMethod = document.resolve_method(fragment.as_str(), None).unwrap().clone();
+    let credential: Credential = credential(&document);
+
+    let jwt: Jwt = document
+      .create_credential_jwt(&credential, &storage, &fragment)
+      .await
+      .unwrap();
+
+    let token: Token<'_> = decode_jwt(&jwt, &method);
+    let protected: JwsHeader = token.protected.unwrap();
+    assert_eq!(protected.alg(), Some(JwsAlgorithm::EdDSA));
+    assert_eq!(protected.kid(), Some(method.id().to_string().as_str()));
+    assert_eq!(protected.typ(), Some("JWT"));
+    assert_eq!(token.claims.as_ref(), credential.serialize_jwt().unwrap().as_bytes());
+  }
+
+  #[tokio::test]
+  async fn create_presentation_jwt() {
+    let storage: MemStorage = storage();
+    let mut document: CoreDocument = core_document();
+    document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        Some("key-1"),
+        MethodScope::authentication(),
+      )
+      .await
+      .unwrap();
+    let method: VerificationMethod = document.resolve_method("key-1", None).unwrap().clone();
+    let presentation: Presentation = Presentation::builder(Object::new())
+      .credential(credential(&document))
+      .holder(Url::parse(document.id().as_str()).unwrap())
+      .build()
+      .unwrap();
+    let options: JwtPresentationOptions = JwtPresentationOptions::new();
+
+    let jwt: Jwt = document
+      .create_presentation_jwt(&presentation, &storage, "key-1", &options)
+      .await
+      .unwrap();
+
+    let token: Token<'_> = decode_jwt(&jwt, &method);
+    assert_eq!(token.protected.unwrap().kid(), Some(method.id().to_string().as_str()));
+    assert_eq!(
+      token.claims.as_ref(),
+      presentation.serialize_jwt(&options).unwrap().as_bytes()
+    );
+  }
+
+  #[tokio::test]
+  async fn create_credential_jwt_unknown_method() {
+    let storage: MemStorage = storage();
+    let document: CoreDocument = core_document();
+
+    let err: Error = document
+      .create_credential_jwt(&credential(&document), &storage, "key-1")
+      .await
+      .unwrap_err();
+    assert!(matches!(err, Error::MethodNotFound));
+
+    // A method whose key is not held by the storage cannot be used for signing.
+    let mut document: CoreDocument = core_document();
+    document
+      .generate_method(
+        &self::storage(),
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        Some("key-1"),
+        MethodScope::assertion_method(),
+      )
+      .await
+      .unwrap();
+    let err: Error = document
+      .create_credential_jwt(&credential(&document), &storage, "key-1")
+      .await
+      .unwrap_err();
+    assert!(matches!(err, Error::KeyIdStorageError(_)));
+  }
+
+  #[cfg(feature = "iota-document")]
+  #[tokio::test]
+  async fn generate_and_purge_method_iota_document() {
+    use identity_iota_core::IotaDocument;
+    use identity_iota_core::NetworkName;
+
+    let storage: MemStorage = storage();
+    let mut document: IotaDocument = IotaDocument::new(&NetworkName::try_from("smr").unwrap());
+
+    let fragment: String = document
+      .generate_method(
+        &storage,
+        ED25519_KEY_TYPE,
+        JwsAlgorithm::EdDSA,
+        Some("key-1"),
+        MethodScope::VerificationMethod,
+      )
+      .await
+      .unwrap();
+
+    let method: VerificationMethod = document.resolve_method(fragment.as_str(), None).unwrap().clone();
+    let key_id: KeyId = key_id(&storage, &method).await;
+    document.purge_method(&storage, method.id()).await.unwrap();
+
+    assert!(document.methods(None).is_empty());
+    assert!(!storage.key_storage().exists(&key_id).await.unwrap());
+  }
+}
@@ -0,0 +1,13 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides extensions to DID documents that keep them consistent with a key and key id storage.
+
+mod error;
+mod jwk_document_ext;
+#[allow(clippy::module_inception)]
+mod storage;
+
+pub use error::*;
+pub use jwk_document_ext::*;
+pub use storage::*;
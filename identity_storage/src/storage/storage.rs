@@ -0,0 +1,31 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// A type wrapping a key and key id storage, typically used with [`JwkStorage`](crate::key_storage::JwkStorage) and
+/// [`KeyIdStorage`](crate::key_id_storage::KeyIdStorage) that should always be used together when calling methods
+/// from [`JwkDocumentExt`](crate::storage::JwkDocumentExt).
+#[derive(Debug)]
+pub struct Storage<K, I> {
+  key_storage: K,
+  key_id_storage: I,
+}
+
+impl<K, I> Storage<K, I> {
+  /// Constructs a new [`Storage`].
+  pub fn new(key_storage: K, key_id_storage: I) -> Self {
+    Self {
+      key_storage,
+      key_id_storage,
+    }
+  }
+
+  /// Obtain the wrapped key storage.
+  pub fn key_storage(&self) -> &K {
+    &self.key_storage
+  }
+
+  /// Obtain the wrapped key id storage.
+  pub fn key_id_storage(&self) -> &I {
+    &self.key_id_storage
+  }
+}
@@ -0,0 +1,33 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Utilities shared by the in-memory and file-based storage implementations.
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use tokio::sync::RwLock;
+use tokio::sync::RwLockReadGuard;
+use tokio::sync::RwLockWriteGuard;
+
+#[derive(Default)]
+pub struct Shared<T>(RwLock<T>);
+
+impl<T> Shared<T> {
+  pub fn new(data: T) -> Self {
+    Self(RwLock::new(data))
+  }
+
+  pub async fn read(&self) -> RwLockReadGuard<'_, T> {
+    self.0.read().await
+  }
+
+  pub async fn write(&self) -> RwLockWriteGuard<'_, T> {
+    self.0.write().await
+  }
+}
+
+impl<T: Debug> Debug for Shared<T> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    Debug::fmt(&self.0, f)
+  }
+}
@@ -25,4 +25,12 @@ pub enum Error {
   InvalidKeyDataBase58,
   #[error("Invalid Multibase Key Data")]
   InvalidKeyDataMultibase,
+  #[error("the method data could not be transformed to the desired type: {0}")]
+  InvalidMethodDataTransformation(&'static str),
+  /// Caused by key material that is not a JSON Web Key.
+  #[error("verification material format not supported")]
+  NotPublicKeyJwk,
+  /// Caused by attempting to construct a verification method from a JWK with private key components.
+  #[error("the verification method would expose private key material")]
+  PrivateKeyMaterialExposed,
 }
@@ -24,4 +24,5 @@ mod error;
 pub mod verification_method;
 pub use error::Error;
 pub use error::Result;
+pub use identity_jose as jose;
 pub use verification_method::*;
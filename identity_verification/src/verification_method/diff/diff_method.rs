@@ -164,6 +164,8 @@ mod test {
   use identity_core::convert::FromJson;
   use identity_core::convert::ToJson;
   use identity_core::diff::DiffString;
+  use identity_jose::jwk::Jwk;
+  use identity_jose::jwk::JwkType;
 
   use super::*;
 
@@ -332,6 +334,26 @@ mod test {
     assert_eq!(merge, new);
   }
 
+  #[test]
+  fn test_data_jwk() {
+    let method = test_method();
+    let mut jwk: Jwk = Jwk::new(JwkType::Okp);
+    jwk.set_kid("key");
+    let mut new = method.clone();
+    *new.data_mut() = MethodData::PublicKeyJwk(jwk.clone());
+
+    let diff = method.diff(&new).unwrap();
+    assert_eq!(diff.data, Some(DiffMethodData::PublicKeyJwk(Some(jwk))));
+    let merge = method.merge(diff).unwrap();
+    assert_eq!(merge, new);
+
+    // An unchanged key yields an empty diff, which cannot be converted without the original key.
+    let diff = new.data().diff(new.data()).unwrap();
+    assert_eq!(diff, DiffMethodData::PublicKeyJwk(None));
+    assert_eq!(new.data().merge(diff.clone()).unwrap(), *new.data());
+    assert!(MethodData::from_diff(diff).is_err());
+  }
+
   #[test]
   fn test_from_diff() {
     let method = test_method();
@@ -4,6 +4,7 @@
 use core::fmt::Debug;
 use core::fmt::Formatter;
 use identity_core::utils::BaseEncoding;
+use identity_jose::jwk::Jwk;
 
 use crate::error::Error;
 use crate::error::Result;
@@ -12,9 +13,11 @@ use crate::error::Result;
 #[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
+#[allow(clippy::large_enum_variant)]
 pub enum MethodData {
   PublicKeyMultibase(String),
   PublicKeyBase58(String),
+  PublicKeyJwk(Jwk),
 }
 
 impl MethodData {
@@ -37,15 +40,33 @@ impl MethodData {
   /// # Errors
   ///
   /// Decoding can fail if `MethodData` has invalid content or cannot be
-  /// represented as a vector of bytes.
+  /// represented as a vector of bytes. In particular, the `PublicKeyJwk` variant
+  /// is not base-encoded and always results in an error.
   pub fn try_decode(&self) -> Result<Vec<u8>> {
     match self {
+      Self::PublicKeyJwk(_) => Err(Error::InvalidMethodDataTransformation(
+        "method data is not base encoded",
+      )),
       Self::PublicKeyMultibase(input) => {
         BaseEncoding::decode_multibase(input).map_err(|_| Error::InvalidKeyDataMultibase)
       }
       Self::PublicKeyBase58(input) => BaseEncoding::decode_base58(input).map_err(|_| Error::InvalidKeyDataBase58),
     }
   }
+
+  /// Returns the wrapped `Jwk` if the format is `PublicKeyJwk`.
+  pub fn public_key_jwk(&self) -> Option<&Jwk> {
+    if let Self::PublicKeyJwk(ref jwk) = self {
+      Some(jwk)
+    } else {
+      None
+    }
+  }
+
+  /// Fallible version of [`Self::public_key_jwk`].
+  pub fn try_public_key_jwk(&self) -> Result<&Jwk> {
+    self.public_key_jwk().ok_or(Error::NotPublicKeyJwk)
+  }
 }
 
 impl Debug for MethodData {
@@ -53,6 +74,7 @@ impl Debug for MethodData {
     match self {
       Self::PublicKeyMultibase(inner) => f.write_fmt(format_args!("PublicKeyMultibase({inner})")),
       Self::PublicKeyBase58(inner) => f.write_fmt(format_args!("PublicKeyBase58({inner})")),
+      Self::PublicKeyJwk(inner) => f.write_fmt(format_args!("PublicKeyJwk({inner:?})")),
     }
   }
 }
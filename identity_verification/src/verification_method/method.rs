@@ -3,6 +3,7 @@
 
 use core::fmt::Display;
 use core::fmt::Formatter;
+use std::borrow::Cow;
 
 use serde::de;
 use serde::Deserialize;
@@ -13,6 +14,7 @@ use identity_core::common::Object;
 use identity_core::convert::FmtJson;
 use identity_core::crypto::KeyType;
 use identity_core::crypto::PublicKey;
+use identity_jose::jwk::Jwk;
 
 use crate::error::Error;
 use crate::error::Result;
@@ -207,6 +209,40 @@ impl VerificationMethod {
     }
     builder.build()
   }
+
+  /// Creates a new [`VerificationMethod`] of type [`MethodType::JSON_WEB_KEY_2020`] from the given `did` and
+  /// public key represented as a [`Jwk`].
+  ///
+  /// If `fragment` is `None` or empty, the [JWK thumbprint](Jwk::thumbprint_b64) of `key` is used as the fragment.
+  ///
+  /// # Errors
+  /// [`Error::PrivateKeyMaterialExposed`] if `key` contains private key components.
+  pub fn new_from_jwk<D: DID>(did: D, key: Jwk, fragment: Option<&str>) -> Result<Self> {
+    if !key.is_public() {
+      return Err(Error::PrivateKeyMaterialExposed);
+    }
+
+    let fragment: Cow<'_, str> = match fragment.map(|fragment| fragment.trim_start_matches('#')) {
+      Some(fragment) if !fragment.is_empty() => Cow::Borrowed(fragment),
+      _ => Cow::Owned(
+        key
+          .thumbprint_b64()
+          .map_err(|_| Error::InvalidMethod("unable to compute the jwk thumbprint"))?,
+      ),
+    };
+
+    let id: DIDUrl = did
+      .to_url()
+      .join(format!("#{fragment}"))
+      .map_err(Error::DIDUrlConstructionError)?;
+
+    MethodBuilder::default()
+      .id(id)
+      .controller(did.into())
+      .type_(MethodType::JSON_WEB_KEY_2020)
+      .data(MethodData::PublicKeyJwk(key))
+      .build()
+  }
 }
 
 impl Display for VerificationMethod {
@@ -229,3 +265,60 @@ impl KeyComparable for VerificationMethod {
     self.id()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use identity_jose::jwk::EdCurve;
+  use identity_jose::jwk::JwkParamsOkp;
+
+  use super::*;
+
+  fn ed25519_jwk() -> Jwk {
+    let mut params = JwkParamsOkp::new();
+    params.crv = EdCurve::Ed25519.name().to_owned();
+    params.x = "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".to_owned();
+    Jwk::from_params(params)
+  }
+
+  #[test]
+  fn test_new_from_jwk_fragment() {
+    let did: CoreDID = "did:example:123".parse().unwrap();
+    let jwk: Jwk = ed25519_jwk();
+
+    let method: VerificationMethod = VerificationMethod::new_from_jwk(did.clone(), jwk.clone(), None).unwrap();
+    assert_eq!(method.id().fragment().unwrap(), jwk.thumbprint_b64().unwrap());
+    assert_eq!(method.type_(), &MethodType::JSON_WEB_KEY_2020);
+    assert_eq!(method.data().try_public_key_jwk().unwrap(), &jwk);
+    assert!(method.data().try_decode().is_err());
+
+    let method: VerificationMethod = VerificationMethod::new_from_jwk(did.clone(), jwk.clone(), Some("")).unwrap();
+    assert_eq!(method.id().fragment().unwrap(), jwk.thumbprint_b64().unwrap());
+
+    let method: VerificationMethod = VerificationMethod::new_from_jwk(did, jwk, Some("#key-1")).unwrap();
+    assert_eq!(method.id().fragment().unwrap(), "key-1");
+  }
+
+  #[test]
+  fn test_new_from_jwk_rejects_private_key() {
+    let did: CoreDID = "did:example:123".parse().unwrap();
+    let mut jwk: Jwk = ed25519_jwk();
+    jwk.try_okp_params_mut().unwrap().d = Some("nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A".to_owned());
+
+    let err: Error = VerificationMethod::new_from_jwk(did, jwk, None).unwrap_err();
+    assert!(matches!(err, Error::PrivateKeyMaterialExposed));
+  }
+
+  #[test]
+  fn test_jwk_method_serde() {
+    let did: CoreDID = "did:example:123".parse().unwrap();
+    let method: VerificationMethod = VerificationMethod::new_from_jwk(did, ed25519_jwk(), Some("key-1")).unwrap();
+
+    let json: serde_json::Value = serde_json::to_value(&method).unwrap();
+    assert_eq!(json["type"], MethodType::JSON_WEB_KEY_2020.as_str());
+    assert_eq!(json["publicKeyJwk"]["kty"], "OKP");
+    assert_eq!(json["publicKeyJwk"]["crv"], "Ed25519");
+
+    let deserialized: VerificationMethod = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, method);
+  }
+}
@@ -11,6 +11,8 @@ use crate::error::Result;
 
 const ED25519_VERIFICATION_KEY_2018_STR: &str = "Ed25519VerificationKey2018";
 const X25519_KEY_AGREEMENT_KEY_2019_STR: &str = "X25519KeyAgreementKey2019";
+const JSON_WEB_KEY_2020_STR: &str = "JsonWebKey2020";
+const MULTIKEY_STR: &str = "Multikey";
 
 /// Supported verification method types.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
@@ -21,6 +23,12 @@ impl MethodType {
   pub const ED25519_VERIFICATION_KEY_2018: Self = Self(Cow::Borrowed(ED25519_VERIFICATION_KEY_2018_STR));
   // The `X25519KeyAgreementKey2019` method type.
   pub const X25519_KEY_AGREEMENT_KEY_2019: Self = Self(Cow::Borrowed(X25519_KEY_AGREEMENT_KEY_2019_STR));
+  /// A verification method for use with JWT verification as prescribed by the [`Jwk`](::identity_jose::jwk::Jwk)
+  /// in the [`publicKeyJwk`](crate::MethodData::PublicKeyJwk) entry.
+  pub const JSON_WEB_KEY_2020: Self = Self(Cow::Borrowed(JSON_WEB_KEY_2020_STR));
+  /// A verification method whose [`publicKeyMultibase`](crate::MethodData::PublicKeyMultibase) entry is a
+  /// [Multikey](https://www.w3.org/TR/vc-data-integrity/#multikey), i.e. a multicodec-prefixed public key.
+  pub const MULTIKEY: Self = Self(Cow::Borrowed(MULTIKEY_STR));
 }
 
 impl MethodType {
@@ -48,6 +56,8 @@ impl FromStr for MethodType {
     match string {
       ED25519_VERIFICATION_KEY_2018_STR => Ok(Self::ED25519_VERIFICATION_KEY_2018),
       X25519_KEY_AGREEMENT_KEY_2019_STR => Ok(Self::X25519_KEY_AGREEMENT_KEY_2019),
+      JSON_WEB_KEY_2020_STR => Ok(Self::JSON_WEB_KEY_2020),
+      MULTIKEY_STR => Ok(Self::MULTIKEY),
       _ => Ok(Self(Cow::Owned(string.to_owned()))),
     }
   }
@@ -64,6 +74,8 @@ mod tests {
     for method_type in [
       MethodType::ED25519_VERIFICATION_KEY_2018,
       MethodType::X25519_KEY_AGREEMENT_KEY_2019,
+      MethodType::JSON_WEB_KEY_2020,
+      MethodType::MULTIKEY,
     ] {
       let ser: Value = serde_json::to_value(method_type.clone()).unwrap();
       assert_eq!(ser.as_str().unwrap(), method_type.as_str());